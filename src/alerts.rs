@@ -0,0 +1,607 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sysinfo::Disks;
+
+use crate::config::{config, AlertRuleConfig};
+use crate::services::{collect_services, start_service_watcher};
+use crate::system::{collect_mount_rows, disks_table_filtered};
+use crate::types::{AppState, DiskRow, ServiceHealth, ServiceRow, ServiceScope, VmSnapshot};
+use crate::utils::{percent, unix_ms_now};
+
+const MAX_EVENTS: usize = 200;
+const MOUNTS_EVERY: Duration = Duration::from_secs(5);
+
+// ── Rule syntax ───────────────────────────────────────────────────────────────
+//
+//   <metric> <op> <value> [for <duration>]
+//   mount <path> <field> <op> <value> [for <duration>]
+//   service <unit> <field> <op> <value> [for <duration>]
+//
+// metric: cpu mem swap load1 load5 load15 psi_cpu psi_mem psi_io
+// mount field: use_pct read_bps write_bps · service field: health active sub
+// op: > >= < <= == != · duration: 30s 5m 1h
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmMetric {
+    Cpu,
+    Mem,
+    Swap,
+    Load1,
+    Load5,
+    Load15,
+    PsiCpu,
+    PsiMem,
+    PsiIo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountField {
+    UsePct,
+    ReadBps,
+    WriteBps,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceField {
+    Health,
+    Active,
+    Sub,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertSubject {
+    Vm(VmMetric),
+    Mount { mount: String, field: MountField },
+    Service { unit: String, field: ServiceField },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Threshold {
+    Number(f64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub name: String,
+    pub expr: String,
+    pub subject: AlertSubject,
+    pub cmp: Cmp,
+    pub threshold: Threshold,
+    pub hold: Duration,
+}
+
+fn parse_vm_metric(token: &str) -> Option<VmMetric> {
+    Some(match token {
+        "cpu" => VmMetric::Cpu,
+        "mem" | "memory" => VmMetric::Mem,
+        "swap" => VmMetric::Swap,
+        "load1" => VmMetric::Load1,
+        "load5" => VmMetric::Load5,
+        "load15" => VmMetric::Load15,
+        "psi_cpu" => VmMetric::PsiCpu,
+        "psi_mem" | "psi_memory" => VmMetric::PsiMem,
+        "psi_io" => VmMetric::PsiIo,
+        _ => return None,
+    })
+}
+
+fn parse_cmp(token: &str) -> Result<Cmp, String> {
+    Ok(match token {
+        ">" => Cmp::Gt,
+        ">=" => Cmp::Ge,
+        "<" => Cmp::Lt,
+        "<=" => Cmp::Le,
+        "==" | "=" => Cmp::Eq,
+        "!=" => Cmp::Ne,
+        _ => {
+            return Err(format!(
+                "expected a comparison (> >= < <= == !=), got `{token}`"
+            ))
+        }
+    })
+}
+
+pub fn parse_duration(token: &str) -> Result<Duration, String> {
+    let split = token
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(token.len());
+    let (num, unit) = token.split_at(split);
+    let n: u64 = num
+        .parse()
+        .map_err(|_| format!("invalid duration `{token}`"))?;
+    let scale = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => return Err(format!("invalid duration `{token}` (use s, m or h)")),
+    };
+    n.checked_mul(scale)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("invalid duration `{token}`"))
+}
+
+pub fn parse_rule(name: Option<&str>, expr: &str) -> Result<AlertRule, String> {
+    let tokens: Vec<&str> = expr.split_whitespace().collect();
+    let (subject, rest) = match tokens.first().copied() {
+        Some("mount") => {
+            let (Some(mount), Some(field)) = (tokens.get(1), tokens.get(2)) else {
+                return Err("expected `mount <path> <field> <op> <value>`".to_string());
+            };
+            let field = match *field {
+                "use_pct" | "use" => MountField::UsePct,
+                "read_bps" => MountField::ReadBps,
+                "write_bps" => MountField::WriteBps,
+                _ => return Err(format!("unknown mount field `{field}`")),
+            };
+            let subject = AlertSubject::Mount {
+                mount: mount.to_string(),
+                field,
+            };
+            (subject, &tokens[3..])
+        }
+        Some("service") => {
+            let (Some(unit), Some(field)) = (tokens.get(1), tokens.get(2)) else {
+                return Err("expected `service <unit> <field> <op> <value>`".to_string());
+            };
+            let field = match *field {
+                "health" => ServiceField::Health,
+                "active" => ServiceField::Active,
+                "sub" => ServiceField::Sub,
+                _ => return Err(format!("unknown service field `{field}`")),
+            };
+            let subject = AlertSubject::Service {
+                unit: unit.to_string(),
+                field,
+            };
+            (subject, &tokens[3..])
+        }
+        Some(metric) => match parse_vm_metric(metric) {
+            Some(m) => (AlertSubject::Vm(m), &tokens[1..]),
+            None => return Err(format!("unknown metric `{metric}`")),
+        },
+        None => return Err("empty rule".to_string()),
+    };
+
+    let (Some(op), Some(value)) = (rest.first(), rest.get(1)) else {
+        return Err("expected `<op> <value>` after the subject".to_string());
+    };
+    let cmp = parse_cmp(op)?;
+    let threshold = if matches!(subject, AlertSubject::Service { .. }) {
+        if !matches!(cmp, Cmp::Eq | Cmp::Ne) {
+            return Err("service fields only support == and !=".to_string());
+        }
+        Threshold::Text(value.to_ascii_lowercase())
+    } else {
+        let n = value
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("expected a number, got `{value}`"))?;
+        Threshold::Number(n)
+    };
+
+    let hold = match &rest[2..] {
+        [] => Duration::ZERO,
+        ["for", d] => parse_duration(d)?,
+        other => return Err(format!("unexpected `{}`", other.join(" "))),
+    };
+
+    Ok(AlertRule {
+        name: name
+            .map(str::to_string)
+            .unwrap_or_else(|| expr.split_whitespace().collect::<Vec<_>>().join(" ")),
+        expr: expr.trim().to_string(),
+        subject,
+        cmp,
+        threshold,
+        hold,
+    })
+}
+
+// ── Observation ───────────────────────────────────────────────────────────────
+
+enum Observed {
+    Number(f64),
+    Text(String),
+}
+
+fn health_label(h: ServiceHealth) -> &'static str {
+    match h {
+        ServiceHealth::Healthy => "healthy",
+        ServiceHealth::Warning => "warning",
+        ServiceHealth::Critical => "critical",
+    }
+}
+
+fn observe(
+    subject: &AlertSubject,
+    vm: &VmSnapshot,
+    mounts: &[DiskRow],
+    services: &[ServiceRow],
+) -> Option<Observed> {
+    match subject {
+        AlertSubject::Vm(metric) => {
+            let v = match metric {
+                VmMetric::Cpu => vm.cpu_usage as f64,
+                VmMetric::Mem => vm.memory_percent,
+                VmMetric::Swap => {
+                    if vm.total_swap == 0 {
+                        return None;
+                    }
+                    percent(vm.used_swap, vm.total_swap)
+                }
+                VmMetric::Load1 => vm.load_avg_one,
+                VmMetric::Load5 => vm.load_avg_five,
+                VmMetric::Load15 => vm.load_avg_fifteen,
+                VmMetric::PsiCpu => vm.psi.cpu?.some.avg10 as f64,
+                VmMetric::PsiMem => vm.psi.memory?.some.avg10 as f64,
+                VmMetric::PsiIo => vm.psi.io?.some.avg10 as f64,
+            };
+            Some(Observed::Number(v))
+        }
+        AlertSubject::Mount { mount, field } => {
+            let row = mounts.iter().find(|r| r.mount == *mount)?;
+            Some(Observed::Number(match field {
+                MountField::UsePct => row.use_pct,
+                MountField::ReadBps => row.read_bps as f64,
+                MountField::WriteBps => row.write_bps as f64,
+            }))
+        }
+        AlertSubject::Service { unit, field } => {
+            let row = services.iter().find(|r| r.name == *unit)?;
+            Some(Observed::Text(match field {
+                ServiceField::Health => health_label(row.health).to_string(),
+                ServiceField::Active => row.active_state.to_ascii_lowercase(),
+                ServiceField::Sub => row.sub_state.to_ascii_lowercase(),
+            }))
+        }
+    }
+}
+
+fn matches(observed: &Observed, cmp: Cmp, threshold: &Threshold) -> bool {
+    match (observed, threshold) {
+        (Observed::Number(v), Threshold::Number(t)) => match cmp {
+            Cmp::Gt => v > t,
+            Cmp::Ge => v >= t,
+            Cmp::Lt => v < t,
+            Cmp::Le => v <= t,
+            Cmp::Eq => v == t,
+            Cmp::Ne => v != t,
+        },
+        (Observed::Text(v), Threshold::Text(t)) => match cmp {
+            Cmp::Eq => v == t,
+            Cmp::Ne => v != t,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn format_observed(observed: &Observed) -> String {
+    match observed {
+        Observed::Number(v) => format!("{v:.1}"),
+        Observed::Text(s) => s.clone(),
+    }
+}
+
+// ── Engine ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    Ok,
+    Pending,
+    Firing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertEventKind {
+    Fired,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertEvent {
+    pub name: String,
+    pub rule: String,
+    pub kind: AlertEventKind,
+    pub at_unix_ms: u64,
+    pub value: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertRuleState {
+    pub rule: AlertRule,
+    pub status: AlertStatus,
+    pub matched_since_ms: Option<u64>,
+    pub fired_at_ms: Option<u64>,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AlertEngine {
+    pub rules: Vec<AlertRuleState>,
+    /// Newest last; capped so a flapping rule can't grow it without bound.
+    pub events: VecDeque<AlertEvent>,
+}
+
+impl AlertEngine {
+    /// Rules were validated when the config was loaded, so bad ones are skipped here.
+    pub fn from_config(rules: &[AlertRuleConfig]) -> Self {
+        Self {
+            rules: rules
+                .iter()
+                .filter_map(|r| parse_rule(r.name.as_deref(), &r.rule).ok())
+                .map(|rule| AlertRuleState {
+                    rule,
+                    status: AlertStatus::Ok,
+                    matched_since_ms: None,
+                    fired_at_ms: None,
+                    value: None,
+                })
+                .collect(),
+            events: VecDeque::new(),
+        }
+    }
+
+    pub fn needs_services(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|r| matches!(r.rule.subject, AlertSubject::Service { .. }))
+    }
+
+    pub fn needs_mounts(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|r| matches!(r.rule.subject, AlertSubject::Mount { .. }))
+    }
+
+    pub fn firing(&self) -> impl Iterator<Item = &AlertRuleState> {
+        self.rules
+            .iter()
+            .filter(|r| r.status == AlertStatus::Firing)
+    }
+
+    /// Advances every rule by one sample and returns the fire/resolve transitions.
+    pub fn evaluate(
+        &mut self,
+        vm: &VmSnapshot,
+        mounts: &[DiskRow],
+        services: &[ServiceRow],
+        now_ms: u64,
+    ) -> Vec<AlertEvent> {
+        let mut out = Vec::new();
+        for state in &mut self.rules {
+            let observed = observe(&state.rule.subject, vm, mounts, services);
+            state.value = observed.as_ref().map(format_observed);
+            let hit = observed
+                .as_ref()
+                .map(|o| matches(o, state.rule.cmp, &state.rule.threshold))
+                .unwrap_or(false);
+
+            let event_kind = if hit {
+                let since = *state.matched_since_ms.get_or_insert(now_ms);
+                let held = now_ms.saturating_sub(since) >= state.rule.hold.as_millis() as u64;
+                match state.status {
+                    AlertStatus::Firing => None,
+                    _ if held => {
+                        state.status = AlertStatus::Firing;
+                        state.fired_at_ms = Some(now_ms);
+                        Some(AlertEventKind::Fired)
+                    }
+                    _ => {
+                        state.status = AlertStatus::Pending;
+                        None
+                    }
+                }
+            } else {
+                state.matched_since_ms = None;
+                let was_firing = state.status == AlertStatus::Firing;
+                state.status = AlertStatus::Ok;
+                was_firing.then_some(AlertEventKind::Resolved)
+            };
+
+            if let Some(kind) = event_kind {
+                out.push(AlertEvent {
+                    name: state.rule.name.clone(),
+                    rule: state.rule.expr.clone(),
+                    kind,
+                    at_unix_ms: now_ms,
+                    value: state.value.clone().unwrap_or_else(|| "n/a".to_string()),
+                });
+            }
+        }
+
+        self.events.extend(out.iter().cloned());
+        while self.events.len() > MAX_EVENTS {
+            self.events.pop_front();
+        }
+        out
+    }
+}
+
+// ── TUI glue ──────────────────────────────────────────────────────────────────
+
+/// System units for service rules. The services screen's rows follow its
+/// scope, so they would drop every system unit when it shows the user
+/// manager; the watcher's list, or a System-scope listing of our own, doesn't.
+fn alert_service_rows(app: &mut AppState) -> Vec<ServiceRow> {
+    start_service_watcher(app);
+    {
+        let state = app.service_state.inner.lock().unwrap();
+        if state.live {
+            return state.system_rows.clone();
+        }
+    }
+    let due = app
+        .alert_last_services_at
+        .map(|t| t.elapsed() >= Duration::from_secs(config().refresh.services_secs))
+        .unwrap_or(true);
+    if due {
+        app.alert_last_services_at = Some(Instant::now());
+        let rows = app.alert_service_rows.clone();
+        std::thread::spawn(move || {
+            // A failed listing keeps the last rows rather than resolving everything.
+            if let Ok(list) = collect_services(ServiceScope::System) {
+                *rows.lock().unwrap() = list;
+            }
+        });
+    }
+    app.alert_service_rows.lock().unwrap().clone()
+}
+
+/// Collects whatever the configured rules need and evaluates them once.
+/// Services and mounts are refreshed here too, since their own screens may be closed.
+pub fn evaluate_app_alerts(app: &mut AppState, vm: &VmSnapshot, disks: &Disks) -> Vec<AlertEvent> {
+    if app.alerts.rules.is_empty() {
+        return Vec::new();
+    }
+    let services = if app.alerts.needs_services() {
+        alert_service_rows(app)
+    } else {
+        Vec::new()
+    };
+    if app.alerts.needs_mounts() {
+        let due = app
+            .alert_last_mounts_at
+            .map(|t| t.elapsed() >= MOUNTS_EVERY)
+            .unwrap_or(true);
+        if due {
+            app.alert_mount_rows = collect_mount_rows(64, true)
+                .unwrap_or_else(|| disks_table_filtered(disks, 64, true));
+            app.alert_last_mounts_at = Some(Instant::now());
+        }
+    }
+    app.alerts
+        .evaluate(vm, &app.alert_mount_rows, &services, unix_ms_now())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Psi;
+
+    fn vm(cpu: f32) -> VmSnapshot {
+        VmSnapshot {
+            cpu_usage: cpu,
+            cpu_cores: 1,
+            core_usage: Vec::new(),
+            cpu_breakdown: None,
+            psi: Psi::default(),
+            load_avg_one: 0.0,
+            load_avg_five: 0.0,
+            load_avg_fifteen: 0.0,
+            total_memory: 100,
+            used_memory: 50,
+            available_memory: 50,
+            memory_percent: 50.0,
+            total_swap: 0,
+            used_swap: 0,
+            uptime_secs: 0,
+        }
+    }
+
+    #[test]
+    fn parse_rule_accepts_each_subject_kind() {
+        let cpu = parse_rule(None, "cpu > 90% for 60s").unwrap();
+        assert_eq!(cpu.subject, AlertSubject::Vm(VmMetric::Cpu));
+        assert_eq!(cpu.threshold, Threshold::Number(90.0));
+        assert_eq!(cpu.hold, Duration::from_secs(60));
+
+        let mount = parse_rule(Some("root"), "mount / use_pct > 85").unwrap();
+        assert_eq!(mount.name, "root");
+        assert_eq!(mount.hold, Duration::ZERO);
+
+        let svc = parse_rule(None, "service nginx.service health == Critical").unwrap();
+        assert_eq!(svc.threshold, Threshold::Text("critical".to_string()));
+
+        assert!(parse_rule(None, "cpu >> 90").is_err());
+        assert!(parse_rule(None, "service x health > ok").is_err());
+        assert!(parse_rule(None, "cpu > 90 for 5d").is_err());
+        assert_eq!(
+            parse_duration("18446744073709551615h"),
+            Err("invalid duration `18446744073709551615h`".to_string())
+        );
+    }
+
+    #[test]
+    fn rule_fires_after_hold_and_resolves() {
+        let mut engine = AlertEngine::from_config(&[AlertRuleConfig {
+            name: None,
+            rule: "cpu > 90 for 60s".to_string(),
+        }]);
+
+        assert!(engine.evaluate(&vm(95.0), &[], &[], 0).is_empty());
+        assert_eq!(engine.rules[0].status, AlertStatus::Pending);
+
+        let fired = engine.evaluate(&vm(95.0), &[], &[], 60_000);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].kind, AlertEventKind::Fired);
+        assert!(engine.evaluate(&vm(99.0), &[], &[], 61_000).is_empty());
+
+        let resolved = engine.evaluate(&vm(10.0), &[], &[], 62_000);
+        assert_eq!(resolved[0].kind, AlertEventKind::Resolved);
+        assert_eq!(engine.rules[0].status, AlertStatus::Ok);
+        assert_eq!(engine.events.len(), 2);
+    }
+
+    #[test]
+    fn switching_service_scope_does_not_resolve_system_rules() {
+        let failed = ServiceRow {
+            name: "nginx.service".to_string(),
+            description: String::new(),
+            load_state: "loaded".to_string(),
+            active_state: "failed".to_string(),
+            sub_state: "failed".to_string(),
+            restarts: 0,
+            last_change: String::new(),
+            health: ServiceHealth::Critical,
+            usage: Default::default(),
+            user: false,
+            control_group: String::new(),
+        };
+        let mut app = AppState {
+            alerts: AlertEngine::from_config(&[AlertRuleConfig {
+                name: None,
+                rule: "service nginx.service health == critical".to_string(),
+            }]),
+            service_watcher_started: true,
+            ..AppState::default()
+        };
+        {
+            let mut state = app.service_state.inner.lock().unwrap();
+            state.live = true;
+            state.replace_system_rows(vec![failed]);
+        }
+        let disks = Disks::new();
+
+        let fired = evaluate_app_alerts(&mut app, &vm(0.0), &disks);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].kind, AlertEventKind::Fired);
+
+        // `u` to the user manager: the screen's rows lose every system unit.
+        app.service_scope = ServiceScope::User;
+        {
+            let mut state = app.service_state.inner.lock().unwrap();
+            state.scope = ServiceScope::User;
+            state.rows.clear();
+        }
+        assert!(evaluate_app_alerts(&mut app, &vm(0.0), &disks).is_empty());
+        assert_eq!(app.alerts.rules[0].status, AlertStatus::Firing);
+    }
+}
@@ -1,6 +1,4 @@
-use std::cmp::Reverse;
 use std::io;
-use std::process::Command;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
@@ -9,22 +7,86 @@ use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::Terminal;
 use sysinfo::{Disks, System};
 
+use crate::alerts::evaluate_app_alerts;
+use crate::config::config;
 use crate::disk::{enter_selected_disk_dir, navigate_disk_up, start_disk_scan};
+use crate::logfollow::{stop_log_follow_off_screen, toggle_log_follow};
+use crate::logsearch::handle_log_search_key;
+use crate::network::{push_net_history, update_net_rates};
+use crate::notify::dispatch_alert_events;
+use crate::procaction::{
+    handle_kill_confirm_key, handle_proc_action_key, open_kill_confirm, open_proc_action_menu,
+};
+use crate::procinfo::{handle_proc_detail_key, open_proc_detail, refresh_proc_detail};
+use crate::procrestart::{handle_restart_confirm_key, open_restart_confirm, take_restart_result};
+use crate::proctree::handle_proc_tree_key;
+use crate::procview::{move_proc_selection, selected_proc_row};
 use crate::services::{
-    handle_proc_search_key, handle_service_search_key, open_logs_for_selected_service,
-    refresh_logs, refresh_services,
+    handle_proc_search_key, handle_service_search_key, next_service_scope,
+    open_logs_for_selected_service, refresh_logs, refresh_services, set_service_scope,
+};
+use crate::svcaction::handle_service_action_key;
+use crate::svcinfo::handle_service_detail_key;
+use crate::svcusage::{next_service_sort, refresh_service_usage};
+use crate::system::{
+    collect_proc_rows, format_snapshot, read_psi, refresh, refresh_dashboard_cache, snapshot,
+    update_cpu_breakdown, update_disk_io_rates, update_proc_io_rates,
 };
-use crate::system::{format_snapshot, refresh, snapshot, update_disk_io_rates};
+use crate::timers::refresh_timers;
 use crate::types::{
-    AppState, DiskTarget, LogSeverity, LogUnitFilter, ProcRow, ProcSort, Screen, ServiceFilter,
+    AppState, DiskTarget, LogSeverity, LogUnitFilter, ProcSort, Screen, ServiceFilter, VmSnapshot,
 };
 use crate::ui::{
-    render_dashboard, render_disk_dive, render_footer, render_header, render_help, render_logs,
-    render_processes, render_services, render_too_small,
+    render_alerts, render_dashboard, render_disk_dive, render_footer, render_header, render_help,
+    render_logs, render_network, render_processes, render_services, render_timers,
+    render_too_small,
 };
 use crate::update::perform_self_update;
 use crate::utils::push_history_sample;
 
+/// Lays out header, active screen and footer; shared by the live loop and replay.
+pub fn draw_screen(frame: &mut ratatui::Frame, app: &mut AppState, vm: &VmSnapshot) {
+    let size = frame.size();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(8),
+            Constraint::Length(if app.show_help { 7 } else { 1 }),
+        ])
+        .margin(1)
+        .split(size);
+
+    // Header
+    frame.render_widget(render_header(app), rows[0]);
+
+    // If terminal is too small, render a friendly message instead of a broken layout.
+    if rows[1].width < 80 || rows[1].height < 14 {
+        render_too_small(frame, rows[1]);
+        // Footer/help still renders below.
+        return;
+    }
+
+    // Main
+    match app.screen {
+        Screen::Dashboard => render_dashboard(frame, rows[1], vm, app),
+        Screen::Processes => render_processes(frame, rows[1], app),
+        Screen::DiskDive => render_disk_dive(frame, rows[1], app),
+        Screen::Services => render_services(frame, rows[1], app),
+        Screen::Timers => render_timers(frame, rows[1], app),
+        Screen::Logs => render_logs(frame, rows[1], app),
+        Screen::Network => render_network(frame, rows[1], app),
+        Screen::Alerts => render_alerts(frame, rows[1], app),
+    }
+
+    // Footer/help
+    if app.show_help {
+        frame.render_widget(render_help(app), rows[2]);
+    } else {
+        frame.render_widget(render_footer(app), rows[2]);
+    }
+}
+
 pub fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     system: &mut System,
@@ -36,11 +98,13 @@ pub fn run_app(
     // Keep the dashboard cheap: refresh processes + fs scan on a slower cadence.
     let dash_proc_every = Duration::from_secs(3);
     let dash_history_every = tick_rate.max(Duration::from_millis(500));
+    let history_len = config().general.history_len;
     let mut tip_clock = Instant::now();
 
     loop {
         // Refresh data (keep it cheap; process refresh only when on the processes screen)
-        if last_tick.elapsed() >= tick_rate {
+        let ticked = last_tick.elapsed() >= tick_rate;
+        if ticked {
             let refresh_processes = if matches!(app.screen, Screen::Processes) {
                 true
             } else if matches!(app.screen, Screen::Dashboard) {
@@ -53,6 +117,7 @@ pub fn run_app(
                 false
             };
             refresh(system, disks, refresh_processes);
+            app.proc_rows_stale = true;
             if matches!(app.screen, Screen::Dashboard) && refresh_processes {
                 // reuse this timestamp for both proc+fs scan cadence
                 app.dash_last_proc_at = Some(Instant::now());
@@ -70,8 +135,15 @@ pub fn run_app(
                     io_elapsed,
                 );
             }
+            update_net_rates(&mut app.net_rows, &mut app.net_prev, io_elapsed);
+            update_cpu_breakdown(&mut app.cpu_breakdown, &mut app.cpu_stat_prev);
+            app.psi = read_psi();
             if matches!(app.screen, Screen::Services) {
                 refresh_services(app, false);
+                refresh_service_usage(app);
+            }
+            if matches!(app.screen, Screen::Timers) {
+                refresh_timers(app, false);
             }
             if matches!(app.screen, Screen::Logs) {
                 refresh_logs(app, false);
@@ -84,57 +156,61 @@ pub fn run_app(
             }
         }
 
-        let vm = snapshot(system);
+        let vm = snapshot(system, app.cpu_breakdown, app.psi);
+        if ticked {
+            let events = evaluate_app_alerts(app, &vm, disks);
+            dispatch_alert_events(&app.hostname, events, &mut app.notify_state);
+        }
         if matches!(app.screen, Screen::Dashboard) {
             let due = app
                 .dash_last_history_at
                 .map(|t| t.elapsed() >= dash_history_every)
                 .unwrap_or(true);
             if due {
-                push_history_sample(&mut app.dash_cpu_history, vm.cpu_usage as f64, 48);
-                push_history_sample(&mut app.dash_mem_history, vm.memory_percent, 48);
+                push_history_sample(&mut app.dash_cpu_history, vm.cpu_usage as f64, history_len);
+                push_history_sample(&mut app.dash_mem_history, vm.memory_percent, history_len);
+                let psi_samples = [
+                    (&mut app.dash_psi_cpu_history, vm.psi.cpu),
+                    (&mut app.dash_psi_mem_history, vm.psi.memory),
+                    (&mut app.dash_psi_io_history, vm.psi.io),
+                ];
+                for (history, res) in psi_samples {
+                    if let Some(res) = res {
+                        push_history_sample(history, res.some.avg10 as f64, history_len);
+                    }
+                }
                 app.dash_last_history_at = Some(Instant::now());
             }
         }
-
-        terminal.draw(|frame| {
-            let size = frame.size();
-            let rows = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1),
-                    Constraint::Min(8),
-                    Constraint::Length(if app.show_help { 7 } else { 1 }),
-                ])
-                .margin(1)
-                .split(size);
-
-            // Header
-            frame.render_widget(render_header(app), rows[0]);
-
-            // If terminal is too small, render a friendly message instead of a broken layout.
-            if rows[1].width < 80 || rows[1].height < 14 {
-                render_too_small(frame, rows[1]);
-                // Footer/help still renders below.
-                return;
+        if matches!(app.screen, Screen::Dashboard | Screen::Network) {
+            let due = app
+                .net_last_history_at
+                .map(|t| t.elapsed() >= dash_history_every)
+                .unwrap_or(true);
+            if due {
+                push_net_history(&mut app.net_history, &app.net_rows, history_len);
+                app.net_last_history_at = Some(Instant::now());
             }
+        }
 
-            // Main
-            match app.screen {
-                Screen::Dashboard => render_dashboard(frame, rows[1], &vm, app, system, disks),
-                Screen::Processes => render_processes(frame, rows[1], app, system),
-                Screen::DiskDive => render_disk_dive(frame, rows[1], app),
-                Screen::Services => render_services(frame, rows[1], app),
-                Screen::Logs => render_logs(frame, rows[1], app),
-            }
+        if matches!(app.screen, Screen::Dashboard) {
+            refresh_dashboard_cache(app, &vm, system, disks);
+        }
+        if matches!(app.screen, Screen::Processes) && app.proc_rows_stale {
+            let io_elapsed = app
+                .proc_io_at
+                .map(|t| t.elapsed().as_secs_f64())
+                .unwrap_or(0.0);
+            app.proc_io_at = Some(Instant::now());
+            app.proc_rows = collect_proc_rows(system);
+            update_proc_io_rates(&mut app.proc_rows, &mut app.proc_io_prev, io_elapsed);
+            app.proc_rows_stale = false;
+            refresh_proc_detail(app, system, false);
+        }
 
-            // Footer/help
-            if app.show_help {
-                frame.render_widget(render_help(app), rows[2]);
-            } else {
-                frame.render_widget(render_footer(app), rows[2]);
-            }
-        })?;
+        take_restart_result(app);
+        stop_log_follow_off_screen(app);
+        terminal.draw(|frame| draw_screen(frame, app, &vm))?;
 
         if app.dump_snapshot {
             app.dump_snapshot = false;
@@ -162,45 +238,20 @@ pub fn run_app(
                 }
 
                 // Kill confirm mode intercepts all keys.
-                if app.proc_kill_confirm.is_some() {
-                    let pid = app.proc_kill_confirm.as_ref().map(|(p, _)| *p).unwrap();
-                    match key.code {
-                        KeyCode::Char('y') => {
-                            if let Some(proc) = system.process(sysinfo::Pid::from_u32(pid as u32)) {
-                                let _ = proc.kill_with(sysinfo::Signal::Term);
-                            }
-                            app.proc_kill_confirm = None;
-                        }
-                        KeyCode::Char('K') => {
-                            if let Some(proc) = system.process(sysinfo::Pid::from_u32(pid as u32)) {
-                                proc.kill();
-                            }
-                            app.proc_kill_confirm = None;
-                        }
-                        KeyCode::Char('n') | KeyCode::Esc => {
-                            app.proc_kill_confirm = None;
-                        }
-                        _ => {}
-                    }
+                if handle_kill_confirm_key(app, system, &key) {
                     continue;
                 }
 
                 // Restart confirm mode intercepts all keys.
-                if app.proc_restart_confirm.is_some() {
-                    let (pid, _, exe, args) = app.proc_restart_confirm.clone().unwrap();
-                    match key.code {
-                        KeyCode::Char('y') => {
-                            if let Some(proc) = system.process(sysinfo::Pid::from_u32(pid as u32)) {
-                                let _ = proc.kill_with(sysinfo::Signal::Term);
-                            }
-                            let _ = Command::new(&exe).args(&args).spawn();
-                            app.proc_restart_confirm = None;
-                        }
-                        KeyCode::Char('n') | KeyCode::Esc => {
-                            app.proc_restart_confirm = None;
-                        }
-                        _ => {}
-                    }
+                if handle_restart_confirm_key(app, system, &key) {
+                    continue;
+                }
+
+                if handle_proc_action_key(app, system, &key) {
+                    continue;
+                }
+
+                if handle_proc_detail_key(app, &key) {
                     continue;
                 }
 
@@ -208,13 +259,32 @@ pub fn run_app(
                     continue;
                 }
 
+                if handle_service_action_key(app, &key) {
+                    continue;
+                }
+
+                if handle_service_detail_key(app, &key) {
+                    continue;
+                }
+
                 if handle_service_search_key(app, &key) {
                     continue;
                 }
 
+                if handle_log_search_key(app, &key) {
+                    continue;
+                }
+
+                if handle_proc_tree_key(app, &key) {
+                    continue;
+                }
+
                 match key.code {
                     KeyCode::Char('q') => return Ok(None),
                     KeyCode::Char('?') => app.show_help = !app.show_help,
+                    KeyCode::Esc if matches!(app.screen, Screen::Logs) && app.log_expanded => {
+                        app.log_expanded = false;
+                    }
                     KeyCode::Esc => {
                         app.show_help = false;
                         app.screen = Screen::Dashboard;
@@ -227,14 +297,27 @@ pub fn run_app(
                         app.show_help = false;
                         app.screen = Screen::DiskDive;
                     }
+                    KeyCode::Char('n') => {
+                        app.show_help = false;
+                        app.screen = Screen::Network;
+                    }
+                    KeyCode::Char('a') => {
+                        app.show_help = false;
+                        app.screen = Screen::Alerts;
+                    }
                     KeyCode::Char('v') => {
                         app.show_help = false;
                         app.screen = Screen::Services;
                         refresh_services(app, true);
                     }
+                    KeyCode::Char('T') => {
+                        app.show_help = false;
+                        app.screen = Screen::Timers;
+                        refresh_timers(app, true);
+                    }
                     KeyCode::Char('l') => {
                         app.show_help = false;
-                        if matches!(app.screen, Screen::Services) {
+                        if matches!(app.screen, Screen::Services | Screen::Timers) {
                             open_logs_for_selected_service(app);
                         } else {
                             app.screen = Screen::Logs;
@@ -255,6 +338,7 @@ pub fn run_app(
                             false
                         };
                         refresh(system, disks, refresh_processes);
+                        app.proc_rows_stale = true;
                         if matches!(app.screen, Screen::Dashboard) && refresh_processes {
                             // reuse this timestamp for both proc+fs scan cadence
                             app.dash_last_proc_at = Some(Instant::now());
@@ -262,6 +346,9 @@ pub fn run_app(
                         if matches!(app.screen, Screen::Services) {
                             refresh_services(app, true);
                         }
+                        if matches!(app.screen, Screen::Timers) {
+                            refresh_timers(app, true);
+                        }
                         if matches!(app.screen, Screen::Logs) {
                             refresh_logs(app, true);
                         }
@@ -276,24 +363,37 @@ pub fn run_app(
                     // Processes + DiskDive share Tab for mode/target.
                     KeyCode::Up => {
                         if matches!(app.screen, Screen::Processes) {
-                            app.proc_scroll = app.proc_scroll.saturating_sub(1);
+                            move_proc_selection(app, -1);
                         } else if matches!(app.screen, Screen::DiskDive) {
                             app.disk_scroll = app.disk_scroll.saturating_sub(1);
                         } else if matches!(app.screen, Screen::Services) {
                             app.service_scroll = app.service_scroll.saturating_sub(1);
+                        } else if matches!(app.screen, Screen::Timers) {
+                            app.timer_scroll = app.timer_scroll.saturating_sub(1);
                         } else if matches!(app.screen, Screen::Logs) {
                             app.logs_scroll = app.logs_scroll.saturating_sub(1);
+                            app.log_follow_pinned = false;
+                        } else if matches!(app.screen, Screen::Network) {
+                            app.net_scroll = app.net_scroll.saturating_sub(1);
+                        } else if matches!(app.screen, Screen::Alerts) {
+                            app.alerts_scroll = app.alerts_scroll.saturating_sub(1);
                         }
                     }
                     KeyCode::Down => {
                         if matches!(app.screen, Screen::Processes) {
-                            app.proc_scroll = app.proc_scroll.saturating_add(1);
+                            move_proc_selection(app, 1);
                         } else if matches!(app.screen, Screen::DiskDive) {
                             app.disk_scroll = app.disk_scroll.saturating_add(1);
                         } else if matches!(app.screen, Screen::Services) {
                             app.service_scroll = app.service_scroll.saturating_add(1);
+                        } else if matches!(app.screen, Screen::Timers) {
+                            app.timer_scroll = app.timer_scroll.saturating_add(1);
                         } else if matches!(app.screen, Screen::Logs) {
                             app.logs_scroll = app.logs_scroll.saturating_add(1);
+                        } else if matches!(app.screen, Screen::Network) {
+                            app.net_scroll = app.net_scroll.saturating_add(1);
+                        } else if matches!(app.screen, Screen::Alerts) {
+                            app.alerts_scroll = app.alerts_scroll.saturating_add(1);
                         }
                     }
 
@@ -319,9 +419,9 @@ pub fn run_app(
                             app.proc_sort = match app.proc_sort {
                                 ProcSort::Cpu => ProcSort::Mem,
                                 ProcSort::Mem => ProcSort::Swap,
-                                ProcSort::Swap => ProcSort::Cpu,
+                                ProcSort::Swap => ProcSort::Io,
+                                ProcSort::Io => ProcSort::Cpu,
                             };
-                            app.proc_scroll = 0;
                         } else if matches!(app.screen, Screen::Services) {
                             app.service_filter = match app.service_filter {
                                 ServiceFilter::Failed => ServiceFilter::Unhealthy,
@@ -341,84 +441,40 @@ pub fn run_app(
                             refresh_logs(app, true);
                         }
                     }
-                    KeyCode::Char('k') => {
-                        if matches!(app.screen, Screen::Processes) && !app.proc_search_active {
-                            // Rebuild filtered list to get the PID at the cursor.
-                            let mut procs: Vec<ProcRow> = system
-                                .processes()
-                                .iter()
-                                .map(|(pid, p)| ProcRow::from_process(*pid, p))
-                                .collect();
-                            match app.proc_sort {
-                                ProcSort::Cpu => procs.sort_by_key(|p| {
-                                    Reverse((p.cpu_x10 as i64, p.mem_bytes as i64))
-                                }),
-                                ProcSort::Mem => procs.sort_by_key(|p| {
-                                    Reverse((p.mem_bytes as i64, p.cpu_x10 as i64))
-                                }),
-                                ProcSort::Swap => procs.sort_by_key(|p| {
-                                    Reverse((p.swap_bytes as i64, p.mem_bytes as i64))
-                                }),
-                            }
-                            if procs.len() > 200 {
-                                procs.truncate(200);
-                            }
-                            let procs =
-                                crate::services::filtered_proc_rows(procs, &app.proc_search);
-                            let idx = (app.proc_scroll as usize).min(procs.len().saturating_sub(1));
-                            if let Some(row) = procs.get(idx) {
-                                app.proc_kill_confirm = Some((row.pid, row.name.clone()));
-                            }
+                    KeyCode::Char('k')
+                        if matches!(app.screen, Screen::Processes) && !app.proc_search_active =>
+                    {
+                        if let Some(row) = selected_proc_row(app) {
+                            open_kill_confirm(app, row.pid, row.name);
                         }
                     }
-                    KeyCode::Char('R') => {
-                        if matches!(app.screen, Screen::Processes) && !app.proc_search_active {
-                            let mut procs: Vec<ProcRow> = system
-                                .processes()
-                                .iter()
-                                .map(|(pid, p)| ProcRow::from_process(*pid, p))
-                                .collect();
-                            match app.proc_sort {
-                                ProcSort::Cpu => procs.sort_by_key(|p| {
-                                    Reverse((p.cpu_x10 as i64, p.mem_bytes as i64))
-                                }),
-                                ProcSort::Mem => procs.sort_by_key(|p| {
-                                    Reverse((p.mem_bytes as i64, p.cpu_x10 as i64))
-                                }),
-                                ProcSort::Swap => procs.sort_by_key(|p| {
-                                    Reverse((p.swap_bytes as i64, p.mem_bytes as i64))
-                                }),
-                            }
-                            if procs.len() > 200 {
-                                procs.truncate(200);
-                            }
-                            let procs =
-                                crate::services::filtered_proc_rows(procs, &app.proc_search);
-                            let idx = (app.proc_scroll as usize).min(procs.len().saturating_sub(1));
-                            if let Some(row) = procs.get(idx) {
-                                let sysinfo_pid = sysinfo::Pid::from_u32(row.pid as u32);
-                                if let Some(proc) = system.process(sysinfo_pid) {
-                                    if let Some(exe_path) = proc.exe() {
-                                        let exe = exe_path.to_path_buf();
-                                        let args: Vec<String> =
-                                            proc.cmd().iter().skip(1).cloned().collect();
-                                        app.proc_restart_confirm =
-                                            Some((row.pid, row.name.clone(), exe, args));
-                                    }
-                                }
-                            }
+                    KeyCode::Char('R')
+                        if matches!(app.screen, Screen::Processes) && !app.proc_search_active =>
+                    {
+                        if let Some(row) = selected_proc_row(app) {
+                            open_restart_confirm(app, system, row.pid, row.name);
                         }
                     }
                     KeyCode::Char('s') => {
                         if matches!(app.screen, Screen::DiskDive) {
                             start_disk_scan(app);
+                        } else if matches!(app.screen, Screen::Processes) {
+                            if let Some(row) = selected_proc_row(app) {
+                                open_proc_action_menu(app, row.pid, row.name);
+                            }
                         }
                     }
                     KeyCode::Enter => {
                         if matches!(app.screen, Screen::DiskDive) {
                             enter_selected_disk_dir(app);
-                        } else if matches!(app.screen, Screen::Services) {
+                        } else if matches!(app.screen, Screen::Services | Screen::Timers) {
                             open_logs_for_selected_service(app);
+                        } else if matches!(app.screen, Screen::Logs) {
+                            app.log_expanded = !app.log_expanded;
+                        } else if matches!(app.screen, Screen::Processes) {
+                            if let Some(row) = selected_proc_row(app) {
+                                open_proc_detail(app, system, row.pid);
+                            }
                         }
                     }
                     KeyCode::Left | KeyCode::Backspace => {
@@ -426,12 +482,22 @@ pub fn run_app(
                             navigate_disk_up(app);
                         }
                     }
+                    KeyCode::Char('o') if matches!(app.screen, Screen::Services) => {
+                        app.service_sort = next_service_sort(app.service_sort);
+                        app.service_scroll = 0;
+                    }
                     KeyCode::Char('f') => {
                         if matches!(app.screen, Screen::Dashboard) {
                             app.dash_show_all_mounts = !app.dash_show_all_mounts;
                             app.dash_last_fs_at = None;
+                        } else if matches!(app.screen, Screen::Logs) {
+                            toggle_log_follow(app);
                         }
                     }
+                    KeyCode::End if matches!(app.screen, Screen::Logs) => {
+                        app.log_follow_pinned = true;
+                        app.logs_scroll = u16::MAX;
+                    }
                     KeyCode::Char('x') => {
                         if matches!(app.screen, Screen::Dashboard) {
                             app.dump_snapshot = true;
@@ -447,6 +513,8 @@ pub fn run_app(
                             };
                             app.logs_scroll = 0;
                             refresh_logs(app, true);
+                        } else if matches!(app.screen, Screen::Services) {
+                            set_service_scope(app, next_service_scope(app.service_scope));
                         }
                     }
 
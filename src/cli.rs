@@ -1,13 +1,36 @@
-use crate::types::Args;
+use std::path::PathBuf;
+
+use crate::exporter::DEFAULT_LISTEN;
+use crate::types::{Args, Command};
 use crate::update::VERSION;
 
 pub fn parse_args() -> Result<Args, String> {
-    let mut tick_ms: u64 = 500;
+    let argv: Vec<String> = std::env::args().collect();
+    parse_args_from(&argv)
+}
+
+/// Subcommand flags may come before or after the subcommand, so they are
+/// collected first and checked against it once the whole line is read.
+fn reject_flags(flags: &[(&str, bool)], command: &str) -> Result<(), String> {
+    match flags.iter().find(|(_, given)| *given) {
+        Some((flag, _)) => Err(format!("{flag} is only valid with `ferro {command}`")),
+        None => Ok(()),
+    }
+}
+
+pub fn parse_args_from(argv: &[String]) -> Result<Args, String> {
+    let mut tick_ms: Option<u64> = None;
     let mut no_mouse = false;
     let mut show_help = false;
     let mut show_version = false;
+    let mut command = Command::Tui;
+
+    let mut json = false;
+    let mut pretty = false;
+    let mut samples: Option<u32> = None;
+    let mut print_defaults = false;
+    let mut listen: Option<String> = None;
 
-    let argv: Vec<String> = std::env::args().collect();
     let mut i = 1;
     while i < argv.len() {
         let a = argv[i].as_str();
@@ -22,18 +45,102 @@ pub fn parse_args() -> Result<Args, String> {
                 let ms = val
                     .parse::<u64>()
                     .map_err(|_| format!("invalid --tick-ms value: {val}"))?;
-                tick_ms = ms.clamp(50, 5000);
+                tick_ms = Some(ms.clamp(50, 5000));
+                i += 1;
+            }
+            "--json" => json = true,
+            "--pretty" => pretty = true,
+            "--samples" => {
+                let Some(val) = argv.get(i + 1) else {
+                    return Err("--samples requires a value".to_string());
+                };
+                samples = Some(
+                    val.parse::<u32>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| format!("invalid --samples value: {val}"))?,
+                );
+                i += 1;
+            }
+            "--print-defaults" => print_defaults = true,
+            "--listen" => {
+                let Some(val) = argv.get(i + 1) else {
+                    return Err("--listen requires an address".to_string());
+                };
+                listen = Some(val.clone());
                 i += 1;
             }
             _ if a.starts_with('-') => {
                 return Err(format!("unknown option: {a}"));
             }
-            _ => {}
+            "snapshot" if command == Command::Tui => {
+                command = Command::Snapshot {
+                    json: false,
+                    samples: 1,
+                    pretty: false,
+                };
+            }
+            "serve" if command == Command::Tui => {
+                command = Command::Serve {
+                    listen: DEFAULT_LISTEN.to_string(),
+                };
+            }
+            "config" if command == Command::Tui => {
+                command = Command::Config {
+                    print_defaults: false,
+                };
+            }
+            "record" | "replay" if command == Command::Tui => {
+                let Some(val) = argv.get(i + 1).filter(|v| !v.starts_with('-')) else {
+                    return Err(format!("`ferro {a}` requires a file path"));
+                };
+                let path = PathBuf::from(val);
+                command = if a == "record" {
+                    Command::Record { path }
+                } else {
+                    Command::Replay { path }
+                };
+                i += 1;
+            }
+            _ => return Err(format!("unknown command: {a}")),
         }
         i += 1;
     }
 
+    match &mut command {
+        Command::Snapshot {
+            json: j,
+            samples: n,
+            pretty: p,
+        } => {
+            *j = json;
+            *p = pretty;
+            *n = samples.unwrap_or(*n);
+        }
+        _ => reject_flags(
+            &[
+                ("--json", json),
+                ("--pretty", pretty),
+                ("--samples", samples.is_some()),
+            ],
+            "snapshot",
+        )?,
+    }
+    match &mut command {
+        Command::Config { print_defaults: d } => *d = print_defaults,
+        _ => reject_flags(&[("--print-defaults", print_defaults)], "config")?,
+    }
+    match &mut command {
+        Command::Serve { listen: addr } => {
+            if let Some(val) = listen {
+                *addr = val;
+            }
+        }
+        _ => reject_flags(&[("--listen", listen.is_some())], "serve")?,
+    }
+
     Ok(Args {
+        command,
         tick_ms,
         no_mouse,
         show_help,
@@ -48,19 +155,47 @@ Lightweight Rust TUI system monitor.
 
 USAGE:
   ferro [OPTIONS]
+  ferro snapshot [--json] [--samples <N>] [--pretty] [--tick-ms <MS>]
+  ferro serve [--listen <ADDR>] [--tick-ms <MS>]
+  ferro record <FILE> [--tick-ms <MS>]
+  ferro replay <FILE>
+  ferro config [--print-defaults]
 
 OPTIONS:
   --tick-ms <MS>   Refresh interval in milliseconds (default: 500, range: 50-5000)
+                   Overrides general.tick_ms from the config file
   --no-mouse       Disable mouse support (useful in tmux/SSH)
   --version, -V    Print version and exit
   --help, -h       Print help and exit
 
+COMMANDS:
+  snapshot         Sample once over --tick-ms and print without starting the TUI
+    --json         Emit versioned JSON (one document per line) instead of text
+    --samples <N>  Take N consecutive samples (default: 1)
+    --pretty       Indent JSON output
+  serve            Serve OpenMetrics on http://<ADDR>/metrics, sampling every --tick-ms
+    --listen <ADDR>  Bind address (default: 127.0.0.1:9717)
+  record <FILE>    Append every tick (system, processes, mounts, network,
+                   services, logs) to a new recording until Ctrl-C
+  replay <FILE>    Browse a recording in the TUI
+                   Space: play/pause  ←/→: step  [/]: seek 10  g/G: start/end  +/-: speed
+  config           Show the config file path and effective settings
+    --print-defaults Print a commented default config.toml
+
+CONFIG:
+  $XDG_CONFIG_HOME/ferromon/config.toml (default ~/.config/ferromon/config.toml)
+  Command-line flags override config values.
+  [notify] runs a command, POSTs to a webhook or writes syslog on alert events.
+
 SCREENS:
   Dashboard  — CPU, memory, disk overview (default)
   p          — Processes
   d          — Disk dive (on-demand scanner)
+  n          — Network interfaces (Linux/proc/net/dev)
   v          — Services (Linux/systemd only)
+  T          — Timers (Linux/systemd only)
   l          — Logs (Linux/journalctl + syslog fallback)
+  a          — Alerts (rules from config.toml)
 
   Esc        — Back to Dashboard
   q          — Quit
@@ -68,3 +203,49 @@ SCREENS:
 "
     );
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Command, String> {
+        let argv: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse_args_from(&argv).map(|args| args.command)
+    }
+
+    #[test]
+    fn subcommand_flags_are_accepted_in_any_order() {
+        let snapshot = Command::Snapshot {
+            json: true,
+            samples: 3,
+            pretty: false,
+        };
+        assert_eq!(
+            parse("ferro snapshot --json --samples 3"),
+            Ok(snapshot.clone())
+        );
+        assert_eq!(
+            parse("ferro --json snapshot --samples 3"),
+            Ok(snapshot.clone())
+        );
+        assert_eq!(parse("ferro --samples 3 --json snapshot"), Ok(snapshot));
+        assert_eq!(
+            parse("ferro --listen 0.0.0.0:9000 serve"),
+            Ok(Command::Serve {
+                listen: "0.0.0.0:9000".to_string()
+            })
+        );
+
+        assert_eq!(
+            parse("ferro --json serve"),
+            Err("--json is only valid with `ferro snapshot`".to_string())
+        );
+        assert_eq!(
+            parse("ferro --listen :9000"),
+            Err("--listen is only valid with `ferro serve`".to_string())
+        );
+        assert!(parse("ferro snapshot --samples 0").is_err());
+    }
+}
@@ -0,0 +1,360 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::alerts::parse_rule;
+use crate::types::Screen;
+
+/// Commented template printed by `ferro config --print-defaults`.
+/// Parsing it must yield `Config::default()` (see tests).
+pub const DEFAULT_CONFIG_TOML: &str = r#"# Ferromon configuration
+# Location: $XDG_CONFIG_HOME/ferromon/config.toml (default ~/.config/ferromon/config.toml)
+# Every key is optional; command-line flags override these values.
+
+[general]
+# Refresh interval in milliseconds (50-5000). Overridden by --tick-ms.
+tick_ms = 500
+# Screen shown at startup: dashboard, processes, disk_dive, network, services, timers, logs, alerts
+default_screen = "dashboard"
+# Samples kept for sparklines and history charts.
+history_len = 48
+# Mouse capture. Overridden by --no-mouse.
+mouse = true
+
+[refresh]
+# Seconds between `systemctl` refreshes on the services screen.
+services_secs = 15
+# Seconds between journal/syslog refreshes on the logs screen.
+logs_secs = 5
+
+[disk_scan]
+# Files visited per disk dive scan before stopping.
+max_files = 300000
+# Files visited inside a single top-level entry before moving on.
+max_files_per_entry = 50000
+
+[mounts]
+# Start with all mounts shown (toggle with `f` on the dashboard).
+show_all = true
+# Also hide filesystems whose source contains any of these (case-insensitive).
+# tmpfs, udev, devfs and automounter maps are always hidden.
+hide_fs = []
+# Hide mount points under these prefixes.
+hide_mount_prefixes = ["/run", "/dev", "/sys"]
+# Hide zero-sized filesystems.
+hide_empty = true
+
+[thresholds]
+# Usage percentages where bars and values turn yellow, then red.
+warn_pct = 75.0
+crit_pct = 90.0
+
+# Alert rules, evaluated every tick. Firing alerts show in the header banner,
+# on the alerts screen (`a`) and in `ferro snapshot` output.
+#
+#   <metric> <op> <value> [for <duration>]         metric: cpu mem swap load1 load5 load15
+#                                                          psi_cpu psi_mem psi_io
+#   mount <path> <field> <op> <value> [for ...]     field: use_pct read_bps write_bps
+#   service <unit> <field> == <value> [for ...]     field: health active sub
+#
+# [[alerts]]
+# name = "cpu-hot"
+# rule = "cpu > 90% for 60s"
+#
+# [[alerts]]
+# rule = "mount / use_pct > 85"
+#
+# [[alerts]]
+# rule = "service nginx.service health == critical"
+
+[notify]
+# Where fire/resolve events go. All sinks receive the same JSON payload.
+# Command (argv list) run once per event with the payload on stdin.
+exec = []
+# URL that receives an HTTP POST per event, e.g. "https://hooks.example.com/ferro".
+# webhook = ""
+# Also log each event to the local syslog socket.
+syslog = false
+# Seconds before a hook command or webhook request is abandoned.
+timeout_secs = 5
+"#;
+
+// ── Schema ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub general: GeneralConfig,
+    pub refresh: RefreshConfig,
+    pub disk_scan: DiskScanConfig,
+    pub mounts: MountsConfig,
+    pub thresholds: ThresholdsConfig,
+    pub alerts: Vec<AlertRuleConfig>,
+    pub notify: NotifyConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneralConfig {
+    pub tick_ms: u64,
+    pub default_screen: Screen,
+    pub history_len: usize,
+    pub mouse: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RefreshConfig {
+    pub services_secs: u64,
+    pub logs_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiskScanConfig {
+    pub max_files: u64,
+    pub max_files_per_entry: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MountsConfig {
+    pub show_all: bool,
+    pub hide_fs: Vec<String>,
+    pub hide_mount_prefixes: Vec<String>,
+    pub hide_empty: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThresholdsConfig {
+    pub warn_pct: f64,
+    pub crit_pct: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRuleConfig {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotifyConfig {
+    pub exec: Vec<String>,
+    pub webhook: Option<String>,
+    pub syslog: bool,
+    pub timeout_secs: u64,
+}
+
+impl NotifyConfig {
+    pub fn enabled(&self) -> bool {
+        !self.exec.is_empty() || self.webhook.is_some() || self.syslog
+    }
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            tick_ms: 500,
+            default_screen: Screen::Dashboard,
+            history_len: 48,
+            mouse: true,
+        }
+    }
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            services_secs: 15,
+            logs_secs: 5,
+        }
+    }
+}
+
+impl Default for DiskScanConfig {
+    fn default() -> Self {
+        Self {
+            max_files: 300_000,
+            max_files_per_entry: 50_000,
+        }
+    }
+}
+
+impl Default for MountsConfig {
+    fn default() -> Self {
+        Self {
+            show_all: true,
+            hide_fs: Vec::new(),
+            hide_mount_prefixes: ["/run", "/dev", "/sys"].map(String::from).to_vec(),
+            hide_empty: true,
+        }
+    }
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            exec: Vec::new(),
+            webhook: None,
+            syslog: false,
+            timeout_secs: 5,
+        }
+    }
+}
+
+impl Default for ThresholdsConfig {
+    fn default() -> Self {
+        Self {
+            warn_pct: 75.0,
+            crit_pct: 90.0,
+        }
+    }
+}
+
+// ── Loading ───────────────────────────────────────────────────────────────────
+
+pub fn config_path() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("ferromon").join("config.toml"))
+}
+
+pub fn parse_config(text: &str) -> Result<Config, String> {
+    let mut config: Config = toml::from_str(text).map_err(|e| e.to_string())?;
+    config.general.tick_ms = config.general.tick_ms.clamp(50, 5000);
+    config.general.history_len = config.general.history_len.clamp(2, 1024);
+    config.refresh.services_secs = config.refresh.services_secs.max(1);
+    config.refresh.logs_secs = config.refresh.logs_secs.max(1);
+    config.notify.timeout_secs = config.notify.timeout_secs.clamp(1, 60);
+    if let Some(url) = &config.notify.webhook {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!(
+                "notify.webhook: expected an http(s) URL, got `{url}`"
+            ));
+        }
+    }
+    let t = &config.thresholds;
+    if !(0.0..=100.0).contains(&t.warn_pct)
+        || !(0.0..=100.0).contains(&t.crit_pct)
+        || t.warn_pct > t.crit_pct
+    {
+        return Err("thresholds: expected 0 <= warn_pct <= crit_pct <= 100".to_string());
+    }
+    for (i, alert) in config.alerts.iter().enumerate() {
+        parse_rule(alert.name.as_deref(), &alert.rule)
+            .map_err(|e| format!("alerts[{i}] `{}`: {e}", alert.rule))?;
+    }
+    Ok(config)
+}
+
+/// A missing file means defaults; a malformed one is an error so typos don't go unnoticed.
+pub fn load_config(path: Option<&Path>) -> Result<Config, String> {
+    let Some(path) = path else {
+        return Ok(Config::default());
+    };
+    match std::fs::read_to_string(path) {
+        Ok(text) => parse_config(&text).map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(format!("failed to read {}: {e}", path.display())),
+    }
+}
+
+// ── Process-wide access ───────────────────────────────────────────────────────
+
+// Set once at startup; helpers like `color_for_pct` read it without threading
+// the config through every render call.
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+pub fn install_config(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+// ── `ferro config` ────────────────────────────────────────────────────────────
+
+pub fn run_config(print_defaults: bool) -> Result<(), String> {
+    if print_defaults {
+        print!("{DEFAULT_CONFIG_TOML}");
+        return Ok(());
+    }
+
+    let path = config_path();
+    match &path {
+        Some(p) if p.exists() => println!("Config: {}", p.display()),
+        Some(p) => println!("Config: {} (not found, using defaults)", p.display()),
+        None => println!("Config: (no HOME or XDG_CONFIG_HOME, using defaults)"),
+    }
+    let c = load_config(path.as_deref())?;
+    println!(
+        "tick_ms={} default_screen={:?} history_len={} mouse={}",
+        c.general.tick_ms, c.general.default_screen, c.general.history_len, c.general.mouse
+    );
+    println!(
+        "refresh: services={}s logs={}s",
+        c.refresh.services_secs, c.refresh.logs_secs
+    );
+    println!(
+        "disk_scan: max_files={} max_files_per_entry={}",
+        c.disk_scan.max_files, c.disk_scan.max_files_per_entry
+    );
+    println!(
+        "mounts: show_all={} hide_fs={:?} hide_mount_prefixes={:?} hide_empty={}",
+        c.mounts.show_all, c.mounts.hide_fs, c.mounts.hide_mount_prefixes, c.mounts.hide_empty
+    );
+    println!(
+        "thresholds: warn={}% crit={}%",
+        c.thresholds.warn_pct, c.thresholds.crit_pct
+    );
+    println!(
+        "notify: exec={:?} webhook={} syslog={} timeout={}s",
+        c.notify.exec,
+        c.notify.webhook.as_deref().unwrap_or("(none)"),
+        c.notify.syslog,
+        c.notify.timeout_secs
+    );
+    println!("alerts: {} rule(s)", c.alerts.len());
+    for alert in &c.alerts {
+        match &alert.name {
+            Some(name) => println!("  {name}: {}", alert.rule),
+            None => println!("  {}", alert.rule),
+        }
+    }
+    Ok(())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_template_matches_built_in_defaults() {
+        assert_eq!(
+            parse_config(DEFAULT_CONFIG_TOML).unwrap(),
+            Config::default()
+        );
+    }
+
+    #[test]
+    fn partial_config_keeps_other_defaults_and_rejects_typos() {
+        let c = parse_config("[general]\ndefault_screen = \"services\"\ntick_ms = 10\n").unwrap();
+
+        assert_eq!(c.general.default_screen, Screen::Services);
+        assert_eq!(c.general.tick_ms, 50);
+        assert_eq!(c.refresh, RefreshConfig::default());
+        assert!(parse_config("[general]\ntick = 100\n").is_err());
+        assert!(parse_config("[thresholds]\nwarn_pct = 95.0\n").is_err());
+        assert!(parse_config("[[alerts]]\nrule = \"cpu >> 1\"\n").is_err());
+    }
+}
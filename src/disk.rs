@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 
 use walkdir::WalkDir;
 
+use crate::config::config;
 use crate::types::{AppState, DiskEntry, DiskEntryKind, DiskScanState, DiskTarget};
 
 // ── Path lookup ───────────────────────────────────────────────────────────────
@@ -173,7 +174,7 @@ fn scan_path_entries(target: &Path, inner: &Arc<Mutex<DiskScanState>>) -> Result
             st.results = results.clone();
         }
 
-        if total_seen >= 300_000 {
+        if total_seen >= config().disk_scan.max_files {
             let mut st = inner.lock().unwrap();
             st.progress = "Reached scan cap (kept it lightweight).".to_string();
             break;
@@ -215,7 +216,9 @@ fn scan_dir_size(path: &Path, total_seen: u64) -> (u64, u64) {
                 size = size.saturating_add(md.len());
             }
             seen += 1;
-            if seen >= 50_000 || total_seen.saturating_add(seen) >= 300_000 {
+            let caps = &config().disk_scan;
+            if seen >= caps.max_files_per_entry || total_seen.saturating_add(seen) >= caps.max_files
+            {
                 break;
             }
         }
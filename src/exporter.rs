@@ -0,0 +1,499 @@
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::config;
+use crate::headless::HeadlessCollector;
+use crate::services::collect_services;
+use crate::types::{DiskRow, NetRow, ServiceHealth, ServiceRow, ServiceScope, VmSnapshot};
+use crate::update::VERSION;
+
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:9717";
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+/// Same cadence as the dashboard's filesystem scan; every mount is exported.
+const MOUNTS_EVERY: Duration = Duration::from_secs(5);
+
+// ── OpenMetrics text rendering ────────────────────────────────────────────────
+
+pub struct MetricsInput<'a> {
+    pub vm: &'a VmSnapshot,
+    pub mounts: &'a [DiskRow],
+    pub network: &'a [NetRow],
+    /// `None` when services are unsupported or the last collection failed.
+    pub services: Option<&'a [ServiceRow]>,
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Writes one metric family: `# TYPE`, `# HELP` and its samples.
+fn family(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(String, f64)]) {
+    if samples.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let suffix = if kind == "counter" { "_total" } else { "" };
+    for (labels, value) in samples {
+        let _ = writeln!(out, "{name}{suffix}{labels} {value}");
+    }
+}
+
+fn labels(pairs: &[(&str, &str)]) -> String {
+    if pairs.is_empty() {
+        return String::new();
+    }
+    let inner: Vec<String> = pairs
+        .iter()
+        .map(|(k, v)| format!("{k}=\"{}\"", escape_label(v)))
+        .collect();
+    format!("{{{}}}", inner.join(","))
+}
+
+fn one(value: f64) -> Vec<(String, f64)> {
+    vec![(String::new(), value)]
+}
+
+pub fn render_openmetrics(input: &MetricsInput) -> String {
+    let vm = input.vm;
+    let mut out = String::new();
+
+    family(
+        &mut out,
+        "ferro_build_info",
+        "gauge",
+        "Ferromon build information.",
+        &[(labels(&[("version", VERSION)]), 1.0)],
+    );
+
+    // CPU
+    family(
+        &mut out,
+        "ferro_cpu_usage_percent",
+        "gauge",
+        "Global CPU usage.",
+        &one(vm.cpu_usage as f64),
+    );
+    family(
+        &mut out,
+        "ferro_cpu_core_usage_percent",
+        "gauge",
+        "Per-core CPU usage.",
+        &vm.core_usage
+            .iter()
+            .enumerate()
+            .map(|(i, pct)| (labels(&[("core", &i.to_string())]), *pct as f64))
+            .collect::<Vec<_>>(),
+    );
+    if let Some(b) = &vm.cpu_breakdown {
+        let modes = [
+            ("user", b.user),
+            ("nice", b.nice),
+            ("system", b.system),
+            ("idle", b.idle),
+            ("iowait", b.iowait),
+            ("irq", b.irq),
+            ("softirq", b.softirq),
+            ("steal", b.steal),
+        ];
+        family(
+            &mut out,
+            "ferro_cpu_time_percent",
+            "gauge",
+            "Share of CPU time per state over the last sampling interval.",
+            &modes
+                .iter()
+                .map(|(mode, pct)| (labels(&[("mode", mode)]), *pct as f64))
+                .collect::<Vec<_>>(),
+        );
+    }
+    family(
+        &mut out,
+        "ferro_load_average",
+        "gauge",
+        "System load average.",
+        &[
+            (labels(&[("window", "1m")]), vm.load_avg_one),
+            (labels(&[("window", "5m")]), vm.load_avg_five),
+            (labels(&[("window", "15m")]), vm.load_avg_fifteen),
+        ],
+    );
+
+    // Memory
+    for (name, help, value) in [
+        ("ferro_memory_total_bytes", "Total memory.", vm.total_memory),
+        ("ferro_memory_used_bytes", "Used memory.", vm.used_memory),
+        (
+            "ferro_memory_available_bytes",
+            "Available memory.",
+            vm.available_memory,
+        ),
+        ("ferro_swap_total_bytes", "Total swap.", vm.total_swap),
+        ("ferro_swap_used_bytes", "Used swap.", vm.used_swap),
+        ("ferro_uptime_seconds", "System uptime.", vm.uptime_secs),
+    ] {
+        family(&mut out, name, "gauge", help, &one(value as f64));
+    }
+
+    // Pressure
+    let mut psi_samples = Vec::new();
+    for (resource, res) in [
+        ("cpu", vm.psi.cpu),
+        ("memory", vm.psi.memory),
+        ("io", vm.psi.io),
+    ] {
+        let Some(res) = res else {
+            continue;
+        };
+        let mut kinds = vec![("some", res.some)];
+        if let Some(full) = res.full {
+            kinds.push(("full", full));
+        }
+        for (kind, avgs) in kinds {
+            for (window, value) in [
+                ("10s", avgs.avg10),
+                ("60s", avgs.avg60),
+                ("300s", avgs.avg300),
+            ] {
+                psi_samples.push((
+                    labels(&[("resource", resource), ("kind", kind), ("window", window)]),
+                    value as f64,
+                ));
+            }
+        }
+    }
+    family(
+        &mut out,
+        "ferro_pressure_stall_percent",
+        "gauge",
+        "Pressure stall information averages from /proc/pressure.",
+        &psi_samples,
+    );
+
+    // Filesystems
+    let mount_family = |out: &mut String, name: &str, help: &str, f: fn(&DiskRow) -> f64| {
+        let samples: Vec<(String, f64)> = input
+            .mounts
+            .iter()
+            .map(|r| (labels(&[("mount", &r.mount), ("fs", &r.fs)]), f(r)))
+            .collect();
+        family(out, name, "gauge", help, &samples);
+    };
+    mount_family(
+        &mut out,
+        "ferro_filesystem_size_bytes",
+        "Filesystem size.",
+        |r| r.size as f64,
+    );
+    mount_family(
+        &mut out,
+        "ferro_filesystem_used_bytes",
+        "Filesystem used space.",
+        |r| r.used as f64,
+    );
+    mount_family(
+        &mut out,
+        "ferro_filesystem_avail_bytes",
+        "Filesystem available space.",
+        |r| r.avail as f64,
+    );
+    mount_family(
+        &mut out,
+        "ferro_filesystem_read_bytes_per_second",
+        "Block device read rate behind the mount.",
+        |r| r.read_bps as f64,
+    );
+    mount_family(
+        &mut out,
+        "ferro_filesystem_write_bytes_per_second",
+        "Block device write rate behind the mount.",
+        |r| r.write_bps as f64,
+    );
+
+    // Network
+    let net_family =
+        |out: &mut String, name: &str, kind: &str, help: &str, f: fn(&NetRow) -> f64| {
+            let samples: Vec<(String, f64)> = input
+                .network
+                .iter()
+                .map(|r| (labels(&[("interface", &r.name)]), f(r)))
+                .collect();
+            family(out, name, kind, help, &samples);
+        };
+    net_family(
+        &mut out,
+        "ferro_network_receive_bytes",
+        "counter",
+        "Bytes received.",
+        |r| r.rx_total as f64,
+    );
+    net_family(
+        &mut out,
+        "ferro_network_transmit_bytes",
+        "counter",
+        "Bytes transmitted.",
+        |r| r.tx_total as f64,
+    );
+    net_family(
+        &mut out,
+        "ferro_network_receive_bytes_per_second",
+        "gauge",
+        "Receive rate over the last sampling interval.",
+        |r| r.rx_bps as f64,
+    );
+    net_family(
+        &mut out,
+        "ferro_network_transmit_bytes_per_second",
+        "gauge",
+        "Transmit rate over the last sampling interval.",
+        |r| r.tx_bps as f64,
+    );
+    net_family(
+        &mut out,
+        "ferro_network_errors",
+        "counter",
+        "Receive + transmit errors.",
+        |r| r.errs as f64,
+    );
+    net_family(
+        &mut out,
+        "ferro_network_drops",
+        "counter",
+        "Receive + transmit drops.",
+        |r| r.drops as f64,
+    );
+
+    // Services
+    family(
+        &mut out,
+        "ferro_services_up",
+        "gauge",
+        "1 if the last systemd service collection succeeded.",
+        &one(if input.services.is_some() { 1.0 } else { 0.0 }),
+    );
+    if let Some(rows) = input.services {
+        family(
+            &mut out,
+            "ferro_service_health",
+            "gauge",
+            "Service health: 0 healthy, 1 warning, 2 critical.",
+            &rows
+                .iter()
+                .map(|r| {
+                    let value = match r.health {
+                        ServiceHealth::Healthy => 0.0,
+                        ServiceHealth::Warning => 1.0,
+                        ServiceHealth::Critical => 2.0,
+                    };
+                    (
+                        labels(&[
+                            ("unit", &r.name),
+                            ("active_state", &r.active_state),
+                            ("sub_state", &r.sub_state),
+                        ]),
+                        value,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        family(
+            &mut out,
+            "ferro_service_restarts",
+            "counter",
+            "Restarts reported by systemd (NRestarts).",
+            &rows
+                .iter()
+                .map(|r| (labels(&[("unit", &r.name)]), r.restarts as f64))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    out.push_str("# EOF\n");
+    out
+}
+
+// ── HTTP ──────────────────────────────────────────────────────────────────────
+
+/// Answers one HTTP/1.x request; enough for Prometheus and curl, nothing more.
+pub fn serve_connection(stream: TcpStream, metrics: &Mutex<String>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain headers; we don't need any of them.
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line == "\r\n" || line == "\n" {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("");
+    let path = path.split('?').next().unwrap_or("");
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", CONTENT_TYPE, metrics.lock().unwrap().clone()),
+        ("GET", "/") => (
+            "200 OK",
+            "text/plain; charset=utf-8",
+            format!("ferro {VERSION} exporter\nmetrics: /metrics\n"),
+        ),
+        ("GET", _) => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "not found\n".to_string(),
+        ),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "method not allowed\n".to_string(),
+        ),
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+// ── `ferro serve` ─────────────────────────────────────────────────────────────
+
+pub fn run_serve(listen: &str, interval_ms: u64) -> Result<(), String> {
+    let listener =
+        TcpListener::bind(listen).map_err(|e| format!("failed to listen on {listen}: {e}"))?;
+    let addr = listener
+        .local_addr()
+        .map(|a| a.to_string())
+        .unwrap_or_else(|_| listen.to_string());
+
+    let metrics = Arc::new(Mutex::new(String::from("# EOF\n")));
+    let interval = Duration::from_millis(interval_ms);
+
+    let shared = metrics.clone();
+    std::thread::spawn(move || {
+        let mut collector = HeadlessCollector::with_mounts(false, usize::MAX, MOUNTS_EVERY);
+        // Same cadence as the services screen: systemctl is too heavy for every tick.
+        let services_every = Duration::from_secs(config().refresh.services_secs);
+        let mut services: Option<Vec<ServiceRow>> = None;
+        let mut services_at: Option<Instant> = None;
+        loop {
+            std::thread::sleep(interval);
+            let vm = collector.sample();
+            let services_due = services_at
+                .map(|t| t.elapsed() >= services_every)
+                .unwrap_or(true);
+            if services_due {
+                services = collect_services(ServiceScope::System).ok();
+                services_at = Some(Instant::now());
+            }
+            let text = render_openmetrics(&MetricsInput {
+                vm: &vm,
+                mounts: &collector.app.dash_mount_rows,
+                network: &collector.app.net_rows,
+                services: services.as_deref(),
+            });
+            *shared.lock().unwrap() = text;
+        }
+    });
+
+    eprintln!("ferro {VERSION}: serving OpenMetrics on http://{addr}/metrics");
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let metrics = metrics.clone();
+        std::thread::spawn(move || {
+            let _ = serve_connection(stream, &metrics);
+        });
+    }
+    Ok(())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Psi;
+
+    fn sample_vm() -> VmSnapshot {
+        VmSnapshot {
+            cpu_usage: 12.5,
+            cpu_cores: 2,
+            core_usage: vec![20.0, 5.0],
+            cpu_breakdown: None,
+            psi: Psi::default(),
+            load_avg_one: 0.5,
+            load_avg_five: 0.25,
+            load_avg_fifteen: 0.1,
+            total_memory: 1024,
+            used_memory: 512,
+            available_memory: 512,
+            memory_percent: 50.0,
+            total_swap: 0,
+            used_swap: 0,
+            uptime_secs: 60,
+        }
+    }
+
+    #[test]
+    fn render_openmetrics_emits_families_and_eof() {
+        let vm = sample_vm();
+        let mounts = vec![DiskRow {
+            fs: "/dev/sda1".to_string(),
+            size: 100,
+            used: 40,
+            avail: 60,
+            use_pct: 40.0,
+            mount: "/".to_string(),
+            read_bps: 0,
+            write_bps: 7,
+        }];
+        let text = render_openmetrics(&MetricsInput {
+            vm: &vm,
+            mounts: &mounts,
+            network: &[],
+            services: None,
+        });
+
+        assert!(text.contains("# TYPE ferro_cpu_usage_percent gauge\n"));
+        assert!(text.contains("ferro_cpu_core_usage_percent{core=\"1\"} 5\n"));
+        assert!(text
+            .contains("ferro_filesystem_write_bytes_per_second{mount=\"/\",fs=\"/dev/sda1\"} 7\n"));
+        assert!(text.contains("ferro_services_up 0\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn serve_connection_answers_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let metrics = Arc::new(Mutex::new("ferro_up 1\n# EOF\n".to_string()));
+        let shared = metrics.clone();
+        let server = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().unwrap();
+                serve_connection(stream, &shared).unwrap();
+            }
+        });
+
+        let resp = ureq::get(&format!("http://{addr}/metrics")).call().unwrap();
+        assert_eq!(resp.header("Content-Type"), Some(CONTENT_TYPE));
+        assert_eq!(resp.into_string().unwrap(), "ferro_up 1\n# EOF\n");
+
+        let missing = ureq::get(&format!("http://{addr}/nope")).call();
+        assert!(matches!(missing, Err(ureq::Error::Status(404, _))));
+        server.join().unwrap();
+    }
+}
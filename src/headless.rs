@@ -0,0 +1,303 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sysinfo::{Disks, System};
+
+use crate::alerts::{AlertEngine, AlertEvent, AlertStatus};
+use crate::network::update_net_rates;
+use crate::services::collect_services;
+use crate::system::{
+    collect_mount_rows, collect_proc_rows, disks_table_filtered, format_snapshot, new_system,
+    read_psi, refresh, snapshot, top_process_rows, update_cpu_breakdown, update_disk_io_rates,
+    update_proc_io_rates,
+};
+use crate::types::{
+    AppState, DiskRow, NetRow, ProcRow, ProcSort, ServiceRow, ServiceScope, VmSnapshot,
+};
+use crate::update::VERSION;
+use crate::utils::unix_ms_now;
+
+/// Bump when a field is removed or changes meaning; adding fields is compatible.
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+// ── Collector ─────────────────────────────────────────────────────────────────
+
+/// Runs the same collectors as the TUI loop, without a terminal.
+pub struct HeadlessCollector {
+    pub system: System,
+    pub disks: Disks,
+    pub app: AppState,
+    /// The exporter has no per-process metrics, so it skips the process table.
+    pub with_processes: bool,
+    mount_limit: usize,
+    /// `df` is a subprocess; between refreshes only the I/O rates update.
+    mounts_every: Duration,
+    mounts_at: Option<Instant>,
+    last_sample_at: Option<Instant>,
+}
+
+impl HeadlessCollector {
+    /// Lists the dashboard's top mounts on every sample.
+    pub fn new(with_processes: bool) -> Self {
+        Self::with_mounts(with_processes, 12, Duration::ZERO)
+    }
+
+    pub fn with_mounts(with_processes: bool, mount_limit: usize, mounts_every: Duration) -> Self {
+        let mut collector = Self {
+            system: new_system(),
+            disks: Disks::new_with_refreshed_list(),
+            app: AppState::default(),
+            with_processes,
+            mount_limit,
+            mounts_every,
+            mounts_at: None,
+            last_sample_at: None,
+        };
+        // Prime the delta-based collectors so the first real sample has rates.
+        collector.sample();
+        collector
+    }
+
+    pub fn sample(&mut self) -> VmSnapshot {
+        let app = &mut self.app;
+        refresh(&mut self.system, &mut self.disks, self.with_processes);
+
+        let elapsed = self
+            .last_sample_at
+            .map(|t| t.elapsed().as_secs_f64())
+            .unwrap_or(0.0);
+        self.last_sample_at = Some(Instant::now());
+
+        let mounts_due = self
+            .mounts_at
+            .map(|t| t.elapsed() >= self.mounts_every)
+            .unwrap_or(true);
+        if mounts_due {
+            let (limit, show_all) = (self.mount_limit, app.dash_show_all_mounts);
+            app.dash_mount_rows = collect_mount_rows(limit, show_all)
+                .unwrap_or_else(|| disks_table_filtered(&self.disks, limit, show_all));
+            self.mounts_at = Some(Instant::now());
+        }
+        update_disk_io_rates(
+            &mut app.dash_mount_rows,
+            &mut app.dash_diskstats_prev,
+            elapsed,
+        );
+        update_net_rates(&mut app.net_rows, &mut app.net_prev, elapsed);
+        update_cpu_breakdown(&mut app.cpu_breakdown, &mut app.cpu_stat_prev);
+        app.psi = read_psi();
+        if self.with_processes {
+            app.proc_rows = collect_proc_rows(&self.system);
+            update_proc_io_rates(&mut app.proc_rows, &mut app.proc_io_prev, elapsed);
+        }
+
+        snapshot(&self.system, app.cpu_breakdown, app.psi)
+    }
+
+    /// Snapshots see every mount, not just the dashboard's top rows.
+    pub fn evaluate_alerts(&mut self, vm: &VmSnapshot, services: &[ServiceRow]) -> Vec<AlertEvent> {
+        let app = &mut self.app;
+        if app.alerts.needs_mounts() {
+            app.alert_mount_rows = collect_mount_rows(64, true)
+                .unwrap_or_else(|| disks_table_filtered(&self.disks, 64, true));
+        }
+        app.alerts
+            .evaluate(vm, &app.alert_mount_rows, services, unix_ms_now())
+    }
+}
+
+// ── JSON document ─────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+struct ServicesDoc {
+    available: bool,
+    error: Option<String>,
+    rows: Vec<ServiceRow>,
+}
+
+#[derive(Serialize)]
+struct AlertDoc<'a> {
+    name: &'a str,
+    rule: &'a str,
+    status: AlertStatus,
+    value: Option<&'a str>,
+    since_unix_ms: Option<u64>,
+}
+
+fn alert_docs(engine: &AlertEngine) -> Vec<AlertDoc<'_>> {
+    engine
+        .rules
+        .iter()
+        .map(|r| AlertDoc {
+            name: &r.rule.name,
+            rule: &r.rule.expr,
+            status: r.status,
+            value: r.value.as_deref(),
+            since_unix_ms: match r.status {
+                AlertStatus::Firing => r.fired_at_ms,
+                AlertStatus::Pending => r.matched_since_ms,
+                AlertStatus::Ok => None,
+            },
+        })
+        .collect()
+}
+
+/// A process in the `top_*` lists. Kept apart from `ProcRow` so the
+/// schema doesn't follow the TUI's internal fixed-point fields.
+#[derive(Serialize)]
+struct ProcDoc {
+    pid: i32,
+    ppid: Option<i32>,
+    name: String,
+    cpu_pct: f64,
+    mem_bytes: u64,
+    swap_bytes: u64,
+    status: &'static str,
+    read_bps: u64,
+    write_bps: u64,
+}
+
+fn top_proc_docs(procs: &[ProcRow], sort: ProcSort) -> Vec<ProcDoc> {
+    top_process_rows(procs, sort, 10)
+        .into_iter()
+        .map(|p| ProcDoc {
+            pid: p.pid,
+            ppid: p.ppid,
+            name: p.name,
+            cpu_pct: p.cpu_x10 as f64 / 10.0,
+            mem_bytes: p.mem_bytes,
+            swap_bytes: p.swap_bytes,
+            status: p.status,
+            read_bps: p.read_bps,
+            write_bps: p.write_bps,
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct SnapshotDoc<'a> {
+    schema: &'static str,
+    schema_version: u32,
+    ferro_version: &'static str,
+    hostname: &'a str,
+    timestamp_unix_ms: u64,
+    interval_ms: u64,
+    sample: u32,
+    vm: &'a VmSnapshot,
+    top_cpu: Vec<ProcDoc>,
+    top_mem: Vec<ProcDoc>,
+    top_io: Vec<ProcDoc>,
+    mounts: &'a [DiskRow],
+    network: &'a [NetRow],
+    services: ServicesDoc,
+    alerts: Vec<AlertDoc<'a>>,
+}
+
+fn services_doc() -> ServicesDoc {
+    match collect_services(ServiceScope::System) {
+        Ok(rows) => ServicesDoc {
+            available: true,
+            error: None,
+            rows,
+        },
+        Err(err) => ServicesDoc {
+            available: false,
+            error: Some(err),
+            rows: Vec::new(),
+        },
+    }
+}
+
+// Flush per sample so `--samples N` output can be streamed into other tools.
+fn write_line(out: &mut impl Write, text: &str) -> Result<(), String> {
+    writeln!(out, "{text}")
+        .and_then(|_| out.flush())
+        .map_err(|e| format!("failed to write snapshot: {e}"))
+}
+
+// ── `ferro snapshot` ──────────────────────────────────────────────────────────
+
+pub fn run_snapshot(
+    interval_ms: u64,
+    samples: u32,
+    json: bool,
+    pretty: bool,
+) -> Result<(), String> {
+    let interval = Duration::from_millis(interval_ms);
+    let mut collector = HeadlessCollector::new(true);
+    let mut stdout = io::stdout().lock();
+
+    for n in 1..=samples {
+        std::thread::sleep(interval);
+        let vm = collector.sample();
+        let services = services_doc();
+        collector.evaluate_alerts(&vm, &services.rows);
+
+        if !json {
+            let text = format_snapshot(&vm, &collector.app, &collector.system, &collector.disks);
+            let sep = if n > 1 { "\n" } else { "" };
+            write_line(&mut stdout, &format!("{sep}{text}"))?;
+            continue;
+        }
+
+        let procs = &collector.app.proc_rows;
+        let doc = SnapshotDoc {
+            schema: "ferromon.snapshot",
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            ferro_version: VERSION,
+            hostname: &collector.app.hostname,
+            timestamp_unix_ms: unix_ms_now(),
+            interval_ms,
+            sample: n,
+            vm: &vm,
+            top_cpu: top_proc_docs(procs, ProcSort::Cpu),
+            top_mem: top_proc_docs(procs, ProcSort::Mem),
+            top_io: top_proc_docs(procs, ProcSort::Io),
+            mounts: &collector.app.dash_mount_rows,
+            network: &collector.app.net_rows,
+            services,
+            alerts: alert_docs(&collector.app.alerts),
+        };
+        let out = if pretty {
+            serde_json::to_string_pretty(&doc)
+        } else {
+            serde_json::to_string(&doc)
+        }
+        .map_err(|e| format!("failed to encode snapshot: {e}"))?;
+        write_line(&mut stdout, &out)?;
+    }
+
+    Ok(())
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_processes_report_cpu_percent() {
+        let row = |pid, cpu_x10| ProcRow {
+            pid,
+            ppid: Some(1),
+            name: format!("p{pid}"),
+            cpu_x10,
+            mem_bytes: 4096,
+            swap_bytes: 0,
+            status: "Run",
+            is_thread: false,
+            read_bps: 0,
+            write_bps: 0,
+        };
+        let docs = top_proc_docs(&[row(10, 5), row(11, 125)], ProcSort::Cpu);
+        let json = serde_json::to_value(&docs).unwrap();
+
+        assert_eq!(json[0]["pid"], 11);
+        assert_eq!(json[0]["cpu_pct"], 12.5);
+        assert_eq!(json[1]["cpu_pct"], 0.5);
+        assert!(json[0].get("cpu_x10").is_none());
+        assert!(json[0].get("is_thread").is_none());
+    }
+}
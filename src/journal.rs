@@ -0,0 +1,132 @@
+//! `journalctl -o json` records as structured log entries.
+
+use serde_json::Value;
+
+use crate::types::LogEntry;
+use crate::utils::format_local_timestamp;
+
+/// `MESSAGE` (and any other field) is a string, or an array of bytes when
+/// it isn't valid UTF-8.
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .filter_map(|b| b.as_u64().map(|b| b as u8))
+                .collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// One line of `journalctl -o json`. `None` for anything that isn't a
+/// record with a timestamp.
+pub fn parse_journal_record(record: &str) -> Option<LogEntry> {
+    let Value::Object(map) = serde_json::from_str(record).ok()? else {
+        return None;
+    };
+    let mut fields: Vec<(String, String)> = map
+        .iter()
+        .map(|(k, v)| (k.clone(), field_text(v)))
+        .collect();
+    fields.sort();
+    let get = |k: &str| map.get(k).map(field_text).unwrap_or_default();
+    let usec: u64 = get("__REALTIME_TIMESTAMP").parse().ok()?;
+    // systemd's own messages about a unit carry it in UNIT=; everything
+    // else is attributed by cgroup.
+    let unit = ["UNIT", "USER_UNIT", "_SYSTEMD_USER_UNIT", "_SYSTEMD_UNIT"]
+        .into_iter()
+        .map(get)
+        .find(|u| !u.is_empty())
+        .unwrap_or_default();
+    let identifier = match get("SYSLOG_IDENTIFIER") {
+        ident if ident.is_empty() => get("_COMM"),
+        ident => ident,
+    };
+    Some(LogEntry {
+        timestamp: format_local_timestamp(usec / 1000),
+        priority: get("PRIORITY").parse().ok(),
+        unit,
+        identifier,
+        pid: get("_PID").parse().ok(),
+        hostname: get("_HOSTNAME"),
+        message: get("MESSAGE"),
+        fields,
+    })
+}
+
+/// A syslog line: no structure, the whole line is the message.
+pub fn raw_entry(line: &str) -> LogEntry {
+    LogEntry {
+        message: line.to_string(),
+        ..LogEntry::default()
+    }
+}
+
+/// Back to a single `short`-style line, for the service detail view and
+/// anywhere else that only has room for text.
+pub fn format_log_line(entry: &LogEntry) -> String {
+    if entry.timestamp.is_empty() {
+        return entry.message.clone();
+    }
+    let pid = entry.pid.map(|p| format!("[{p}]")).unwrap_or_default();
+    format!(
+        "{} {} {}{pid}: {}",
+        entry.timestamp, entry.hostname, entry.identifier, entry.message
+    )
+}
+
+pub fn priority_label(priority: Option<u8>) -> &'static str {
+    match priority {
+        Some(0) => "emerg",
+        Some(1) => "alert",
+        Some(2) => "crit",
+        Some(3) => "err",
+        Some(4) => "warning",
+        Some(5) => "notice",
+        Some(6) => "info",
+        Some(7) => "debug",
+        _ => "-",
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_journal_json_records() {
+        let record = r#"{"__REALTIME_TIMESTAMP":"1700000000123456","_HOSTNAME":"web1","SYSLOG_IDENTIFIER":"systemd","_PID":"1","PRIORITY":"3","_SYSTEMD_UNIT":"init.scope","UNIT":"nginx.service","MESSAGE":"nginx.service: Failed with result 'exit-code'."}"#;
+        let e = parse_journal_record(record).unwrap();
+        // Local time, like journalctl's own output.
+        assert_eq!(e.timestamp, format_local_timestamp(1_700_000_000_123));
+        assert_eq!(e.priority, Some(3));
+        assert_eq!(priority_label(e.priority), "err");
+        assert_eq!(e.unit, "nginx.service");
+        assert_eq!(e.pid, Some(1));
+        assert!(e
+            .fields
+            .contains(&("_SYSTEMD_UNIT".to_string(), "init.scope".to_string())));
+        assert_eq!(
+            format_log_line(&e),
+            format!(
+                "{} web1 systemd[1]: nginx.service: Failed with result 'exit-code'.",
+                e.timestamp
+            )
+        );
+
+        // Non-UTF-8 messages arrive as byte arrays.
+        let bytes =
+            r#"{"__REALTIME_TIMESTAMP":"1700000000000000","_COMM":"app","MESSAGE":[104,105]}"#;
+        let e = parse_journal_record(bytes).unwrap();
+        assert_eq!((e.identifier.as_str(), e.message.as_str()), ("app", "hi"));
+        assert_eq!(e.priority, None);
+
+        assert_eq!(parse_journal_record("-- No entries --"), None);
+    }
+}
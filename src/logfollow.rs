@@ -0,0 +1,292 @@
+//! Follow mode for the logs screen: stream new lines from `journalctl -f`
+//! (or the syslog file) into a bounded buffer instead of re-reading the
+//! last 80 every few seconds.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::journal::{parse_journal_record, raw_entry};
+use crate::logsearch::LogFilter;
+use crate::services::{journal_filter_args, syslog_line_matches, syslog_path};
+use crate::types::{AppState, LogEntry, LogSeverity, LogState, LogUnitFilter, Screen};
+
+/// Lines kept while following; older ones drop off the top.
+pub const FOLLOW_BUFFER: usize = 2000;
+/// How much of the syslog file to show when following starts.
+const SYSLOG_BACKLOG_BYTES: u64 = 64 * 1024;
+const SYSLOG_POLL: Duration = Duration::from_millis(500);
+
+/// A running follow stream. Dropping it stops the stream and reaps
+/// `journalctl`.
+pub struct LogFollow {
+    stop: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl Drop for LogFollow {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(mut child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Returns how many of the oldest entries were dropped to make room.
+pub fn push_bounded(entries: &mut VecDeque<LogEntry>, entry: LogEntry) -> usize {
+    entries.push_back(entry);
+    let excess = entries.len().saturating_sub(FOLLOW_BUFFER);
+    entries.drain(..excess);
+    excess
+}
+
+/// Streams until stopped. Returns journalctl's complaint if it exits on its
+/// own, e.g. no journal on this host.
+fn stream_journal(
+    state: &LogState,
+    filter: Option<&LogFilter>,
+    stop: &AtomicBool,
+    child: &Mutex<Option<Child>>,
+) -> Result<(), String> {
+    let (stdout, stderr) = {
+        let mut guard = child.lock().unwrap();
+        let c = guard.as_mut().ok_or("journalctl stopped")?;
+        (c.stdout.take(), c.stderr.take())
+    };
+    let stdout = stdout.ok_or("journalctl has no stdout")?;
+    // Drained on its own so a chatty journalctl can't fill the pipe and stall.
+    let stderr = stderr.map(|mut stderr| {
+        std::thread::spawn(move || {
+            let mut message = String::new();
+            let _ = stderr.read_to_string(&mut message);
+            message
+        })
+    });
+    for record in BufReader::new(stdout).lines() {
+        let Ok(record) = record else { break };
+        let Some(entry) = parse_journal_record(&record) else {
+            continue;
+        };
+        if filter.is_some_and(|f| !f.matches(&entry)) {
+            continue;
+        }
+        let mut st = state.inner.lock().unwrap();
+        if stop.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        st.dropped += push_bounded(&mut st.entries, entry);
+        st.last_updated_at = Some(std::time::SystemTime::now());
+    }
+    if stop.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    if let Some(mut c) = child.lock().unwrap().take() {
+        let _ = c.wait();
+    }
+    let message = stderr
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    let message = message.trim();
+    Err(if message.is_empty() {
+        "journalctl -f exited".to_string()
+    } else {
+        message.to_string()
+    })
+}
+
+// ── Syslog tail ───────────────────────────────────────────────────────────────
+
+#[cfg(unix)]
+fn file_id(meta: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.ino()
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &std::fs::Metadata) -> u64 {
+    0
+}
+
+/// Reads what was appended since the last poll, starting over when the file
+/// is rotated (new inode) or truncated.
+fn tail_syslog(
+    path: &str,
+    unit: Option<&str>,
+    severity: LogSeverity,
+    filter: Option<&LogFilter>,
+    state: &LogState,
+    stop: &AtomicBool,
+) {
+    let mut id = None;
+    let mut pos = 0u64;
+    let mut partial = String::new();
+    while !stop.load(Ordering::SeqCst) {
+        if let Ok(mut file) = File::open(path) {
+            let meta = file.metadata().ok();
+            let len = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+            let now_id = meta.as_ref().map(file_id);
+            let first = id.is_none();
+            if first {
+                pos = len.saturating_sub(SYSLOG_BACKLOG_BYTES);
+            } else if now_id != id || len < pos {
+                pos = 0;
+                partial.clear();
+            }
+            id = now_id;
+            let mut chunk = Vec::new();
+            // The backlog starts mid-file; its first line is likely cut.
+            let skip = usize::from(first && pos > 0);
+            if len > pos && file.seek(SeekFrom::Start(pos)).is_ok() {
+                let _ = file.take(len - pos).read_to_end(&mut chunk);
+                pos += chunk.len() as u64;
+            }
+            partial.push_str(&String::from_utf8_lossy(&chunk));
+            let complete = partial.rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let text: String = partial.drain(..complete).collect();
+            let mut st = state.inner.lock().unwrap();
+            if stop.load(Ordering::SeqCst) {
+                return;
+            }
+            for line in text.lines().skip(skip) {
+                if !syslog_line_matches(line, unit, severity) {
+                    continue;
+                }
+                let entry = raw_entry(line);
+                if filter.is_some_and(|f| !f.matches(&entry)) {
+                    continue;
+                }
+                st.dropped += push_bounded(&mut st.entries, entry);
+            }
+            st.last_updated_at = Some(std::time::SystemTime::now());
+        }
+        std::thread::sleep(SYSLOG_POLL);
+    }
+}
+
+// ── App glue ──────────────────────────────────────────────────────────────────
+
+/// (Re)starts following with the screen's current unit and severity filters.
+pub fn start_log_follow(app: &mut AppState) {
+    // Drop the old stream first so it can't append after the clear below.
+    app.log_follow = None;
+    let unit = match app.log_unit_filter {
+        LogUnitFilter::Selected => app.log_selected_unit.clone(),
+        LogUnitFilter::All => None,
+    };
+    let user = app.log_selected_user && unit.is_some();
+    let severity = app.log_severity;
+    // Filtered as lines arrive rather than with `--grep`, so a journalctl
+    // without pattern support still streams.
+    let filter = app.log_filter.clone();
+
+    let mut cmd = Command::new("journalctl");
+    cmd.args(["--no-pager", "-o", "json", "-f", "-n", "80"]);
+    journal_filter_args(&mut cmd, unit.as_deref(), user, severity);
+    let spawned = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let source = if spawned.is_ok() {
+        "journalctl -f".to_string()
+    } else {
+        match syslog_path() {
+            Some(path) => format!("syslog ({path}), following"),
+            None => "journalctl -f".to_string(),
+        }
+    };
+    {
+        let mut st = app.log_state.inner.lock().unwrap();
+        st.following = true;
+        st.entries.clear();
+        st.dropped = 0;
+        st.error = None;
+        st.source = source;
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let journal = spawned.is_ok();
+    let child = Arc::new(Mutex::new(spawned.ok()));
+    let (state, thread_stop, thread_child) = (app.log_state.clone(), stop.clone(), child.clone());
+    std::thread::spawn(move || {
+        let journal_error = if journal {
+            match stream_journal(&state, filter.as_ref(), &thread_stop, &thread_child) {
+                Ok(()) => return,
+                Err(err) => err,
+            }
+        } else {
+            "journalctl not available".to_string()
+        };
+        match syslog_path() {
+            Some(path) => {
+                state.inner.lock().unwrap().source = format!("syslog ({path}), following");
+                tail_syslog(
+                    path,
+                    unit.as_deref(),
+                    severity,
+                    filter.as_ref(),
+                    &state,
+                    &thread_stop,
+                );
+            }
+            None => {
+                let mut st = state.inner.lock().unwrap();
+                if !thread_stop.load(Ordering::SeqCst) {
+                    st.error = Some(journal_error);
+                }
+            }
+        }
+    });
+
+    app.log_follow = Some(LogFollow { stop, child });
+    app.log_follow_pinned = true;
+}
+
+pub fn stop_log_follow(app: &mut AppState) {
+    app.log_follow = None;
+    app.log_state.inner.lock().unwrap().following = false;
+}
+
+/// Nothing shows the stream once the logs screen is left, so it stops
+/// there; coming back goes to periodic reads until `f` is pressed again.
+pub fn stop_log_follow_off_screen(app: &mut AppState) {
+    if app.log_follow.is_some() && !matches!(app.screen, Screen::Logs) {
+        stop_log_follow(app);
+    }
+}
+
+/// `f` on the logs screen. Turning it off goes back to periodic reads.
+pub fn toggle_log_follow(app: &mut AppState) {
+    if app.log_follow.is_some() {
+        stop_log_follow(app);
+        crate::services::refresh_logs(app, true);
+    } else {
+        start_log_follow(app);
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follow_buffer_drops_the_oldest_entries() {
+        let mut entries = VecDeque::new();
+        let mut dropped = 0;
+        for i in 0..FOLLOW_BUFFER + 5 {
+            dropped += push_bounded(&mut entries, raw_entry(&i.to_string()));
+        }
+        assert_eq!(dropped, 5);
+        assert_eq!(entries.len(), FOLLOW_BUFFER);
+        assert_eq!(entries.front().map(|e| e.message.as_str()), Some("5"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Search and regex filtering on the logs screen.
+//!
+//! `/` is an incremental search over the loaded entries: matches are
+//! highlighted and `n`/`N` jump between them. `&` (as in `less`) sets a
+//! filter that decides which entries are loaded at all; `!pattern` drops
+//! matching entries instead of keeping them.
+
+use std::collections::VecDeque;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use regex::Regex;
+
+use crate::services::{is_text_input_key, refresh_logs};
+use crate::types::{AppState, LogEntry, Screen};
+
+/// Case-insensitive unless the pattern has an uppercase letter, the same
+/// rule `journalctl --grep` uses, so both sides of a filter agree.
+pub fn smart_case_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    if pattern.chars().any(char::is_uppercase) {
+        Regex::new(pattern)
+    } else {
+        Regex::new(&format!("(?i){pattern}"))
+    }
+}
+
+/// The `/` query as a regex. Half-typed patterns like `foo(` are searched
+/// for literally rather than matching nothing.
+pub fn search_regex(query: &str) -> Option<Regex> {
+    if query.is_empty() {
+        return None;
+    }
+    smart_case_regex(query)
+        .or_else(|_| smart_case_regex(&regex::escape(query)))
+        .ok()
+}
+
+#[derive(Clone, Debug)]
+pub struct LogFilter {
+    /// As typed, including a leading `!`.
+    pub input: String,
+    pub exclude: bool,
+    pattern: String,
+    regex: Regex,
+}
+
+impl LogFilter {
+    /// `None` for an empty pattern, which clears the filter.
+    pub fn parse(input: &str) -> Result<Option<Self>, String> {
+        let (exclude, pattern) = match input.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, input),
+        };
+        if pattern.is_empty() {
+            return Ok(None);
+        }
+        let regex = smart_case_regex(pattern).map_err(|e| format!("invalid regex: {e}"))?;
+        Ok(Some(Self {
+            input: input.to_string(),
+            exclude,
+            pattern: pattern.to_string(),
+            regex,
+        }))
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.regex.is_match(&entry.message) != self.exclude
+    }
+
+    /// The `--grep` argument. journalctl's PCRE2 has no inverted match, so
+    /// an exclude becomes a negative lookahead.
+    pub fn journal_grep(&self) -> String {
+        if self.exclude {
+            format!("^(?!.*(?:{}))", self.pattern)
+        } else {
+            self.pattern.clone()
+        }
+    }
+}
+
+/// The next entry whose message matches, starting at `from` and wrapping.
+pub fn find_match(
+    entries: &VecDeque<LogEntry>,
+    re: &Regex,
+    from: usize,
+    forward: bool,
+) -> Option<usize> {
+    let len = entries.len();
+    (0..len)
+        .map(|step| {
+            if forward {
+                (from + step) % len
+            } else {
+                (from % len + len - step) % len
+            }
+        })
+        .find(|&i| re.is_match(&entries[i].message))
+}
+
+/// Moves the selection to a match. `skip_current` is `n`/`N`; typing
+/// keeps the selection if it still matches.
+fn jump_to_match(app: &mut AppState, forward: bool, skip_current: bool) {
+    let Some(re) = search_regex(&app.log_search) else {
+        return;
+    };
+    let found = {
+        let st = app.log_state.inner.lock().unwrap();
+        let len = st.entries.len();
+        if len == 0 {
+            return;
+        }
+        let selected = if st.following && app.log_follow_pinned {
+            len - 1
+        } else {
+            (app.logs_scroll as usize).min(len - 1)
+        };
+        let from = match (skip_current, forward) {
+            (false, _) => selected,
+            (true, true) => selected + 1,
+            (true, false) => selected + len - 1,
+        };
+        find_match(&st.entries, &re, from, forward)
+    };
+    if let Some(idx) = found {
+        app.logs_scroll = idx as u16;
+        app.log_follow_pinned = false;
+    }
+}
+
+fn apply_log_filter(app: &mut AppState) {
+    match LogFilter::parse(&app.log_filter_input) {
+        Ok(filter) => {
+            app.log_filter = filter;
+            app.log_filter_active = false;
+            app.log_filter_error = None;
+            app.logs_scroll = 0;
+            refresh_logs(app, true);
+        }
+        Err(err) => app.log_filter_error = Some(err),
+    }
+}
+
+pub fn handle_log_search_key(app: &mut AppState, key: &KeyEvent) -> bool {
+    if !matches!(app.screen, Screen::Logs) {
+        return false;
+    }
+
+    if app.log_search_active {
+        match key.code {
+            KeyCode::Enter => {
+                app.log_search_active = false;
+            }
+            KeyCode::Esc => {
+                app.log_search.clear();
+                app.log_search_active = false;
+            }
+            KeyCode::Backspace => {
+                if app.log_search.pop().is_none() {
+                    app.log_search_active = false;
+                }
+                jump_to_match(app, true, false);
+            }
+            KeyCode::Char(c) if is_text_input_key(key) => {
+                app.log_search.push(c);
+                jump_to_match(app, true, false);
+            }
+            _ => return false,
+        }
+        return true;
+    }
+
+    if app.log_filter_active {
+        match key.code {
+            KeyCode::Enter => apply_log_filter(app),
+            KeyCode::Esc => {
+                app.log_filter_active = false;
+                app.log_filter_error = None;
+            }
+            KeyCode::Backspace => {
+                app.log_filter_input.pop();
+                app.log_filter_error = None;
+            }
+            KeyCode::Char(c) if is_text_input_key(key) => {
+                app.log_filter_input.push(c);
+                app.log_filter_error = None;
+            }
+            _ => return false,
+        }
+        return true;
+    }
+
+    match key.code {
+        KeyCode::Char('/') if is_text_input_key(key) => {
+            app.log_search.clear();
+            app.log_search_active = true;
+        }
+        KeyCode::Char('&') if is_text_input_key(key) => {
+            app.log_filter_input = app
+                .log_filter
+                .as_ref()
+                .map(|f| f.input.clone())
+                .unwrap_or_default();
+            app.log_filter_active = true;
+        }
+        // Without a search, `n` still goes to the network screen.
+        KeyCode::Char('n') if !app.log_search.is_empty() => jump_to_match(app, true, true),
+        KeyCode::Char('N') if !app.log_search.is_empty() => jump_to_match(app, false, true),
+        KeyCode::Esc if !app.log_expanded && !app.log_search.is_empty() => {
+            app.log_search.clear();
+        }
+        KeyCode::Esc if !app.log_expanded && app.log_filter.is_some() => {
+            app.log_filter = None;
+            app.log_filter_input.clear();
+            app.logs_scroll = 0;
+            refresh_logs(app, true);
+        }
+        _ => return false,
+    }
+    true
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::raw_entry;
+
+    #[test]
+    fn filters_and_searches_log_messages() {
+        let entries: VecDeque<LogEntry> = ["Started nginx", "upstream timed out", "Stopped nginx"]
+            .into_iter()
+            .map(raw_entry)
+            .collect();
+
+        let keep = LogFilter::parse("NGINX|timed").unwrap().unwrap();
+        assert!(
+            !keep.matches(&entries[0]),
+            "uppercase makes it case-sensitive"
+        );
+        assert!(keep.matches(&entries[1]));
+        assert_eq!(keep.journal_grep(), "NGINX|timed");
+
+        let drop = LogFilter::parse("!nginx").unwrap().unwrap();
+        let kept: Vec<_> = entries.iter().filter(|e| drop.matches(e)).collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(drop.journal_grep(), "^(?!.*(?:nginx))");
+
+        assert!(LogFilter::parse("").unwrap().is_none());
+        assert!(LogFilter::parse("!").unwrap().is_none());
+        assert!(LogFilter::parse("(").is_err());
+
+        let re = search_regex("nginx").unwrap();
+        assert_eq!(find_match(&entries, &re, 1, true), Some(2));
+        assert_eq!(find_match(&entries, &re, 3, true), Some(0), "wraps around");
+        assert_eq!(find_match(&entries, &re, 1, false), Some(0));
+        assert_eq!(find_match(&entries, &re, 2, false), Some(2));
+        // A half-typed group is searched for literally.
+        assert!(search_regex("out (").is_some());
+    }
+}
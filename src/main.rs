@@ -1,8 +1,32 @@
+mod alerts;
 mod app;
 mod cli;
+mod config;
+#[cfg(target_os = "linux")]
+mod dbus;
 mod disk;
+mod exporter;
+mod headless;
+mod journal;
+mod logfollow;
+mod logsearch;
+mod network;
+mod notify;
+mod procaction;
+mod procinfo;
+mod procrestart;
+mod proctree;
+mod procview;
+mod recording;
+mod replay;
 mod services;
+mod svcaction;
+mod svcinfo;
+mod svcusage;
 mod system;
+#[cfg(target_os = "linux")]
+mod systemd;
+mod timers;
 mod types;
 mod ui;
 mod update;
@@ -20,12 +44,12 @@ use crossterm::terminal::{
 };
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use sysinfo::{Disks, ProcessRefreshKind, RefreshKind, System};
+use sysinfo::Disks;
 
 use app::run_app;
 use cli::{parse_args, print_cli_help};
-use system::refresh;
-use types::AppState;
+use system::{new_system, refresh};
+use types::{AppState, Command};
 use update::{check_update, load_update_cache, VERSION};
 
 struct TerminalGuard {
@@ -89,29 +113,57 @@ fn run() -> Result<(), String> {
         print_cli_help();
         return Ok(());
     }
+    if let Command::Config { print_defaults } = args.command {
+        return config::run_config(print_defaults);
+    }
+
+    let cfg = config::load_config(config::config_path().as_deref())?;
+    let tick_ms = args.tick_ms.unwrap_or(cfg.general.tick_ms);
+    let mouse = cfg.general.mouse && !args.no_mouse;
+    config::install_config(cfg);
+
+    if let Command::Snapshot {
+        json,
+        samples,
+        pretty,
+    } = args.command
+    {
+        return headless::run_snapshot(tick_ms, samples, json, pretty);
+    }
+    if let Command::Serve { listen } = &args.command {
+        return exporter::run_serve(listen, tick_ms);
+    }
+    if let Command::Record { path } = &args.command {
+        return recording::run_record(path, tick_ms);
+    }
+    if let Command::Replay { path } = &args.command {
+        // Load before touching the terminal so errors print normally.
+        let (header, frames) = recording::load_recording(path)?;
+        let mut guard = TerminalGuard::enter(mouse).map_err(|e| e.to_string())?;
+        let mut terminal =
+            Terminal::new(CrosstermBackend::new(io::stdout())).map_err(|e| e.to_string())?;
+        let out = replay::run_replay(&mut terminal, &header, &frames).map_err(|e| e.to_string());
+        guard.restore().map_err(|e| e.to_string())?;
+        return out;
+    }
 
-    let mut guard = TerminalGuard::enter(!args.no_mouse).map_err(|e| e.to_string())?;
+    let mut guard = TerminalGuard::enter(mouse).map_err(|e| e.to_string())?;
 
     let stdout = io::stdout();
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).map_err(|e| e.to_string())?;
 
-    // Keep dashboard refresh light, but allow process refresh when needed.
-    let refresh_kind = RefreshKind::new()
-        .with_cpu(sysinfo::CpuRefreshKind::everything())
-        .with_memory(sysinfo::MemoryRefreshKind::everything())
-        .with_processes(ProcessRefreshKind::everything());
-    let mut system = System::new_with_specifics(refresh_kind);
+    let mut system = new_system();
 
     let mut disks = Disks::new_with_refreshed_list();
 
     refresh(&mut system, &mut disks, true);
 
-    let tick_rate = Duration::from_millis(args.tick_ms);
+    let tick_rate = Duration::from_millis(tick_ms);
     let mut last_tick = Instant::now();
 
     let mut app = AppState {
-        tick_ms: args.tick_ms,
+        tick_ms,
         ..Default::default()
     };
 
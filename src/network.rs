@@ -0,0 +1,188 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{NetCounters, NetHistory, NetRow};
+
+// ── /proc/net/dev parsing ─────────────────────────────────────────────────────
+
+pub fn parse_net_dev(content: &str) -> Vec<(String, NetCounters)> {
+    let mut out = Vec::new();
+    // First two lines are the column headers.
+    for line in content.lines().skip(2) {
+        let Some((name, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let fields: Vec<u64> = rest
+            .split_whitespace()
+            .map(|f| f.parse::<u64>().unwrap_or(0))
+            .collect();
+        if fields.len() < 12 {
+            continue;
+        }
+        out.push((
+            name.trim().to_string(),
+            NetCounters {
+                rx_bytes: fields[0],
+                rx_packets: fields[1],
+                rx_errs: fields[2],
+                rx_drop: fields[3],
+                tx_bytes: fields[8],
+                tx_packets: fields[9],
+                tx_errs: fields[10],
+                tx_drop: fields[11],
+            },
+        ));
+    }
+    out
+}
+
+#[cfg(target_os = "linux")]
+fn read_net_dev() -> Vec<(String, NetCounters)> {
+    std::fs::read_to_string("/proc/net/dev")
+        .map(|content| parse_net_dev(&content))
+        .unwrap_or_default()
+}
+
+// ── Interface rates ───────────────────────────────────────────────────────────
+
+pub fn net_rows_from_counters(
+    current: &[(String, NetCounters)],
+    prev_stats: &HashMap<String, NetCounters>,
+    elapsed_secs: f64,
+) -> Vec<NetRow> {
+    let rate = |cur: u64, prev: u64| -> u64 {
+        if elapsed_secs <= 0.0 {
+            0
+        } else {
+            (cur.saturating_sub(prev) as f64 / elapsed_secs) as u64
+        }
+    };
+
+    current
+        .iter()
+        .map(|(name, c)| {
+            let errs = c.rx_errs.saturating_add(c.tx_errs);
+            let drops = c.rx_drop.saturating_add(c.tx_drop);
+            let mut row = NetRow {
+                name: name.clone(),
+                errs,
+                drops,
+                rx_total: c.rx_bytes,
+                tx_total: c.tx_bytes,
+                ..Default::default()
+            };
+            if let Some(p) = prev_stats.get(name) {
+                row.rx_bps = rate(c.rx_bytes, p.rx_bytes);
+                row.tx_bps = rate(c.tx_bytes, p.tx_bytes);
+                row.rx_pps = rate(c.rx_packets, p.rx_packets);
+                row.tx_pps = rate(c.tx_packets, p.tx_packets);
+                row.errs_delta = errs.saturating_sub(p.rx_errs.saturating_add(p.tx_errs));
+                row.drops_delta = drops.saturating_sub(p.rx_drop.saturating_add(p.tx_drop));
+            }
+            row
+        })
+        .collect()
+}
+
+pub fn update_net_rates(
+    rows: &mut Vec<NetRow>,
+    prev_stats: &mut HashMap<String, NetCounters>,
+    elapsed_secs: f64,
+) {
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (rows, prev_stats, elapsed_secs);
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let current = read_net_dev();
+        *rows = net_rows_from_counters(&current, prev_stats, elapsed_secs);
+        *prev_stats = current.into_iter().collect();
+    }
+}
+
+pub fn push_net_history(
+    history: &mut HashMap<String, NetHistory>,
+    rows: &[NetRow],
+    max_len: usize,
+) {
+    let live: HashSet<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+    history.retain(|name, _| live.contains(name.as_str()));
+
+    for row in rows {
+        let entry = history.entry(row.name.clone()).or_default();
+        entry.rx.push_back(row.rx_bps);
+        entry.tx.push_back(row.tx_bps);
+        while entry.rx.len() > max_len {
+            entry.rx.pop_front();
+        }
+        while entry.tx.len() > max_len {
+            entry.tx.pop_front();
+        }
+    }
+}
+
+/// Busiest interfaces first; used by the dashboard panel.
+pub fn net_rows_by_throughput(rows: &[NetRow]) -> Vec<NetRow> {
+    use std::cmp::Reverse;
+
+    let mut rows = rows.to_vec();
+    rows.sort_by_key(|r| {
+        (
+            Reverse(r.rx_bps.saturating_add(r.tx_bps)),
+            Reverse(r.rx_total.saturating_add(r.tx_total)),
+            r.name.clone(),
+        )
+    });
+    rows
+}
+
+pub fn net_supported_message() -> Option<String> {
+    if cfg!(target_os = "linux") {
+        None
+    } else {
+        Some("Network counters are read from /proc/net/dev (Linux-only).".to_string())
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NET_DEV: &str = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:  1000      10    0    0    0     0          0         0     1000      10    0    0    0     0       0          0
+  eth0: 50000     400    2    1    0     0          0         0    20000     150    0    3    0     0       0          0
+";
+
+    #[test]
+    fn parse_net_dev_reads_rx_and_tx_columns() {
+        let rows = parse_net_dev(NET_DEV);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].0, "eth0");
+        assert_eq!(rows[1].1.rx_bytes, 50000);
+        assert_eq!(rows[1].1.rx_errs, 2);
+        assert_eq!(rows[1].1.tx_packets, 150);
+        assert_eq!(rows[1].1.tx_drop, 3);
+    }
+
+    #[test]
+    fn net_rows_from_counters_derives_rates_from_deltas() {
+        let prev: HashMap<String, NetCounters> = parse_net_dev(NET_DEV).into_iter().collect();
+        let mut current = parse_net_dev(NET_DEV);
+        current[1].1.rx_bytes += 4000;
+        current[1].1.tx_packets += 20;
+        current[1].1.rx_drop += 2;
+
+        let rows = net_rows_from_counters(&current, &prev, 2.0);
+
+        assert_eq!(rows[1].rx_bps, 2000);
+        assert_eq!(rows[1].tx_pps, 10);
+        assert_eq!(rows[1].drops_delta, 2);
+        assert_eq!(rows[1].drops, 6);
+        assert_eq!(rows[0].rx_bps, 0);
+    }
+}
@@ -0,0 +1,251 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::alerts::{AlertEvent, AlertEventKind};
+use crate::config::{config, NotifyConfig};
+use crate::types::{NotifyState, NotifyStateInner};
+use crate::update::VERSION;
+
+/// Bump when a field is removed or changes meaning; adding fields is compatible.
+pub const ALERT_PAYLOAD_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct AlertPayload<'a> {
+    schema: &'static str,
+    schema_version: u32,
+    ferro_version: &'static str,
+    hostname: &'a str,
+    #[serde(flatten)]
+    event: &'a AlertEvent,
+}
+
+pub fn alert_payload_json(hostname: &str, event: &AlertEvent) -> String {
+    serde_json::to_string(&AlertPayload {
+        schema: "ferromon.alert",
+        schema_version: ALERT_PAYLOAD_VERSION,
+        ferro_version: VERSION,
+        hostname,
+        event,
+    })
+    .unwrap_or_default()
+}
+
+// ── Sinks ─────────────────────────────────────────────────────────────────────
+
+/// Runs `argv` with the payload on stdin; killed if it outlives `timeout`.
+pub fn run_exec_hook(argv: &[String], payload: &str, timeout: Duration) -> Result<(), String> {
+    let Some((program, args)) = argv.split_first() else {
+        return Ok(());
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("exec {program}: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin closes the pipe early; that's not a failure.
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("exec {program}: exited with {status}")),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "exec {program}: timed out after {}s",
+                    timeout.as_secs()
+                ));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("exec {program}: {e}")),
+        }
+    }
+}
+
+pub fn post_webhook(url: &str, payload: &str, timeout: Duration) -> Result<(), String> {
+    ureq::post(url)
+        .set("User-Agent", "ferromon")
+        .set("Content-Type", "application/json")
+        .timeout(timeout)
+        .send_string(payload)
+        .map(|_| ())
+        .map_err(|e| format!("webhook: {e}"))
+}
+
+#[cfg(unix)]
+pub fn write_syslog(event: &AlertEvent) -> Result<(), String> {
+    use std::os::unix::net::UnixDatagram;
+
+    // facility user (1); warning (4) when firing, notice (5) when resolved.
+    let pri = match event.kind {
+        AlertEventKind::Fired => 8 + 4,
+        AlertEventKind::Resolved => 8 + 5,
+    };
+    let kind = match event.kind {
+        AlertEventKind::Fired => "FIRED",
+        AlertEventKind::Resolved => "RESOLVED",
+    };
+    let msg = format!(
+        "<{pri}>ferro[{}]: alert {kind} {}: {} (value {})",
+        std::process::id(),
+        event.name,
+        event.rule,
+        event.value
+    );
+    let sock = UnixDatagram::unbound().map_err(|e| format!("syslog: {e}"))?;
+    for path in ["/dev/log", "/var/run/syslog"] {
+        if sock.send_to(msg.as_bytes(), path).is_ok() {
+            return Ok(());
+        }
+    }
+    Err("syslog: no socket at /dev/log or /var/run/syslog".to_string())
+}
+
+#[cfg(not(unix))]
+pub fn write_syslog(_event: &AlertEvent) -> Result<(), String> {
+    Err("syslog: unsupported on this OS".to_string())
+}
+
+// ── Dispatch ──────────────────────────────────────────────────────────────────
+
+fn deliver(cfg: &NotifyConfig, hostname: &str, event: &AlertEvent) -> Vec<String> {
+    let timeout = Duration::from_secs(cfg.timeout_secs);
+    let payload = alert_payload_json(hostname, event);
+    let mut errors = Vec::new();
+    if !cfg.exec.is_empty() {
+        errors.extend(run_exec_hook(&cfg.exec, &payload, timeout).err());
+    }
+    if let Some(url) = &cfg.webhook {
+        errors.extend(post_webhook(url, &payload, timeout).err());
+    }
+    if cfg.syslog {
+        errors.extend(write_syslog(event).err());
+    }
+    errors
+}
+
+/// One worker delivers every event in order, so a resolve can't reach a
+/// sink before the fire it follows.
+fn spawn_notify_worker(
+    hostname: String,
+    inner: Arc<Mutex<NotifyStateInner>>,
+) -> mpsc::Sender<AlertEvent> {
+    let (tx, rx) = mpsc::channel::<AlertEvent>();
+    std::thread::spawn(move || {
+        for event in rx {
+            let errors = deliver(&config().notify, &hostname, &event);
+            let mut st = inner.lock().unwrap();
+            st.sent += 1;
+            if let Some(last) = errors.last() {
+                st.failures += 1;
+                st.last_error = Some(last.clone());
+            }
+        }
+    });
+    tx
+}
+
+/// Queues fire/resolve events for every configured sink on a background
+/// thread, so a slow webhook never stalls the UI.
+pub fn dispatch_alert_events(hostname: &str, events: Vec<AlertEvent>, state: &mut NotifyState) {
+    if events.is_empty() || !config().notify.enabled() {
+        return;
+    }
+    let queue = state
+        .queue
+        .get_or_insert_with(|| spawn_notify_worker(hostname.to_string(), state.inner.clone()));
+    for event in events {
+        let _ = queue.send(event);
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+
+    fn event() -> AlertEvent {
+        AlertEvent {
+            name: "cpu-hot".to_string(),
+            rule: "cpu > 90 for 60s".to_string(),
+            kind: AlertEventKind::Fired,
+            at_unix_ms: 1_000,
+            value: "97.5".to_string(),
+        }
+    }
+
+    #[test]
+    fn webhook_posts_alert_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = v.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).unwrap();
+            (&stream)
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            (request_line, String::from_utf8(body).unwrap())
+        });
+
+        let payload = alert_payload_json("bastion-1", &event());
+        post_webhook(
+            &format!("http://{addr}/hook"),
+            &payload,
+            Duration::from_secs(5),
+        )
+        .unwrap();
+
+        let (request_line, body) = server.join().unwrap();
+        assert!(request_line.starts_with("POST /hook "));
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["schema"], "ferromon.alert");
+        assert_eq!(json["hostname"], "bastion-1");
+        assert_eq!(json["name"], "cpu-hot");
+        assert_eq!(json["kind"], "fired");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_hook_receives_payload_on_stdin() {
+        let out = std::env::temp_dir().join(format!("ferro-notify-{}.json", std::process::id()));
+        let argv = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("cat > '{}'", out.display()),
+        ];
+
+        run_exec_hook(&argv, "{\"ok\":true}", Duration::from_secs(5)).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "{\"ok\":true}");
+        let _ = std::fs::remove_file(&out);
+        assert!(run_exec_hook(&["false".to_string()], "", Duration::from_secs(5)).is_err());
+    }
+}
@@ -0,0 +1,583 @@
+use std::collections::{HashMap, VecDeque};
+use std::process::Command;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use sysinfo::{Signal, System};
+
+use crate::procview::confirmed_process;
+use crate::types::{AppState, ProcRow};
+
+/// Signals offered by the action menu, with what they usually mean.
+pub const MENU_SIGNALS: [(&str, &str); 9] = [
+    ("HUP", "hang up / reload config"),
+    ("INT", "interrupt (like Ctrl-C)"),
+    ("QUIT", "quit and dump core"),
+    ("USR1", "user-defined 1"),
+    ("USR2", "user-defined 2"),
+    ("STOP", "pause (cannot be caught)"),
+    ("CONT", "resume a paused process"),
+    ("TERM", "ask to terminate"),
+    ("KILL", "kill (cannot be caught)"),
+];
+/// Menu rows after the signals.
+pub const MENU_NICE: usize = MENU_SIGNALS.len();
+pub const MENU_IONICE: usize = MENU_NICE + 1;
+const MENU_LEN: usize = MENU_IONICE + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoClass {
+    Realtime,
+    BestEffort,
+    Idle,
+}
+
+impl IoClass {
+    pub fn label(self) -> &'static str {
+        match self {
+            IoClass::Realtime => "realtime",
+            IoClass::BestEffort => "best-effort",
+            IoClass::Idle => "idle",
+        }
+    }
+
+    /// `ionice -c` class number.
+    fn number(self) -> u8 {
+        match self {
+            IoClass::Realtime => 1,
+            IoClass::BestEffort => 2,
+            IoClass::Idle => 3,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            IoClass::BestEffort => IoClass::Idle,
+            IoClass::Idle => IoClass::Realtime,
+            IoClass::Realtime => IoClass::BestEffort,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcAction {
+    Signal(&'static str),
+    Renice(i32),
+    /// Class and level (0 = highest, 7 = lowest; unused for idle).
+    Ionice(IoClass, u8),
+}
+
+impl ProcAction {
+    pub fn describe(&self) -> String {
+        match self {
+            ProcAction::Signal(sig) => format!("send SIG{sig}"),
+            ProcAction::Renice(nice) => format!("set nice to {nice}"),
+            ProcAction::Ionice(IoClass::Idle, _) => "set I/O class to idle".to_string(),
+            ProcAction::Ionice(class, level) => {
+                format!("set I/O priority to {} {level}", class.label())
+            }
+        }
+    }
+}
+
+/// State of the `s` action menu on the processes screen.
+#[derive(Debug, Clone)]
+pub struct ProcActionMenu {
+    pub pid: i32,
+    pub name: String,
+    pub cursor: usize,
+    pub nice: i32,
+    pub io_class: IoClass,
+    pub io_level: u8,
+    /// Waiting for y/n on the action under the cursor.
+    pub confirm: bool,
+}
+
+impl ProcActionMenu {
+    pub fn action(&self) -> ProcAction {
+        match self.cursor {
+            MENU_NICE => ProcAction::Renice(self.nice),
+            MENU_IONICE => ProcAction::Ionice(self.io_class, self.io_level),
+            i => ProcAction::Signal(MENU_SIGNALS[i.min(MENU_SIGNALS.len() - 1)].0),
+        }
+    }
+}
+
+// ── Current priorities ────────────────────────────────────────────────────────
+
+/// Nice value from `/proc/<pid>/stat` (field 19). The command name may
+/// contain spaces or parens, so count fields from the last ')'.
+pub fn parse_stat_nice(stat: &str) -> Option<i32> {
+    stat_field(stat, 16)
+}
+
+/// Process group and session IDs (`/proc/<pid>/stat` fields 5 and 6).
+pub fn parse_stat_pgid_sid(stat: &str) -> Option<(i32, i32)> {
+    Some((stat_field(stat, 2)?, stat_field(stat, 3)?))
+}
+
+/// `idx` counts from the state field, the first one after the command name.
+fn stat_field(stat: &str, idx: usize) -> Option<i32> {
+    let rest = &stat[stat.rfind(')')? + 1..];
+    rest.split_whitespace().nth(idx)?.parse().ok()
+}
+
+/// Output of `ionice -p <pid>`, e.g. "best-effort: prio 4" or "idle".
+/// Class "none" means best-effort at a level derived from nice.
+pub fn parse_ionice(out: &str) -> Option<(IoClass, u8)> {
+    let out = out.trim();
+    let (class, rest) = out.split_once(':').unwrap_or((out, ""));
+    let class = match class {
+        "realtime" => IoClass::Realtime,
+        "best-effort" | "none" => IoClass::BestEffort,
+        "idle" => IoClass::Idle,
+        _ => return None,
+    };
+    let level = rest
+        .trim()
+        .strip_prefix("prio ")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(4);
+    Some((class, level.min(7)))
+}
+
+fn current_nice(pid: i32) -> i32 {
+    std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .ok()
+        .and_then(|s| parse_stat_nice(&s))
+        .unwrap_or(0)
+}
+
+fn current_ionice(pid: i32) -> (IoClass, u8) {
+    Command::new("ionice")
+        .args(["-p", &pid.to_string()])
+        .output()
+        .ok()
+        .and_then(|o| parse_ionice(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or((IoClass::BestEffort, 4))
+}
+
+// ── Running actions ───────────────────────────────────────────────────────────
+
+/// Turns a failed tool's stderr into something actionable; permission
+/// problems are the usual case and get spelled out.
+pub fn action_error(what: &str, stderr: &str) -> String {
+    let lower = stderr.to_ascii_lowercase();
+    if lower.contains("not permitted") || lower.contains("permission denied") {
+        format!("permission denied: cannot {what} (needs root, or the owner for signals)")
+    } else if lower.contains("no such process") {
+        format!("cannot {what}: the process has exited")
+    } else {
+        let detail = stderr.lines().last().unwrap_or("").trim();
+        format!("cannot {what}: {detail}")
+    }
+}
+
+/// Runs the action through kill(1), renice(1) or ionice(1) so failures come
+/// back with the OS reason instead of a bare "didn't work".
+pub fn run_proc_action(pid: i32, action: &ProcAction) -> Result<(), String> {
+    let pid_s = pid.to_string();
+    let mut cmd = match action {
+        ProcAction::Signal(sig) => {
+            let mut c = Command::new("kill");
+            c.args(["-s", sig, &pid_s]);
+            c
+        }
+        ProcAction::Renice(nice) => {
+            let mut c = Command::new("renice");
+            c.args(["-n", &nice.to_string(), "-p", &pid_s]);
+            c
+        }
+        ProcAction::Ionice(class, level) => {
+            let mut c = Command::new("ionice");
+            c.args(["-c", &class.number().to_string()]);
+            if *class != IoClass::Idle {
+                c.args(["-n", &level.to_string()]);
+            }
+            c.args(["-p", &pid_s]);
+            c
+        }
+    };
+    let what = action.describe();
+    let out = cmd.output().map_err(|e| format!("cannot {what}: {e}"))?;
+    if out.status.success() {
+        Ok(())
+    } else {
+        Err(action_error(&what, &String::from_utf8_lossy(&out.stderr)))
+    }
+}
+
+// ── Kill scopes ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillScope {
+    Process,
+    /// The process and every descendant, by parent PID.
+    Subtree,
+    Group,
+    Session,
+}
+
+impl KillScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            KillScope::Process => "process",
+            KillScope::Subtree => "subtree",
+            KillScope::Group => "process group",
+            KillScope::Session => "session",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            KillScope::Process => KillScope::Subtree,
+            KillScope::Subtree => KillScope::Group,
+            KillScope::Group => KillScope::Session,
+            KillScope::Session => KillScope::Process,
+        }
+    }
+}
+
+/// State of the `k` confirmation. `targets` is exactly what y/K will signal.
+#[derive(Debug, Clone)]
+pub struct KillConfirm {
+    pub pid: i32,
+    pub name: String,
+    pub scope: KillScope,
+    pub targets: Vec<(i32, String)>,
+    /// Why this scope can't be signalled; y/K are disabled while set.
+    pub refused: Option<String>,
+}
+
+/// PIDs covered by `scope`, the selected process first and the rest in
+/// breadth-first / PID order so a supervisor is signalled before its
+/// children and can't respawn them. `ids` maps PID to (pgid, sid) and is
+/// only consulted for the group and session scopes. Threads are skipped:
+/// signalling the process covers them.
+pub fn scope_targets(
+    procs: &[ProcRow],
+    ids: &HashMap<i32, (i32, i32)>,
+    pid: i32,
+    scope: KillScope,
+) -> Vec<(i32, String)> {
+    let procs: Vec<&ProcRow> = procs.iter().filter(|p| !p.is_thread).collect();
+    let Some(selected) = procs.iter().find(|p| p.pid == pid) else {
+        return Vec::new();
+    };
+    let mut out = vec![(selected.pid, selected.name.clone())];
+    match scope {
+        KillScope::Process => {}
+        KillScope::Subtree => {
+            let mut children: HashMap<i32, Vec<&ProcRow>> = HashMap::new();
+            for p in &procs {
+                if let Some(ppid) = p.ppid {
+                    children.entry(ppid).or_default().push(p);
+                }
+            }
+            let mut queue = VecDeque::from([pid]);
+            while let Some(parent) = queue.pop_front() {
+                let mut kids = children.remove(&parent).unwrap_or_default();
+                kids.sort_by_key(|p| p.pid);
+                for p in kids {
+                    out.push((p.pid, p.name.clone()));
+                    queue.push_back(p.pid);
+                }
+            }
+        }
+        KillScope::Group | KillScope::Session => {
+            let key = |p: i32| {
+                ids.get(&p).map(|&(pgid, sid)| match scope {
+                    KillScope::Group => pgid,
+                    _ => sid,
+                })
+            };
+            let Some(want) = key(pid) else {
+                return out;
+            };
+            let mut members: Vec<&&ProcRow> = procs
+                .iter()
+                .filter(|p| p.pid != pid && key(p.pid) == Some(want))
+                .collect();
+            members.sort_by_key(|p| p.pid);
+            out.extend(members.into_iter().map(|p| (p.pid, p.name.clone())));
+        }
+    }
+    out
+}
+
+#[cfg(target_os = "linux")]
+fn read_pgid_sid(procs: &[ProcRow]) -> HashMap<i32, (i32, i32)> {
+    procs
+        .iter()
+        .filter(|p| !p.is_thread)
+        .filter_map(|p| {
+            let stat = std::fs::read_to_string(format!("/proc/{}/stat", p.pid)).ok()?;
+            Some((p.pid, parse_stat_pgid_sid(&stat)?))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_pgid_sid(_procs: &[ProcRow]) -> HashMap<i32, (i32, i32)> {
+    HashMap::new()
+}
+
+fn kill_refusal(targets: &[(i32, String)], scope: KillScope) -> Option<String> {
+    let own = std::process::id() as i32;
+    if scope == KillScope::Process {
+        None
+    } else if targets.iter().any(|(p, _)| *p == 1) {
+        Some(format!("the {} includes PID 1 (init)", scope.label()))
+    } else if targets.iter().any(|(p, _)| *p == own) {
+        Some(format!("the {} includes ferro itself", scope.label()))
+    } else {
+        None
+    }
+}
+
+fn set_kill_scope(app: &mut AppState, scope: KillScope) {
+    let Some(confirm) = app.proc_kill_confirm.as_ref() else {
+        return;
+    };
+    let ids = match scope {
+        KillScope::Group | KillScope::Session => read_pgid_sid(&app.proc_rows),
+        _ => HashMap::new(),
+    };
+    let mut targets = scope_targets(&app.proc_rows, &ids, confirm.pid, scope);
+    if targets.is_empty() {
+        targets.push((confirm.pid, confirm.name.clone()));
+    }
+    let refused = kill_refusal(&targets, scope);
+    if let Some(confirm) = app.proc_kill_confirm.as_mut() {
+        confirm.scope = scope;
+        confirm.targets = targets;
+        confirm.refused = refused;
+    }
+}
+
+pub fn open_kill_confirm(app: &mut AppState, pid: i32, name: String) {
+    app.proc_kill_confirm = Some(KillConfirm {
+        pid,
+        name: name.clone(),
+        scope: KillScope::Process,
+        targets: vec![(pid, name)],
+        refused: None,
+    });
+}
+
+/// Signals every target that still carries the listed name, so a PID reused
+/// since the list was built is left alone.
+fn kill_targets(system: &System, confirm: &KillConfirm, signal: Signal) -> String {
+    let sig = match signal {
+        Signal::Kill => "SIGKILL",
+        _ => "SIGTERM",
+    };
+    let (mut sent, mut gone, mut failed) = (0, 0, 0);
+    let mut last_err = None;
+    for (pid, name) in &confirm.targets {
+        match confirmed_process(system, *pid, name) {
+            Ok(p) => match p.kill_with(signal) {
+                Some(true) => sent += 1,
+                _ => failed += 1,
+            },
+            Err(e) => {
+                gone += 1;
+                last_err = Some(e);
+            }
+        }
+    }
+    if confirm.targets.len() == 1 {
+        return match (sent, last_err) {
+            (1, _) => format!("sent {sig} to {} ({})", confirm.name, confirm.pid),
+            (_, Some(e)) => e,
+            _ => format!(
+                "permission denied: cannot signal {} ({})",
+                confirm.name, confirm.pid
+            ),
+        };
+    }
+    let mut msg = format!("sent {sig} to {sent} of {}", confirm.targets.len());
+    if gone > 0 {
+        msg.push_str(&format!(" · {gone} gone or reused"));
+    }
+    if failed > 0 {
+        msg.push_str(&format!(" · {failed} permission denied"));
+    }
+    msg
+}
+
+/// Keys while the kill confirmation is open; it intercepts everything.
+pub fn handle_kill_confirm_key(app: &mut AppState, system: &System, key: &KeyEvent) -> bool {
+    let Some(confirm) = app.proc_kill_confirm.clone() else {
+        return false;
+    };
+    let signal = match key.code {
+        KeyCode::Char('y') => Some(Signal::Term),
+        KeyCode::Char('K') => Some(Signal::Kill),
+        KeyCode::Tab | KeyCode::Char('g') => {
+            set_kill_scope(app, confirm.scope.next());
+            None
+        }
+        KeyCode::Char('n') | KeyCode::Esc => {
+            app.proc_kill_confirm = None;
+            None
+        }
+        _ => None,
+    };
+    if let Some(signal) = signal {
+        if confirm.refused.is_none() {
+            let msg = kill_targets(system, &confirm, signal);
+            app.proc_message = Some((msg, Instant::now()));
+            app.proc_kill_confirm = None;
+        }
+    }
+    true
+}
+
+// ── App glue ──────────────────────────────────────────────────────────────────
+
+pub fn open_proc_action_menu(app: &mut AppState, pid: i32, name: String) {
+    let (io_class, io_level) = current_ionice(pid);
+    app.proc_action = Some(ProcActionMenu {
+        pid,
+        name,
+        cursor: 0,
+        nice: current_nice(pid),
+        io_class,
+        io_level,
+        confirm: false,
+    });
+}
+
+/// Keys while the action menu is open; it intercepts everything.
+pub fn handle_proc_action_key(app: &mut AppState, system: &System, key: &KeyEvent) -> bool {
+    let Some(menu) = app.proc_action.as_mut() else {
+        return false;
+    };
+    if menu.confirm {
+        match key.code {
+            KeyCode::Char('y') => {
+                let (pid, name, action) = (menu.pid, menu.name.clone(), menu.action());
+                app.proc_action = None;
+                let result = confirmed_process(system, pid, &name)
+                    .and_then(|_| run_proc_action(pid, &action));
+                let msg = match result {
+                    Ok(()) => format!("{}: {name} ({pid})", action.describe()),
+                    Err(e) => e,
+                };
+                app.proc_message = Some((msg, Instant::now()));
+            }
+            KeyCode::Char('n') | KeyCode::Esc => menu.confirm = false,
+            _ => {}
+        }
+        return true;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.proc_action = None,
+        KeyCode::Up => menu.cursor = menu.cursor.saturating_sub(1),
+        KeyCode::Down => menu.cursor = (menu.cursor + 1).min(MENU_LEN - 1),
+        KeyCode::Left | KeyCode::Right => {
+            let step: i32 = if key.code == KeyCode::Right { 1 } else { -1 };
+            match menu.cursor {
+                MENU_NICE => menu.nice = (menu.nice + step).clamp(-20, 19),
+                MENU_IONICE => menu.io_level = (menu.io_level as i32 + step).clamp(0, 7) as u8,
+                _ => {}
+            }
+        }
+        KeyCode::Char('c') if menu.cursor == MENU_IONICE => menu.io_class = menu.io_class.next(),
+        KeyCode::Enter => menu.confirm = true,
+        _ => {}
+    }
+    true
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_current_priorities_and_permission_errors() {
+        let stat = "4242 (my (odd) job) S 1 4242 4242 0 -1 4194560 100 0 0 0 \
+            5 3 0 0 20 5 1 0 123456 1000000 200";
+        assert_eq!(parse_stat_nice(stat), Some(5));
+
+        assert_eq!(
+            parse_ionice("best-effort: prio 6\n"),
+            Some((IoClass::BestEffort, 6))
+        );
+        assert_eq!(parse_ionice("none: prio 0"), Some((IoClass::BestEffort, 0)));
+        assert_eq!(parse_ionice("idle"), Some((IoClass::Idle, 4)));
+
+        let err = action_error(
+            "set nice to -5",
+            "renice: failed to set priority for 1 (process ID): Permission denied\n",
+        );
+        assert!(err.starts_with("permission denied"), "{err}");
+        let err = action_error("send SIGSTOP", "kill: (1) - Operation not permitted\n");
+        assert!(err.starts_with("permission denied"), "{err}");
+    }
+
+    fn proc(pid: i32, ppid: i32) -> ProcRow {
+        ProcRow {
+            pid,
+            ppid: Some(ppid),
+            name: format!("p{pid}"),
+            cpu_x10: 0,
+            mem_bytes: 0,
+            swap_bytes: 0,
+            status: "Sleep",
+            is_thread: false,
+            read_bps: 0,
+            write_bps: 0,
+        }
+    }
+
+    #[test]
+    fn kill_scopes_cover_subtree_group_and_session() {
+        // 100 runner → 110 job → {111, 112}; 120 unrelated sibling; 113 a thread.
+        let procs = vec![
+            proc(100, 1),
+            proc(110, 100),
+            proc(112, 110),
+            proc(111, 110),
+            proc(120, 100),
+            ProcRow {
+                is_thread: true,
+                ..proc(113, 110)
+            },
+        ];
+        let pids = |t: Vec<(i32, String)>| t.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
+        let none = HashMap::new();
+
+        assert_eq!(
+            pids(scope_targets(&procs, &none, 110, KillScope::Process)),
+            [110]
+        );
+        assert_eq!(
+            pids(scope_targets(&procs, &none, 110, KillScope::Subtree)),
+            [110, 111, 112]
+        );
+
+        let ids = HashMap::from([
+            (100, (100, 100)),
+            (110, (110, 100)),
+            (111, (110, 100)),
+            (112, (110, 100)),
+            (120, (120, 100)),
+        ]);
+        assert_eq!(
+            pids(scope_targets(&procs, &ids, 111, KillScope::Group)),
+            [111, 110, 112]
+        );
+        assert_eq!(
+            pids(scope_targets(&procs, &ids, 110, KillScope::Session)),
+            [110, 100, 111, 112, 120]
+        );
+        assert_eq!(
+            parse_stat_pgid_sid("42 (a b) S 1 40 30 0 -1"),
+            Some((40, 30))
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use sysinfo::{Groups, Pid, System, Users};
+
+use crate::procaction::{open_kill_confirm, open_proc_action_menu};
+use crate::types::{AppState, Screen};
+
+const DETAIL_REFRESH: Duration = Duration::from_secs(2);
+
+// ── Detail model ──────────────────────────────────────────────────────────────
+
+/// Everything the detail view shows for one PID. Linux-only sections carry
+/// `Err` with the reason (permission denied, unsupported OS) instead of
+/// hiding the section, so an empty list never looks like "nothing there".
+#[derive(Debug, Clone)]
+pub struct ProcDetail {
+    pub pid: i32,
+    pub ppid: Option<i32>,
+    pub name: String,
+    pub status: &'static str,
+    pub cmdline: Vec<String>,
+    pub exe: Option<PathBuf>,
+    pub cwd: Option<PathBuf>,
+    pub user: String,
+    pub group: String,
+    pub start_time_unix: u64,
+    pub run_time_secs: u64,
+    pub threads: Option<u64>,
+    pub oom_score: Option<i32>,
+    pub oom_score_adj: Option<i32>,
+    pub cgroup: Result<Vec<String>, String>,
+    pub memory: Result<MemRollup, String>,
+    pub namespaces: Result<Vec<(String, String)>, String>,
+    pub limits: Result<Vec<Rlimit>, String>,
+    pub fds: Result<Vec<(u32, String)>, String>,
+    pub environ: Result<Vec<String>, String>,
+}
+
+/// Totals from `/proc/<pid>/smaps_rollup`, in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemRollup {
+    pub rss: u64,
+    pub pss: u64,
+    /// Private clean + private dirty: what freeing this process would return.
+    pub uss: u64,
+    pub swap: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rlimit {
+    pub name: String,
+    pub soft: String,
+    pub hard: String,
+    pub units: String,
+}
+
+// ── /proc parsing ─────────────────────────────────────────────────────────────
+
+pub fn parse_smaps_rollup(content: &str) -> MemRollup {
+    let mut m = MemRollup::default();
+    for line in content.lines() {
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let kb: u64 = rest
+            .split_whitespace()
+            .next()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        match key {
+            "Rss" => m.rss = kb * 1024,
+            "Pss" => m.pss = kb * 1024,
+            "Private_Clean" | "Private_Dirty" => m.uss += kb * 1024,
+            "Swap" => m.swap = kb * 1024,
+            _ => {}
+        }
+    }
+    m
+}
+
+/// `/proc/<pid>/limits` is a fixed-width table; the limit name itself contains
+/// spaces, so split on the column offsets from the header line.
+pub fn parse_limits(content: &str) -> Vec<Rlimit> {
+    let mut lines = content.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let (Some(soft_at), Some(hard_at), Some(units_at)) = (
+        header.find("Soft Limit"),
+        header.find("Hard Limit"),
+        header.find("Units"),
+    ) else {
+        return Vec::new();
+    };
+    let col = |line: &str, from: usize, to: usize| -> String {
+        line.get(from.min(line.len())..to.min(line.len()))
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+    lines
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| Rlimit {
+            name: col(l, 0, soft_at),
+            soft: col(l, soft_at, hard_at),
+            hard: col(l, hard_at, units_at),
+            units: col(l, units_at, l.len()),
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn status_field(status: &str, key: &str) -> Option<String> {
+    status
+        .lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+        .map(|v| v.trim().to_string())
+}
+
+// ── Collection ────────────────────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+fn read_proc_file(pid: i32, file: &str) -> Result<String, String> {
+    std::fs::read_to_string(format!("/proc/{pid}/{file}")).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => "permission denied (run as root)".to_string(),
+        _ => e.to_string(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_link_dir(pid: i32, dir: &str) -> Result<Vec<(String, String)>, String> {
+    let entries = std::fs::read_dir(format!("/proc/{pid}/{dir}")).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => "permission denied (run as root)".to_string(),
+        _ => e.to_string(),
+    })?;
+    Ok(entries
+        .flatten()
+        .map(|e| {
+            let target = std::fs::read_link(e.path())
+                .map(|t| t.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| "?".to_string());
+            (e.file_name().to_string_lossy().into_owned(), target)
+        })
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+fn fill_linux_details(d: &mut ProcDetail) {
+    let pid = d.pid;
+    if let Ok(status) = read_proc_file(pid, "status") {
+        d.threads = status_field(&status, "Threads").and_then(|v| v.parse().ok());
+    }
+    d.oom_score = read_proc_file(pid, "oom_score")
+        .ok()
+        .and_then(|v| v.trim().parse().ok());
+    d.oom_score_adj = read_proc_file(pid, "oom_score_adj")
+        .ok()
+        .and_then(|v| v.trim().parse().ok());
+    d.environ = read_proc_file(pid, "environ").map(|c| {
+        c.split('\0')
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+    d.cgroup = read_proc_file(pid, "cgroup").map(|c| c.lines().map(str::to_string).collect());
+    d.memory = read_proc_file(pid, "smaps_rollup").map(|c| parse_smaps_rollup(&c));
+    d.limits = read_proc_file(pid, "limits").map(|c| parse_limits(&c));
+    d.namespaces = read_link_dir(pid, "ns").map(|mut ns| {
+        ns.sort();
+        ns
+    });
+    d.fds = read_link_dir(pid, "fd").map(|fds| {
+        let mut fds: Vec<(u32, String)> = fds
+            .into_iter()
+            .filter_map(|(fd, target)| Some((fd.parse().ok()?, target)))
+            .collect();
+        fds.sort();
+        fds
+    });
+}
+
+#[cfg(not(target_os = "linux"))]
+fn fill_linux_details(_d: &mut ProcDetail) {}
+
+fn not_available<T>() -> Result<T, String> {
+    Err("not available on this OS".to_string())
+}
+
+/// Reads the parts of the detail view that sysinfo already holds for `pid`;
+/// errors only if the process is gone. The `/proc` sections are filled in
+/// afterwards by `fill_linux_details`, off the UI thread.
+fn read_proc_detail(
+    system: &System,
+    pid: i32,
+    users: &Users,
+    groups: &Groups,
+) -> Result<ProcDetail, String> {
+    let p = system
+        .process(Pid::from_u32(pid as u32))
+        .ok_or_else(|| format!("process {pid} has exited"))?;
+    let row = crate::types::ProcRow::from_process(Pid::from_u32(pid as u32), p);
+
+    let user = match p.user_id() {
+        Some(uid) => match users.get_user_by_id(uid) {
+            Some(u) => format!("{} (uid {})", u.name(), **uid),
+            None => format!("uid {}", **uid),
+        },
+        None => "?".to_string(),
+    };
+    let group = match p.group_id() {
+        Some(gid) => match groups.iter().find(|g| *g.id() == gid) {
+            Some(g) => format!("{} (gid {})", g.name(), *gid),
+            None => format!("gid {}", *gid),
+        },
+        None => "?".to_string(),
+    };
+    Ok(ProcDetail {
+        pid,
+        ppid: row.ppid,
+        name: row.name,
+        status: row.status,
+        cmdline: p.cmd().to_vec(),
+        exe: p.exe().map(|e| e.to_path_buf()),
+        cwd: p.cwd().map(|c| c.to_path_buf()),
+        user,
+        group,
+        start_time_unix: p.start_time(),
+        run_time_secs: p.run_time(),
+        threads: p.tasks().map(|t| t.len() as u64),
+        oom_score: None,
+        oom_score_adj: None,
+        cgroup: not_available(),
+        memory: not_available(),
+        namespaces: not_available(),
+        limits: not_available(),
+        fds: not_available(),
+        environ: Ok(p.environ().to_vec()),
+    })
+}
+
+// ── App glue ──────────────────────────────────────────────────────────────────
+
+/// Takes the sysinfo snapshot here and reads `/proc` on a background thread,
+/// since a process with thousands of fds means thousands of `readlink`s.
+fn load_proc_detail(app: &mut AppState, system: &System, pid: i32) {
+    {
+        let mut state = app.proc_detail_state.inner.lock().unwrap();
+        if state.running && state.pid == Some(pid) {
+            return;
+        }
+        state.pid = Some(pid);
+    }
+    let (users, groups) = app.proc_detail_accounts.get_or_insert_with(|| {
+        (
+            Users::new_with_refreshed_list(),
+            Groups::new_with_refreshed_list(),
+        )
+    });
+    let mut d = match read_proc_detail(system, pid, users, groups) {
+        Ok(d) => d,
+        Err(e) => {
+            let mut state = app.proc_detail_state.inner.lock().unwrap();
+            state.running = false;
+            state.detail = Some(Err(e));
+            return;
+        }
+    };
+    app.proc_detail_state.inner.lock().unwrap().running = true;
+
+    let inner = app.proc_detail_state.inner.clone();
+    std::thread::spawn(move || {
+        fill_linux_details(&mut d);
+        let mut state = inner.lock().unwrap();
+        if state.pid != Some(pid) {
+            // Closed, or another process opened, while this was loading.
+            return;
+        }
+        state.running = false;
+        state.detail = Some(Ok(d));
+    });
+}
+
+pub fn open_proc_detail(app: &mut AppState, system: &System, pid: i32) {
+    app.proc_detail_state.inner.lock().unwrap().detail = None;
+    app.proc_detail_accounts = None;
+    app.proc_detail_pid = Some(pid);
+    app.proc_detail_scroll = 0;
+    refresh_proc_detail(app, system, true);
+}
+
+pub fn refresh_proc_detail(app: &mut AppState, system: &System, force: bool) {
+    let Some(pid) = app.proc_detail_pid else {
+        return;
+    };
+    let due = match app.proc_detail_at {
+        Some(t) => force || t.elapsed() >= DETAIL_REFRESH,
+        None => true,
+    };
+    if due {
+        load_proc_detail(app, system, pid);
+        app.proc_detail_at = Some(Instant::now());
+    }
+}
+
+fn close_proc_detail(app: &mut AppState) {
+    app.proc_detail_pid = None;
+    app.proc_detail_at = None;
+    app.proc_detail_accounts = None;
+    let mut state = app.proc_detail_state.inner.lock().unwrap();
+    state.running = false;
+    state.pid = None;
+    state.detail = None;
+}
+
+/// The name for the kill/action prompts, once the detail has loaded.
+fn detail_name(app: &AppState, pid: i32) -> String {
+    match &app.proc_detail_state.inner.lock().unwrap().detail {
+        Some(Ok(d)) => d.name.clone(),
+        _ => format!("PID {pid}"),
+    }
+}
+
+/// Keys while the detail view is open. List-only keys are swallowed so they
+/// don't act on an invisible cursor.
+pub fn handle_proc_detail_key(app: &mut AppState, key: &KeyEvent) -> bool {
+    if !matches!(app.screen, Screen::Processes) {
+        return false;
+    }
+    let Some(pid) = app.proc_detail_pid else {
+        return false;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Left | KeyCode::Backspace => close_proc_detail(app),
+        KeyCode::Up => app.proc_detail_scroll = app.proc_detail_scroll.saturating_sub(1),
+        KeyCode::Down => app.proc_detail_scroll = app.proc_detail_scroll.saturating_add(1),
+        KeyCode::PageUp => app.proc_detail_scroll = app.proc_detail_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.proc_detail_scroll = app.proc_detail_scroll.saturating_add(10),
+        KeyCode::Home => app.proc_detail_scroll = 0,
+        KeyCode::Char('k') => {
+            let name = detail_name(app, pid);
+            open_kill_confirm(app, pid, name);
+        }
+        KeyCode::Char('s') => {
+            let name = detail_name(app, pid);
+            open_proc_action_menu(app, pid, name);
+        }
+        KeyCode::Char('/' | 't' | ' ' | 'R') | KeyCode::Tab | KeyCode::Enter => {}
+        _ => return false,
+    }
+    true
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_smaps_rollup_and_limits() {
+        let rollup = "55d0c0a00000-7ffc2d9f1000 ---p 00000000 00:00 0    [rollup]\n\
+            Rss:               10240 kB\n\
+            Pss:                6144 kB\n\
+            Shared_Clean:       4096 kB\n\
+            Private_Clean:      1024 kB\n\
+            Private_Dirty:      3072 kB\n\
+            Swap:                512 kB\n";
+        assert_eq!(
+            parse_smaps_rollup(rollup),
+            MemRollup {
+                rss: 10240 * 1024,
+                pss: 6144 * 1024,
+                uss: 4096 * 1024,
+                swap: 512 * 1024,
+            }
+        );
+
+        let limits =
+            "Limit                     Soft Limit           Hard Limit           Units     \n\
+            Max cpu time              unlimited            unlimited            seconds   \n\
+            Max open files            1024                 524288               files     \n";
+        let parsed = parse_limits(limits);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(
+            parsed[1],
+            Rlimit {
+                name: "Max open files".to_string(),
+                soft: "1024".to_string(),
+                hard: "524288".to_string(),
+                units: "files".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use sysinfo::{Pid, Signal, System};
+
+use crate::procview::confirmed_process;
+use crate::types::AppState;
+
+/// How long the old process gets to exit after SIGTERM before we give up.
+const EXIT_WAIT: Duration = Duration::from_secs(3);
+
+/// Everything needed to start a process again the way it was started.
+#[derive(Debug, Clone)]
+pub struct RestartPlan {
+    pub exe: PathBuf,
+    /// Full argv, including argv[0] as the process saw it.
+    pub argv: Vec<String>,
+    pub cwd: PathBuf,
+    pub environ: Vec<String>,
+    /// uid/gid to switch to; set when ferro is root and the process isn't.
+    pub run_as: Option<(u32, u32)>,
+    pub user: String,
+    pub log_path: PathBuf,
+}
+
+/// State of the `R` confirmation. `plan` carries the reason when a faithful
+/// restart isn't possible.
+#[derive(Debug, Clone)]
+pub struct RestartConfirm {
+    pub pid: i32,
+    pub name: String,
+    pub plan: Result<RestartPlan, String>,
+}
+
+// ── Planning ──────────────────────────────────────────────────────────────────
+
+/// The systemd service owning a process, from `/proc/<pid>/cgroup`. Only the
+/// leaf counts: a shell in a session scope under `user@1000.service` is not
+/// managed by that unit.
+pub fn systemd_unit_from_cgroup(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        let leaf = path.rsplit('/').next()?;
+        leaf.ends_with(".service").then(|| leaf.to_string())
+    })
+}
+
+/// `/proc/<pid>/exe` reads "<path> (deleted)" after a package upgrade; the
+/// new binary at the same path is what a restart should run.
+pub fn live_exe_path(exe: &Path) -> Option<PathBuf> {
+    let s = exe.to_string_lossy();
+    let path = PathBuf::from(s.strip_suffix(" (deleted)").unwrap_or(&s));
+    path.exists().then_some(path)
+}
+
+fn restart_log_dir() -> PathBuf {
+    let state = match std::env::var_os("XDG_STATE_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/state")),
+    };
+    state
+        .map(|s| s.join("ferromon").join("restarts"))
+        .unwrap_or_else(|| std::env::temp_dir().join("ferromon-restarts"))
+}
+
+#[cfg(target_os = "linux")]
+fn read_environ(pid: i32) -> Result<Vec<String>, String> {
+    let raw = std::fs::read(format!("/proc/{pid}/environ")).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            "cannot read its environment (permission denied)".to_string()
+        }
+        _ => format!("cannot read its environment: {e}"),
+    })?;
+    Ok(String::from_utf8_lossy(&raw)
+        .split('\0')
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_environ(_pid: i32) -> Result<Vec<String>, String> {
+    Err("reading another process's environment needs /proc (Linux)".to_string())
+}
+
+/// Collects what a restart needs, or explains why it would not be faithful.
+pub fn plan_restart(system: &System, pid: i32) -> Result<RestartPlan, String> {
+    let p = system
+        .process(Pid::from_u32(pid as u32))
+        .ok_or_else(|| format!("process {pid} has exited"))?;
+    let name = p.name();
+
+    if let Ok(cgroup) = std::fs::read_to_string(format!("/proc/{pid}/cgroup")) {
+        if let Some(unit) = systemd_unit_from_cgroup(&cgroup) {
+            let user = if cgroup.contains("/user@") {
+                " --user"
+            } else {
+                ""
+            };
+            return Err(format!(
+                "{name} belongs to systemd unit {unit}; systemd would see it die and \
+                 may restart it too. Restart the unit instead: systemctl{user} restart {unit}"
+            ));
+        }
+    }
+
+    let exe = p
+        .exe()
+        .ok_or("cannot read its executable (kernel thread, or permission denied)")?;
+    let exe = live_exe_path(exe)
+        .ok_or_else(|| format!("executable {} no longer exists", exe.display()))?;
+    let cwd = p
+        .cwd()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "cannot read its working directory (permission denied)".to_string())?;
+    let environ = read_environ(pid)?;
+    let mut argv = p.cmd().to_vec();
+    if argv.is_empty() {
+        argv.push(exe.to_string_lossy().into_owned());
+    }
+
+    let uid = p.user_id().map(|u| **u);
+    let gid = p.group_id().map(|g| *g);
+    let my_uid = sysinfo::get_current_pid()
+        .ok()
+        .and_then(|me| system.process(me))
+        .and_then(|me| me.user_id().map(|u| **u));
+    let run_as = match (my_uid, uid, gid) {
+        (Some(0), Some(uid), Some(gid)) if uid != 0 => Some((uid, gid)),
+        (Some(me), Some(uid), _) if me != uid && me != 0 => {
+            return Err(format!(
+                "it runs as uid {uid}; relaunching it as you would change its user (run ferro as root)"
+            ));
+        }
+        _ => None,
+    };
+
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let safe_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Ok(RestartPlan {
+        exe,
+        argv,
+        cwd,
+        environ,
+        run_as,
+        user: uid
+            .map(|u| format!("uid {u}"))
+            .unwrap_or_else(|| "?".to_string()),
+        log_path: restart_log_dir().join(format!("{safe_name}-{stamp}.log")),
+    })
+}
+
+// ── Relaunch ──────────────────────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+fn has_exited(pid: i32) -> bool {
+    match std::fs::read_to_string(format!("/proc/{pid}/stat")) {
+        // A zombie is dead; it's only waiting for its parent to reap it.
+        Ok(stat) => match stat
+            .rfind(')')
+            .and_then(|i| stat[i + 1..].split_whitespace().next())
+        {
+            Some(state) => state == "Z" || state == "X",
+            None => true,
+        },
+        Err(_) => true,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn has_exited(pid: i32) -> bool {
+    !Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Starts the child in a new session so it has no controlling terminal and
+/// survives ferro and the terminal going away.
+#[cfg(unix)]
+fn detach(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+
+    // SAFETY: setsid is async-signal-safe and touches no memory in the child.
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::setsid() < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(unix)]
+fn spawn_plan(plan: &RestartPlan) -> Result<u32, String> {
+    use std::os::unix::process::CommandExt;
+
+    if let Some(dir) = plan.log_path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    }
+    let log = std::fs::File::create(&plan.log_path)
+        .map_err(|e| format!("{}: {e}", plan.log_path.display()))?;
+    let log_err = log.try_clone().map_err(|e| e.to_string())?;
+
+    let mut cmd = Command::new(&plan.exe);
+    cmd.arg0(&plan.argv[0])
+        .args(&plan.argv[1..])
+        .current_dir(&plan.cwd)
+        .env_clear()
+        .envs(plan.environ.iter().filter_map(|kv| kv.split_once('=')))
+        .stdin(Stdio::null())
+        .stdout(log)
+        .stderr(log_err);
+    if let Some((uid, gid)) = plan.run_as {
+        cmd.uid(uid).gid(gid);
+    }
+    detach(&mut cmd);
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("relaunch {} failed: {e}", plan.exe.display()))?;
+    let new_pid = child.id();
+    // Reap it if it exits while ferro is still running.
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(new_pid)
+}
+
+#[cfg(not(unix))]
+fn spawn_plan(_plan: &RestartPlan) -> Result<u32, String> {
+    Err("restart is only supported on Unix".to_string())
+}
+
+/// Waits up to `EXIT_WAIT` for the SIGTERMed process to go, then
+/// relaunches it. A process that ignores SIGTERM is left alone rather than
+/// run twice.
+fn relaunch_after_exit(pid: i32, name: &str, plan: &RestartPlan) -> Result<u32, String> {
+    let started = Instant::now();
+    while !has_exited(pid) {
+        if started.elapsed() >= EXIT_WAIT {
+            return Err(format!(
+                "{name} ({pid}) still running {}s after SIGTERM; not relaunched",
+                EXIT_WAIT.as_secs()
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    spawn_plan(plan)
+}
+
+// ── App glue ──────────────────────────────────────────────────────────────────
+
+pub fn open_restart_confirm(app: &mut AppState, system: &System, pid: i32, name: String) {
+    app.proc_restart_confirm = Some(RestartConfirm {
+        pid,
+        name,
+        plan: plan_restart(system, pid),
+    });
+}
+
+/// SIGTERM now, then wait and relaunch on a background thread so the UI
+/// keeps drawing; the outcome lands in `proc_message`.
+fn start_restart(app: &mut AppState, system: &System, confirm: &RestartConfirm, plan: RestartPlan) {
+    let (pid, name) = (confirm.pid, confirm.name.clone());
+    if app.proc_restart_state.inner.lock().unwrap().running {
+        app.proc_message = Some((
+            "a restart is already in progress".to_string(),
+            Instant::now(),
+        ));
+        return;
+    }
+    let signalled =
+        confirmed_process(system, pid, &name).and_then(|proc| match proc.kill_with(Signal::Term) {
+            Some(true) => Ok(()),
+            _ => Err(format!("permission denied: cannot signal {name} ({pid})")),
+        });
+    if let Err(e) = signalled {
+        app.proc_message = Some((e, Instant::now()));
+        return;
+    }
+
+    app.proc_message = Some((
+        format!("restarting {name}: waiting for {pid} to exit…"),
+        Instant::now(),
+    ));
+    app.proc_restart_state.inner.lock().unwrap().running = true;
+    let inner = app.proc_restart_state.inner.clone();
+    std::thread::spawn(move || {
+        let result = relaunch_after_exit(pid, &name, &plan).map(|new_pid| {
+            let msg = format!(
+                "restarted {name}: new PID {new_pid} · output in {}",
+                plan.log_path.display()
+            );
+            (new_pid, msg)
+        });
+        let mut state = inner.lock().unwrap();
+        state.running = false;
+        state.result = Some(result);
+    });
+}
+
+/// Moves a finished restart's outcome into `proc_message`, selecting the
+/// new process.
+pub fn take_restart_result(app: &mut AppState) {
+    let Some(result) = app.proc_restart_state.inner.lock().unwrap().result.take() else {
+        return;
+    };
+    let msg = match result {
+        Ok((new_pid, msg)) => {
+            app.proc_selected_pid = Some(new_pid as i32);
+            msg
+        }
+        Err(e) => e,
+    };
+    app.proc_message = Some((msg, Instant::now()));
+}
+
+/// Keys while the restart confirmation is open; it intercepts everything.
+pub fn handle_restart_confirm_key(app: &mut AppState, system: &System, key: &KeyEvent) -> bool {
+    let Some(confirm) = app.proc_restart_confirm.clone() else {
+        return false;
+    };
+    match key.code {
+        KeyCode::Char('y') => {
+            if let Ok(plan) = confirm.plan.clone() {
+                app.proc_restart_confirm = None;
+                start_restart(app, system, &confirm, plan);
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Esc => app.proc_restart_confirm = None,
+        _ => {}
+    }
+    true
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_systemd_units_and_upgraded_binaries() {
+        assert_eq!(
+            systemd_unit_from_cgroup("0::/system.slice/nginx.service\n"),
+            Some("nginx.service".to_string())
+        );
+        assert_eq!(
+            systemd_unit_from_cgroup(
+                "12:pids:/user.slice/user-1000.slice/user@1000.service/app.slice/syncthing.service\n"
+            ),
+            Some("syncthing.service".to_string())
+        );
+        // Terminal sessions and containers are scopes, not services.
+        assert_eq!(
+            systemd_unit_from_cgroup(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/vte-spawn-1.scope\n"
+            ),
+            None
+        );
+        assert_eq!(systemd_unit_from_cgroup("0::/\n"), None);
+
+        let sh = PathBuf::from("/bin/sh (deleted)");
+        assert_eq!(live_exe_path(&sh), Some(PathBuf::from("/bin/sh")));
+        assert_eq!(live_exe_path(Path::new("/nonexistent/ferro-test")), None);
+    }
+}
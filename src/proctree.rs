@@ -0,0 +1,328 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::procview::{sync_proc_selection, ProcView, ProcViewRows};
+use crate::types::{AppState, ProcRow, ProcSort, Screen};
+
+/// One visible line of the process tree, in display order.
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    pub row: ProcRow,
+    pub depth: usize,
+    /// Box-drawing guides drawn before the name ("│ ├─").
+    pub prefix: String,
+    pub children: usize,
+    pub collapsed: bool,
+    /// The process plus every descendant.
+    pub subtree_cpu_x10: i64,
+    pub subtree_mem_bytes: u64,
+    pub subtree_swap_bytes: u64,
+    pub subtree_io_bps: u64,
+    pub descendants: usize,
+    /// False for ancestors shown only to reveal a search match.
+    pub matched: bool,
+}
+
+#[derive(Default, Clone, Copy)]
+struct Totals {
+    cpu_x10: i64,
+    mem_bytes: u64,
+    swap_bytes: u64,
+    io_bps: u64,
+    descendants: usize,
+}
+
+struct Forest<'a> {
+    rows: Vec<&'a ProcRow>,
+    children: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+    totals: Vec<Totals>,
+}
+
+fn build_forest(procs: &[ProcRow]) -> Forest<'_> {
+    // Userland threads report their process as parent; their CPU is already
+    // included in the process, so they'd double-count in subtree totals.
+    let rows: Vec<&ProcRow> = procs.iter().filter(|p| !p.is_thread).collect();
+    let index: HashMap<i32, usize> = rows.iter().enumerate().map(|(i, p)| (p.pid, i)).collect();
+
+    let mut children = vec![Vec::new(); rows.len()];
+    let mut roots = Vec::new();
+    for (i, p) in rows.iter().enumerate() {
+        match p.ppid.and_then(|ppid| index.get(&ppid)) {
+            Some(&parent) if parent != i => children[parent].push(i),
+            _ => roots.push(i),
+        }
+    }
+
+    // Post-order accumulation without recursion; `seen` guards against a
+    // parent loop from PID reuse between /proc reads.
+    let mut totals = vec![Totals::default(); rows.len()];
+    let mut seen = vec![false; rows.len()];
+    let mut order = Vec::with_capacity(rows.len());
+    let mut stack: Vec<usize> = roots.clone();
+    while let Some(i) = stack.pop() {
+        if std::mem::replace(&mut seen[i], true) {
+            continue;
+        }
+        order.push(i);
+        stack.extend(children[i].iter().copied());
+    }
+    for &i in order.iter().rev() {
+        let p = rows[i];
+        let mut t = Totals {
+            cpu_x10: p.cpu_x10 as i64,
+            mem_bytes: p.mem_bytes,
+            swap_bytes: p.swap_bytes,
+            io_bps: p.io_bps(),
+            descendants: 0,
+        };
+        for &c in &children[i] {
+            let ct = totals[c];
+            t.cpu_x10 += ct.cpu_x10;
+            t.mem_bytes += ct.mem_bytes;
+            t.swap_bytes += ct.swap_bytes;
+            t.io_bps += ct.io_bps;
+            t.descendants += ct.descendants + 1;
+        }
+        totals[i] = t;
+    }
+    // Nodes on a loop are unreachable from any root; show them at the top level.
+    roots.extend((0..rows.len()).filter(|&i| !seen[i]));
+
+    Forest {
+        rows,
+        children,
+        roots,
+        totals,
+    }
+}
+
+fn sort_key(t: &Totals, sort: ProcSort) -> Reverse<(u64, u64)> {
+    let cpu = t.cpu_x10.max(0) as u64;
+    match sort {
+        ProcSort::Cpu => Reverse((cpu, t.mem_bytes)),
+        ProcSort::Mem => Reverse((t.mem_bytes, cpu)),
+        ProcSort::Swap => Reverse((t.swap_bytes, t.mem_bytes)),
+        ProcSort::Io => Reverse((t.io_bps, cpu)),
+    }
+}
+
+/// Flattens the process hierarchy into display rows. Siblings are ordered by
+/// their subtree totals for `sort`. A non-empty `search` shows every match
+/// together with its ancestors, ignoring `collapsed`.
+pub fn build_proc_tree(
+    procs: &[ProcRow],
+    sort: ProcSort,
+    collapsed: &HashSet<i32>,
+    search: &str,
+) -> Vec<TreeRow> {
+    let mut forest = build_forest(procs);
+    let totals = &forest.totals;
+    let by_key = |a: &usize, b: &usize| {
+        sort_key(&totals[*a], sort)
+            .cmp(&sort_key(&totals[*b], sort))
+            .then(forest.rows[*a].pid.cmp(&forest.rows[*b].pid))
+    };
+    let mut roots = std::mem::take(&mut forest.roots);
+    roots.sort_by(by_key);
+    let mut children = std::mem::take(&mut forest.children);
+    for list in &mut children {
+        list.sort_by(by_key);
+    }
+
+    let needle = search.trim().to_ascii_lowercase();
+    let matches: Vec<bool> = forest
+        .rows
+        .iter()
+        .map(|p| needle.is_empty() || p.name.to_ascii_lowercase().contains(&needle))
+        .collect();
+    // keep[i]: the node matches or has a matching descendant.
+    let mut keep = matches.clone();
+    if !needle.is_empty() {
+        fn mark(i: usize, children: &[Vec<usize>], keep: &mut [bool], depth: usize) -> bool {
+            let mut any = keep[i];
+            if depth < 4096 {
+                for &c in &children[i] {
+                    any |= mark(c, children, keep, depth + 1);
+                }
+            }
+            keep[i] = any;
+            any
+        }
+        for &r in &roots {
+            mark(r, &children, &mut keep, 0);
+        }
+    }
+
+    let mut out = Vec::new();
+    // (node, depth, guides for ancestors, is last sibling)
+    let mut stack: Vec<(usize, usize, String, bool)> = Vec::new();
+    let visible_roots: Vec<usize> = roots.into_iter().filter(|&r| keep[r]).collect();
+    for (n, &r) in visible_roots.iter().enumerate().rev() {
+        stack.push((r, 0, String::new(), n + 1 == visible_roots.len()));
+    }
+    let mut emitted = vec![false; forest.rows.len()];
+    while let Some((i, depth, guides, last)) = stack.pop() {
+        if std::mem::replace(&mut emitted[i], true) {
+            continue;
+        }
+        let p = forest.rows[i];
+        let kids: Vec<usize> = children[i].iter().copied().filter(|&c| keep[c]).collect();
+        let is_collapsed = needle.is_empty() && collapsed.contains(&p.pid) && !kids.is_empty();
+        let prefix = if depth == 0 {
+            String::new()
+        } else {
+            format!("{guides}{}", if last { "└─" } else { "├─" })
+        };
+        let t = forest.totals[i];
+        out.push(TreeRow {
+            row: p.clone(),
+            depth,
+            prefix,
+            children: children[i].len(),
+            collapsed: is_collapsed,
+            subtree_cpu_x10: t.cpu_x10,
+            subtree_mem_bytes: t.mem_bytes,
+            subtree_swap_bytes: t.swap_bytes,
+            subtree_io_bps: t.io_bps,
+            descendants: t.descendants,
+            matched: matches[i],
+        });
+        if is_collapsed {
+            continue;
+        }
+        let child_guides = if depth == 0 {
+            String::new()
+        } else {
+            format!("{guides}{}", if last { "  " } else { "│ " })
+        };
+        for (n, &c) in kids.iter().enumerate().rev() {
+            stack.push((c, depth + 1, child_guides.clone(), n + 1 == kids.len()));
+        }
+    }
+    out
+}
+
+// ── Key handling ──────────────────────────────────────────────────────────────
+
+/// `t` toggles tree mode; in tree mode Space folds the selected subtree,
+/// ← collapses it (or jumps to the parent) and → expands it.
+pub fn handle_proc_tree_key(app: &mut AppState, key: &KeyEvent) -> bool {
+    if !matches!(app.screen, Screen::Processes) || app.proc_search_active {
+        return false;
+    }
+    if key.code == KeyCode::Char('t') {
+        // The selected PID carries over; the render re-finds it in the new layout.
+        app.proc_tree = !app.proc_tree;
+        return true;
+    }
+    if !app.proc_tree {
+        return false;
+    }
+
+    let view = ProcView::build(app);
+    let ProcViewRows::Tree(rows) = &view.rows else {
+        return false;
+    };
+    let Some(idx) = sync_proc_selection(app, &view) else {
+        return false;
+    };
+    let sel = &rows[idx];
+    let pid = sel.row.pid;
+    match key.code {
+        KeyCode::Char(' ') if sel.children > 0 => {
+            if !app.proc_collapsed.remove(&pid) {
+                app.proc_collapsed.insert(pid);
+            }
+            true
+        }
+        KeyCode::Right if sel.children > 0 => {
+            app.proc_collapsed.remove(&pid);
+            true
+        }
+        KeyCode::Left => {
+            if sel.children > 0 && !sel.collapsed && app.proc_search.is_empty() {
+                app.proc_collapsed.insert(pid);
+            } else if let Some(parent) = rows[..idx].iter().rposition(|r| r.depth + 1 == sel.depth)
+            {
+                app.proc_selected_pid = Some(rows[parent].row.pid);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: i32, ppid: Option<i32>, name: &str, cpu_x10: i32) -> ProcRow {
+        ProcRow {
+            pid,
+            ppid,
+            name: name.to_string(),
+            cpu_x10,
+            mem_bytes: 1024,
+            swap_bytes: 0,
+            status: "Sleep",
+            is_thread: false,
+            read_bps: 0,
+            write_bps: 0,
+        }
+    }
+
+    fn names(rows: &[TreeRow]) -> Vec<String> {
+        rows.iter()
+            .map(|r| format!("{}{}", r.prefix, r.row.name))
+            .collect()
+    }
+
+    #[test]
+    fn tree_aggregates_sorts_collapses_and_reveals_ancestors() {
+        let procs = vec![
+            proc(1, None, "init", 0),
+            proc(10, Some(1), "php-fpm", 1),
+            proc(11, Some(10), "php-fpm-worker", 50),
+            proc(12, Some(10), "php-fpm-worker", 20),
+            proc(20, Some(1), "sshd", 5),
+            proc(21, Some(20), "bash", 0),
+            ProcRow {
+                is_thread: true,
+                ..proc(22, Some(11), "worker-thread", 50)
+            },
+        ];
+
+        let rows = build_proc_tree(&procs, ProcSort::Cpu, &HashSet::new(), "");
+        assert_eq!(
+            names(&rows),
+            [
+                "init",
+                "├─php-fpm",
+                "│ ├─php-fpm-worker",
+                "│ └─php-fpm-worker",
+                "└─sshd",
+                "  └─bash",
+            ]
+        );
+        assert_eq!(rows[0].subtree_cpu_x10, 76);
+        assert_eq!(rows[1].subtree_cpu_x10, 71);
+        assert_eq!(rows[1].descendants, 2);
+
+        let collapsed = HashSet::from([10]);
+        let rows = build_proc_tree(&procs, ProcSort::Cpu, &collapsed, "");
+        assert_eq!(rows.len(), 4);
+        assert!(rows[1].collapsed);
+
+        let rows = build_proc_tree(&procs, ProcSort::Cpu, &collapsed, "bash");
+        assert_eq!(names(&rows), ["init", "└─sshd", "  └─bash"]);
+        assert_eq!(
+            rows.iter().map(|r| r.matched).collect::<Vec<_>>(),
+            [false, false, true]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use sysinfo::{Pid, Process, System};
+
+use crate::proctree::{build_proc_tree, TreeRow};
+use crate::services::filtered_proc_rows;
+use crate::system::sort_proc_rows;
+use crate::types::{AppState, ProcRow, ProcSort};
+
+/// Rows kept in the flat list; the tree always shows every process.
+pub const PROC_LIST_LIMIT: usize = 200;
+
+// ── Process view model ────────────────────────────────────────────────────────
+
+pub enum ProcViewRows {
+    List(Vec<ProcRow>),
+    Tree(Vec<TreeRow>),
+}
+
+/// The processes screen's rows in display order. Rendering and every key
+/// handler build it through here, so what you see is what `k` acts on.
+pub struct ProcView {
+    pub rows: ProcViewRows,
+}
+
+impl ProcView {
+    pub fn build(app: &AppState) -> Self {
+        if app.proc_tree {
+            return Self {
+                rows: ProcViewRows::Tree(build_proc_tree(
+                    &app.proc_rows,
+                    app.proc_sort,
+                    &app.proc_collapsed,
+                    &app.proc_search,
+                )),
+            };
+        }
+        let mut procs = app.proc_rows.clone();
+        sort_proc_rows(&mut procs, app.proc_sort);
+        // In Swap mode show only processes that are actually using swap.
+        if matches!(app.proc_sort, ProcSort::Swap) {
+            procs.retain(|p| p.swap_bytes > 0);
+        }
+        procs.truncate(PROC_LIST_LIMIT);
+        // When searching, results are sorted by name for stability.
+        Self {
+            rows: ProcViewRows::List(filtered_proc_rows(procs, &app.proc_search)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.rows {
+            ProcViewRows::List(rows) => rows.len(),
+            ProcViewRows::Tree(rows) => rows.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn row(&self, idx: usize) -> Option<&ProcRow> {
+        match &self.rows {
+            ProcViewRows::List(rows) => rows.get(idx),
+            ProcViewRows::Tree(rows) => rows.get(idx).map(|t| &t.row),
+        }
+    }
+
+    pub fn position(&self, pid: i32) -> Option<usize> {
+        (0..self.len()).find(|&i| self.row(i).map(|r| r.pid) == Some(pid))
+    }
+}
+
+// ── Selection ─────────────────────────────────────────────────────────────────
+
+/// Re-finds the selected PID in `view` and returns its row index. If that
+/// process is gone, the row now at the old cursor position is selected.
+pub fn sync_proc_selection(app: &mut AppState, view: &ProcView) -> Option<usize> {
+    if view.is_empty() {
+        return None;
+    }
+    let idx = app
+        .proc_selected_pid
+        .and_then(|pid| view.position(pid))
+        .unwrap_or_else(|| (app.proc_cursor as usize).min(view.len() - 1));
+    app.proc_selected_pid = view.row(idx).map(|r| r.pid);
+    app.proc_cursor = idx as u16;
+    Some(idx)
+}
+
+pub fn move_proc_selection(app: &mut AppState, delta: i64) {
+    let view = ProcView::build(app);
+    let Some(idx) = sync_proc_selection(app, &view) else {
+        return;
+    };
+    let next = (idx as i64 + delta).clamp(0, view.len() as i64 - 1) as usize;
+    app.proc_selected_pid = view.row(next).map(|r| r.pid);
+    app.proc_cursor = next as u16;
+}
+
+/// Back to the top row; used when the list's contents change wholesale
+/// (e.g. a new search term) rather than just re-ordering.
+pub fn reset_proc_selection(app: &mut AppState) {
+    app.proc_selected_pid = None;
+    app.proc_cursor = 0;
+    app.proc_scroll = 0;
+}
+
+/// The selected process, but only while it is still listed. Never falls back
+/// to whatever now sits at the old cursor position, so actions can't land
+/// on a different PID than the one highlighted.
+pub fn selected_proc_row(app: &AppState) -> Option<ProcRow> {
+    let pid = app.proc_selected_pid?;
+    let view = ProcView::build(app);
+    view.position(pid).and_then(|i| view.row(i)).cloned()
+}
+
+/// The live process behind a confirmed action, provided `pid` still belongs
+/// to `name`; the PID may have exited or been reused while the dialog was up.
+pub fn confirmed_process<'a>(
+    system: &'a System,
+    pid: i32,
+    name: &str,
+) -> Result<&'a Process, String> {
+    match system.process(Pid::from_u32(pid as u32)) {
+        Some(p) if p.name() == name => Ok(p),
+        Some(_) => Err(format!("PID {pid} is no longer {name}; nothing sent")),
+        None => Err(format!("{name} ({pid}) has already exited")),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: i32, cpu_x10: i32) -> ProcRow {
+        ProcRow {
+            pid,
+            ppid: None,
+            name: format!("p{pid}"),
+            cpu_x10,
+            mem_bytes: 0,
+            swap_bytes: 0,
+            status: "Run",
+            is_thread: false,
+            read_bps: 0,
+            write_bps: 0,
+        }
+    }
+
+    #[test]
+    fn selection_follows_pid_across_resorts_and_exits() {
+        let mut app = AppState {
+            proc_rows: vec![proc(1, 30), proc(2, 20), proc(3, 10)],
+            ..AppState::default()
+        };
+        move_proc_selection(&mut app, 1);
+        assert_eq!(app.proc_selected_pid, Some(2));
+
+        // PID 2 becomes the busiest; the selection moves with it.
+        app.proc_rows = vec![proc(1, 30), proc(2, 90), proc(3, 10)];
+        let view = ProcView::build(&app);
+        assert_eq!(sync_proc_selection(&mut app, &view), Some(0));
+        assert_eq!(selected_proc_row(&app).map(|r| r.pid), Some(2));
+
+        // Once it exits, actions refuse rather than picking a neighbour...
+        app.proc_rows = vec![proc(1, 30), proc(3, 10)];
+        assert!(selected_proc_row(&app).is_none());
+        // ...and the cursor settles on the row at its old position.
+        let view = ProcView::build(&app);
+        assert_eq!(sync_proc_selection(&mut app, &view), Some(0));
+        assert_eq!(app.proc_selected_pid, Some(1));
+
+        // I/O rates only exist on the collected rows; `k`/`R` must act on
+        // those, not on rows rebuilt from sysinfo with zero I/O.
+        app.proc_sort = ProcSort::Io;
+        app.proc_rows = vec![
+            proc(1, 30),
+            ProcRow {
+                write_bps: 4096,
+                ..proc(3, 10)
+            },
+        ];
+        app.proc_selected_pid = None;
+        move_proc_selection(&mut app, 0);
+        assert_eq!(selected_proc_row(&app).map(|r| r.pid), Some(3));
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use crate::config::config;
+use crate::headless::HeadlessCollector;
+use crate::journal::raw_entry;
+use crate::services::{collect_logs, collect_services};
+use crate::system::top_process_rows;
+use crate::types::{
+    DiskRow, LogEntry, LogSeverity, NetRow, ProcRow, ProcSort, ServiceRow, ServiceScope, VmSnapshot,
+};
+use crate::update::VERSION;
+use crate::utils::unix_ms_now;
+
+pub const RECORDING_FORMAT: &str = "ferromon.recording";
+/// Bump when a field is removed or changes meaning; adding fields is compatible.
+pub const RECORDING_VERSION: u32 = 1;
+
+/// Per sort key; the union keeps every view of the Processes screen faithful.
+const RECORDED_PROCS_PER_SORT: usize = 200;
+
+// ── File format ───────────────────────────────────────────────────────────────
+//
+// A recording is one gzip stream of JSON lines: a header first, then one
+// frame per tick. Each frame ends with a sync flush, so the bytes on disk
+// always decode up to the last complete frame even though a recorder
+// stopped with Ctrl-C never writes the gzip trailer.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub format: String,
+    pub version: u32,
+    pub ferro_version: String,
+    pub hostname: String,
+    pub tick_ms: u64,
+    pub started_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    pub t_unix_ms: u64,
+    pub vm: VmSnapshot,
+    /// On disk, rows that are new or changed since the previous frame; after
+    /// `read_recording`, the whole recorded table.
+    pub procs: Vec<ProcRow>,
+    /// PIDs dropped from the table since the previous frame.
+    #[serde(default)]
+    pub procs_gone: Vec<i32>,
+    pub mounts: Vec<DiskRow>,
+    pub network: Vec<NetRow>,
+    /// `None` means unchanged since the previous frame that carried rows.
+    pub services: Option<Vec<ServiceRow>>,
+    /// Why `services` is empty when listing them failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub services_error: Option<String>,
+    pub logs: Option<RecordedLogs>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedLogs {
+    pub source: String,
+    pub entries: Vec<LogEntry>,
+}
+
+pub struct RecordingWriter<W: Write> {
+    out: GzEncoder<W>,
+}
+
+impl<W: Write> RecordingWriter<W> {
+    pub fn new(out: W, header: &RecordingHeader) -> io::Result<Self> {
+        let mut writer = Self {
+            out: GzEncoder::new(out, Compression::default()),
+        };
+        writer.append(header)?;
+        Ok(writer)
+    }
+
+    pub fn append<T: Serialize>(&mut self, record: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+}
+
+/// Replaces each frame's process changes with the table they add up to.
+fn resolve_proc_deltas(frames: &mut [RecordedFrame]) {
+    let mut table: BTreeMap<i32, ProcRow> = BTreeMap::new();
+    for frame in frames {
+        for pid in std::mem::take(&mut frame.procs_gone) {
+            table.remove(&pid);
+        }
+        for row in frame.procs.drain(..) {
+            table.insert(row.pid, row);
+        }
+        frame.procs = table.values().cloned().collect();
+    }
+}
+
+pub fn read_recording(
+    reader: impl io::Read,
+) -> Result<(RecordingHeader, Vec<RecordedFrame>), String> {
+    let mut lines = BufReader::new(GzDecoder::new(reader)).lines();
+    let first = lines
+        .next()
+        .ok_or_else(|| "recording is empty".to_string())?
+        .map_err(|e| format!("failed to read recording: {e}"))?;
+    let header: RecordingHeader =
+        serde_json::from_str(&first).map_err(|_| "not a ferro recording".to_string())?;
+    if header.format != RECORDING_FORMAT {
+        return Err("not a ferro recording".to_string());
+    }
+    if header.version > RECORDING_VERSION {
+        return Err(format!(
+            "recording version {} is newer than this ferro supports ({RECORDING_VERSION})",
+            header.version
+        ));
+    }
+
+    let mut frames = Vec::new();
+    for line in lines {
+        // A truncated tail (recorder killed mid-write) ends the recording.
+        let Ok(line) = line else { break };
+        let Ok(frame) = serde_json::from_str::<RecordedFrame>(&line) else {
+            break;
+        };
+        frames.push(frame);
+    }
+    if frames.is_empty() {
+        return Err("recording has no frames".to_string());
+    }
+    resolve_proc_deltas(&mut frames);
+    Ok((header, frames))
+}
+
+pub fn load_recording(path: &Path) -> Result<(RecordingHeader, Vec<RecordedFrame>), String> {
+    let file = File::open(path).map_err(|e| format!("failed to open {}: {e}", path.display()))?;
+    read_recording(file)
+}
+
+// ── `ferro record` ────────────────────────────────────────────────────────────
+
+fn recorded_procs(procs: &[ProcRow]) -> Vec<ProcRow> {
+    let mut out: Vec<ProcRow> = Vec::new();
+    for sort in [ProcSort::Cpu, ProcSort::Mem, ProcSort::Swap, ProcSort::Io] {
+        for row in top_process_rows(procs, sort, RECORDED_PROCS_PER_SORT) {
+            if !out.iter().any(|r| r.pid == row.pid) {
+                out.push(row);
+            }
+        }
+    }
+    out
+}
+
+/// The rows of `procs` that differ from `prev`, and the PIDs no longer in it.
+fn proc_changes(prev: &HashMap<i32, ProcRow>, procs: &[ProcRow]) -> (Vec<ProcRow>, Vec<i32>) {
+    let changed = procs
+        .iter()
+        .filter(|row| prev.get(&row.pid) != Some(row))
+        .cloned()
+        .collect();
+    let pids: HashSet<i32> = procs.iter().map(|row| row.pid).collect();
+    let mut gone: Vec<i32> = prev
+        .keys()
+        .filter(|pid| !pids.contains(pid))
+        .copied()
+        .collect();
+    gone.sort_unstable();
+    (changed, gone)
+}
+
+pub fn run_record(path: &Path, tick_ms: u64) -> Result<(), String> {
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| format!("failed to create {}: {e}", path.display()))?;
+
+    let interval = Duration::from_millis(tick_ms);
+    let services_every = Duration::from_secs(config().refresh.services_secs);
+    let logs_every = Duration::from_secs(config().refresh.logs_secs);
+    let mut collector = HeadlessCollector::new(true);
+    let header = RecordingHeader {
+        format: RECORDING_FORMAT.to_string(),
+        version: RECORDING_VERSION,
+        ferro_version: VERSION.to_string(),
+        hostname: collector.app.hostname.clone(),
+        tick_ms,
+        started_unix_ms: unix_ms_now(),
+    };
+    let mut writer = RecordingWriter::new(io::BufWriter::new(file), &header)
+        .map_err(|e| format!("failed to write recording: {e}"))?;
+
+    eprintln!(
+        "ferro: recording to {} every {tick_ms}ms (Ctrl-C to stop)",
+        path.display()
+    );
+
+    let mut last_procs: HashMap<i32, ProcRow> = HashMap::new();
+    let mut last_services: Option<(Instant, Result<Vec<ServiceRow>, String>)> = None;
+    let mut last_logs: Option<(Instant, RecordedLogs)> = None;
+
+    // Every frame is flushed to disk as it is written, so Ctrl-C is the stop
+    // button: at worst the final frame is cut short and ignored on replay.
+    loop {
+        std::thread::sleep(interval);
+        let vm = collector.sample();
+
+        let services = match &last_services {
+            Some((at, _)) if at.elapsed() < services_every => None,
+            _ => {
+                let result = collect_services(ServiceScope::System);
+                let changed = last_services
+                    .as_ref()
+                    .map(|(_, prev)| *prev != result)
+                    .unwrap_or(true);
+                last_services = Some((Instant::now(), result.clone()));
+                changed.then_some(result)
+            }
+        };
+        let (services, services_error) = match services {
+            Some(Ok(rows)) => (Some(rows), None),
+            Some(Err(err)) => (Some(Vec::new()), Some(err)),
+            None => (None, None),
+        };
+        let logs = match &last_logs {
+            Some((at, _)) if at.elapsed() < logs_every => None,
+            _ => {
+                let logs = match collect_logs(None, false, LogSeverity::default(), None) {
+                    Ok((source, entries)) => RecordedLogs {
+                        source,
+                        entries: entries
+                            .into_iter()
+                            .map(|entry| LogEntry {
+                                fields: Vec::new(),
+                                ..entry
+                            })
+                            .collect(),
+                    },
+                    Err(err) => RecordedLogs {
+                        source: "unavailable".to_string(),
+                        entries: vec![raw_entry(&err)],
+                    },
+                };
+                let changed = last_logs
+                    .as_ref()
+                    .map(|(_, prev)| *prev != logs)
+                    .unwrap_or(true);
+                last_logs = Some((Instant::now(), logs.clone()));
+                changed.then_some(logs)
+            }
+        };
+
+        let procs = recorded_procs(&collector.app.proc_rows);
+        let (changed_procs, procs_gone) = proc_changes(&last_procs, &procs);
+        last_procs = procs.into_iter().map(|row| (row.pid, row)).collect();
+
+        let frame = RecordedFrame {
+            t_unix_ms: unix_ms_now(),
+            vm,
+            procs: changed_procs,
+            procs_gone,
+            mounts: collector.app.dash_mount_rows.clone(),
+            network: collector.app.net_rows.clone(),
+            services,
+            services_error,
+            logs,
+        };
+        writer
+            .append(&frame)
+            .map_err(|e| format!("failed to write recording: {e}"))?;
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Psi;
+
+    fn frame(t_unix_ms: u64, cpu_usage: f32, procs: Vec<ProcRow>, gone: Vec<i32>) -> RecordedFrame {
+        RecordedFrame {
+            t_unix_ms,
+            vm: VmSnapshot {
+                cpu_usage,
+                cpu_cores: 1,
+                core_usage: vec![cpu_usage],
+                cpu_breakdown: None,
+                psi: Psi::default(),
+                load_avg_one: 0.0,
+                load_avg_five: 0.0,
+                load_avg_fifteen: 0.0,
+                total_memory: 0,
+                used_memory: 0,
+                available_memory: 0,
+                memory_percent: 0.0,
+                total_swap: 0,
+                used_swap: 0,
+                uptime_secs: 0,
+            },
+            procs,
+            procs_gone: gone,
+            mounts: Vec::new(),
+            network: Vec::new(),
+            services: None,
+            services_error: None,
+            logs: None,
+        }
+    }
+
+    fn proc(pid: i32, cpu_x10: i32) -> ProcRow {
+        ProcRow {
+            pid,
+            ppid: None,
+            name: format!("p{pid}"),
+            cpu_x10,
+            mem_bytes: 1024,
+            swap_bytes: 0,
+            status: "Sleep",
+            is_thread: false,
+            read_bps: 0,
+            write_bps: 0,
+        }
+    }
+
+    #[test]
+    fn recording_round_trips_and_tolerates_truncated_tail() {
+        let header = RecordingHeader {
+            format: RECORDING_FORMAT.to_string(),
+            version: RECORDING_VERSION,
+            ferro_version: "test".to_string(),
+            hostname: "box".to_string(),
+            tick_ms: 500,
+            started_unix_ms: 1_000,
+        };
+        let first = vec![proc(1, 5), proc(2, 7)];
+        let (changed, gone) = proc_changes(&HashMap::new(), &first);
+        assert_eq!((changed.len(), gone.len()), (2, 0));
+        let prev = first.into_iter().map(|row| (row.pid, row)).collect();
+        let (changed, gone) = proc_changes(&prev, &[proc(1, 5), proc(3, 1)]);
+        assert_eq!(changed, vec![proc(3, 1)]);
+        assert_eq!(gone, vec![2]);
+
+        let mut writer = RecordingWriter::new(Vec::new(), &header).unwrap();
+        writer
+            .append(&frame(1_500, 10.0, vec![proc(1, 5), proc(2, 7)], vec![]))
+            .unwrap();
+        writer.append(&frame(2_000, 20.0, changed, gone)).unwrap();
+        // Simulate a recorder killed halfway through its third frame.
+        let full_len = writer.out.get_ref().len();
+        writer
+            .append(&frame(2_500, 30.0, vec![proc(4, 1)], vec![]))
+            .unwrap();
+        let mut bytes = writer.out.get_ref().clone();
+        bytes.truncate(full_len + (bytes.len() - full_len) / 2);
+
+        let (read_header, frames) = read_recording(&bytes[..]).unwrap();
+
+        assert_eq!(read_header.hostname, "box");
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].t_unix_ms, 2_000);
+        assert_eq!(frames[1].vm.cpu_usage, 20.0);
+        assert_eq!(frames[0].procs[0].status, "Sleep");
+        // Process changes add up to the whole table again.
+        let pids: Vec<i32> = frames[1].procs.iter().map(|r| r.pid).collect();
+        assert_eq!(pids, vec![1, 3]);
+    }
+}
@@ -0,0 +1,243 @@
+use std::io;
+use std::time::{Duration, Instant, SystemTime};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+use crate::app::draw_screen;
+use crate::config::config;
+use crate::network::push_net_history;
+use crate::procview::move_proc_selection;
+use crate::recording::{RecordedFrame, RecordedLogs, RecordingHeader};
+use crate::services::{handle_proc_search_key, handle_service_search_key};
+use crate::system::fill_dashboard_process_cache;
+use crate::types::{AppState, ProcSort, ReplayStatus, Screen, ServiceFilter};
+use crate::utils::push_history_sample;
+
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const SEEK_FRAMES: usize = 10;
+
+fn latest_services(frames: &[RecordedFrame], idx: usize) -> Option<&RecordedFrame> {
+    frames[..=idx].iter().rev().find(|f| f.services.is_some())
+}
+
+fn latest_logs(frames: &[RecordedFrame], idx: usize) -> Option<(u64, &RecordedLogs)> {
+    frames[..=idx]
+        .iter()
+        .rev()
+        .find_map(|f| f.logs.as_ref().map(|logs| (f.t_unix_ms, logs)))
+}
+
+// "Updated Ns ago" labels should be relative to the frame, not to wall time.
+fn replayed_at(frame_t_ms: u64, sample_t_ms: u64) -> SystemTime {
+    SystemTime::now() - Duration::from_millis(frame_t_ms.saturating_sub(sample_t_ms))
+}
+
+/// Loads frame `idx` into the same `AppState` fields the live collectors fill.
+fn apply_frame(app: &mut AppState, frames: &[RecordedFrame], idx: usize) {
+    let frame = &frames[idx];
+    let history_len = config().general.history_len;
+    app.proc_rows = frame.procs.clone();
+    app.dash_mount_rows = frame.mounts.clone();
+    app.net_rows = frame.network.clone();
+    app.cpu_breakdown = frame.vm.cpu_breakdown;
+    app.psi = frame.vm.psi;
+    fill_dashboard_process_cache(app, &frame.vm, &frame.procs);
+
+    if let Some(sample) = latest_services(frames, idx) {
+        let mut state = app.service_state.inner.lock().unwrap();
+        state.rows = sample.services.clone().unwrap_or_default();
+        state.error = sample.services_error.clone();
+        state.last_updated_at = Some(replayed_at(frame.t_unix_ms, sample.t_unix_ms));
+    }
+    if let Some((t, logs)) = latest_logs(frames, idx) {
+        let mut state = app.log_state.inner.lock().unwrap();
+        state.source = logs.source.clone();
+        state.entries = logs.entries.iter().cloned().collect();
+        state.last_updated_at = Some(replayed_at(frame.t_unix_ms, t));
+    }
+
+    // Rebuild sparklines from the preceding frames so seeking shows context.
+    app.dash_cpu_history.clear();
+    app.dash_mem_history.clear();
+    app.dash_psi_cpu_history.clear();
+    app.dash_psi_mem_history.clear();
+    app.dash_psi_io_history.clear();
+    app.net_history.clear();
+    let start = (idx + 1).saturating_sub(history_len);
+    for f in &frames[start..=idx] {
+        push_history_sample(
+            &mut app.dash_cpu_history,
+            f.vm.cpu_usage as f64,
+            history_len,
+        );
+        push_history_sample(&mut app.dash_mem_history, f.vm.memory_percent, history_len);
+        let psi_samples = [
+            (&mut app.dash_psi_cpu_history, f.vm.psi.cpu),
+            (&mut app.dash_psi_mem_history, f.vm.psi.memory),
+            (&mut app.dash_psi_io_history, f.vm.psi.io),
+        ];
+        for (history, res) in psi_samples {
+            if let Some(res) = res {
+                push_history_sample(history, res.some.avg10 as f64, history_len);
+            }
+        }
+        push_net_history(&mut app.net_history, &f.network, history_len);
+    }
+}
+
+pub fn run_replay(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    header: &RecordingHeader,
+    frames: &[RecordedFrame],
+) -> io::Result<()> {
+    let mut app = AppState {
+        hostname: header.hostname.clone(),
+        tick_ms: header.tick_ms,
+        // Recorded rows are unfiltered; start on the widest view.
+        service_filter: ServiceFilter::All,
+        dash_dir_sizes: vec!["Dir sizes".to_string(), "(not recorded)".to_string()],
+        ..Default::default()
+    };
+    // Disk dive and timers read live state, which a recording cannot stand in for.
+    if matches!(app.screen, Screen::DiskDive | Screen::Timers) {
+        app.screen = Screen::Dashboard;
+    }
+    let first_t = frames[0].t_unix_ms;
+    let mut idx = 0usize;
+    let mut playing = true;
+    let mut speed_idx = SPEEDS.iter().position(|s| *s == 1.0).unwrap_or(0);
+    let mut applied: Option<usize> = None;
+    let mut last_advance = Instant::now();
+    let mut tip_clock = Instant::now();
+
+    loop {
+        if tip_clock.elapsed() >= Duration::from_secs(12) {
+            app.footer_tip_idx = app.footer_tip_idx.wrapping_add(1);
+            tip_clock = Instant::now();
+        }
+
+        if playing {
+            if idx + 1 < frames.len() {
+                let gap = frames[idx + 1]
+                    .t_unix_ms
+                    .saturating_sub(frames[idx].t_unix_ms);
+                let due = Duration::from_secs_f64(gap as f64 / 1000.0 / SPEEDS[speed_idx]);
+                if last_advance.elapsed() >= due {
+                    idx += 1;
+                    last_advance = Instant::now();
+                }
+            } else {
+                playing = false;
+            }
+        }
+
+        if applied != Some(idx) {
+            apply_frame(&mut app, frames, idx);
+            applied = Some(idx);
+        }
+        app.replay = Some(ReplayStatus {
+            position: idx + 1,
+            total: frames.len(),
+            playing,
+            speed: SPEEDS[speed_idx],
+            offset_ms: frames[idx].t_unix_ms.saturating_sub(first_t),
+        });
+
+        let vm = frames[idx].vm.clone();
+        terminal.draw(|frame| draw_screen(frame, &mut app, &vm))?;
+
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if handle_proc_search_key(&mut app, &key) || handle_service_search_key(&mut app, &key) {
+            continue;
+        }
+
+        let last = frames.len() - 1;
+        match key.code {
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Char('?') => app.show_help = !app.show_help,
+            KeyCode::Esc => {
+                app.show_help = false;
+                app.screen = Screen::Dashboard;
+            }
+            KeyCode::Char('p') => app.screen = Screen::Processes,
+            KeyCode::Char('n') => app.screen = Screen::Network,
+            KeyCode::Char('v') => app.screen = Screen::Services,
+            KeyCode::Char('l') => app.screen = Screen::Logs,
+            // Recordings keep only the busiest processes, so the tree is partial.
+            KeyCode::Char('t') if matches!(app.screen, Screen::Processes) => {
+                app.proc_tree = !app.proc_tree;
+            }
+
+            // Playback
+            KeyCode::Char(' ') => {
+                if !playing && idx == last {
+                    idx = 0;
+                }
+                playing = !playing;
+                last_advance = Instant::now();
+            }
+            KeyCode::Right | KeyCode::Char('.') => {
+                playing = false;
+                idx = (idx + 1).min(last);
+            }
+            KeyCode::Left | KeyCode::Char(',') => {
+                playing = false;
+                idx = idx.saturating_sub(1);
+            }
+            KeyCode::Char(']') => idx = (idx + SEEK_FRAMES).min(last),
+            KeyCode::Char('[') => idx = idx.saturating_sub(SEEK_FRAMES),
+            KeyCode::Char('g') | KeyCode::Home => idx = 0,
+            KeyCode::Char('G') | KeyCode::End => idx = last,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                speed_idx = (speed_idx + 1).min(SPEEDS.len() - 1);
+            }
+            KeyCode::Char('-') => speed_idx = speed_idx.saturating_sub(1),
+
+            KeyCode::Up => match app.screen {
+                Screen::Processes => move_proc_selection(&mut app, -1),
+                Screen::Services => app.service_scroll = app.service_scroll.saturating_sub(1),
+                Screen::Logs => app.logs_scroll = app.logs_scroll.saturating_sub(1),
+                Screen::Network => app.net_scroll = app.net_scroll.saturating_sub(1),
+                _ => {}
+            },
+            KeyCode::Down => match app.screen {
+                Screen::Processes => move_proc_selection(&mut app, 1),
+                Screen::Services => app.service_scroll = app.service_scroll.saturating_add(1),
+                Screen::Logs => app.logs_scroll = app.logs_scroll.saturating_add(1),
+                Screen::Network => app.net_scroll = app.net_scroll.saturating_add(1),
+                _ => {}
+            },
+            KeyCode::Tab => match app.screen {
+                Screen::Processes => {
+                    app.proc_sort = match app.proc_sort {
+                        ProcSort::Cpu => ProcSort::Mem,
+                        ProcSort::Mem => ProcSort::Swap,
+                        ProcSort::Swap => ProcSort::Io,
+                        ProcSort::Io => ProcSort::Cpu,
+                    };
+                }
+                Screen::Services => {
+                    app.service_filter = match app.service_filter {
+                        ServiceFilter::Failed => ServiceFilter::Unhealthy,
+                        ServiceFilter::Unhealthy => ServiceFilter::Active,
+                        ServiceFilter::Active => ServiceFilter::All,
+                        ServiceFilter::All => ServiceFilter::Failed,
+                    };
+                    app.service_scroll = 0;
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
@@ -6,8 +6,16 @@ use std::time::Instant;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use crate::config::config;
+use crate::journal::{parse_journal_record, raw_entry};
+use crate::logfollow::start_log_follow;
+use crate::logsearch::LogFilter;
+use crate::procview::reset_proc_selection;
+use crate::svcusage::sort_service_view;
+use crate::timers::selected_timer;
 use crate::types::{
-    AppState, LogSeverity, LogUnitFilter, ProcRow, Screen, ServiceFilter, ServiceHealth, ServiceRow,
+    AppState, LogEntry, LogSeverity, LogUnitFilter, ProcRow, Screen, ServiceFilter, ServiceHealth,
+    ServiceRow, ServiceScope, ServiceSort,
 };
 use crate::utils::trim_to;
 
@@ -36,34 +44,47 @@ pub fn refresh_services(app: &mut AppState, force: bool) {
     let due = force
         || app
             .service_last_refresh_at
-            .map(|t| t.elapsed() >= Duration::from_secs(15))
+            .map(|t| t.elapsed() >= Duration::from_secs(config().refresh.services_secs))
             .unwrap_or(true);
     if !due {
         return;
     }
 
-    {
+    start_service_watcher(app);
+
+    let scope = app.service_scope;
+    let live = {
         let mut state = app.service_state.inner.lock().unwrap();
-        if state.running {
+        // The D-Bus watcher keeps system units current; only the user
+        // manager still needs polling.
+        if state.running || (state.live && scope == ServiceScope::System) {
             return;
         }
         state.running = true;
         state.error = None;
         state.unsupported = None;
-    }
+        state.live
+    };
 
     app.service_last_refresh_at = Some(Instant::now());
     let inner = app.service_state.inner.clone();
+    let user_only = live && scope == ServiceScope::Both;
     std::thread::spawn(move || {
-        let result = collect_services();
+        let result = if user_only {
+            // As in collect_services: no user manager shouldn't hide anything.
+            Ok(collect_services_systemctl(true).unwrap_or_default())
+        } else {
+            collect_services(scope)
+        };
         let mut state = inner.lock().unwrap();
         state.running = false;
+        if state.scope != scope {
+            // Switched manager while this was running; the next tick reloads.
+            return;
+        }
         match result {
-            Ok(rows) => {
-                state.rows = rows;
-                state.error = None;
-                state.last_updated_at = Some(std::time::SystemTime::now());
-            }
+            Ok(rows) if user_only => state.replace_user_rows(rows),
+            Ok(rows) => state.replace_rows(rows),
             Err(err) => {
                 state.error = Some(err);
             }
@@ -71,6 +92,17 @@ pub fn refresh_services(app: &mut AppState, force: bool) {
     });
 }
 
+/// Starts the D-Bus watcher once; from then on it keeps system units current.
+pub fn start_service_watcher(app: &mut AppState) {
+    #[cfg(target_os = "linux")]
+    if !app.service_watcher_started {
+        app.service_watcher_started = true;
+        crate::systemd::spawn_service_watcher(app.service_state.clone());
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = app;
+}
+
 // ── Log refresh ───────────────────────────────────────────────────────────────
 
 pub fn refresh_logs(app: &mut AppState, force: bool) {
@@ -79,15 +111,24 @@ pub fn refresh_logs(app: &mut AppState, force: bool) {
         state.running = false;
         state.unsupported = Some(msg);
         state.error = None;
-        state.lines.clear();
+        state.entries.clear();
         state.source.clear();
         return;
     }
 
+    // Following streams on its own; a forced refresh (new filter, `r`)
+    // restarts the stream with the current filters.
+    if app.log_follow.is_some() {
+        if force {
+            start_log_follow(app);
+        }
+        return;
+    }
+
     let due = force
         || app
             .log_last_refresh_at
-            .map(|t| t.elapsed() >= Duration::from_secs(5))
+            .map(|t| t.elapsed() >= Duration::from_secs(config().refresh.logs_secs))
             .unwrap_or(true);
     if !due {
         return;
@@ -97,6 +138,7 @@ pub fn refresh_logs(app: &mut AppState, force: bool) {
         LogUnitFilter::Selected => app.log_selected_unit.clone(),
         LogUnitFilter::All => None,
     };
+    let user = app.log_selected_user && unit.is_some();
 
     {
         let mut state = app.log_state.inner.lock().unwrap();
@@ -111,14 +153,19 @@ pub fn refresh_logs(app: &mut AppState, force: bool) {
     app.log_last_refresh_at = Some(Instant::now());
     let inner = app.log_state.inner.clone();
     let severity = app.log_severity;
+    let filter = app.log_filter.clone();
     std::thread::spawn(move || {
-        let result = collect_logs(unit.as_deref(), severity);
+        let result = collect_logs(unit.as_deref(), user, severity, filter.as_ref());
         let mut state = inner.lock().unwrap();
         state.running = false;
+        if state.following {
+            // Follow mode started while this read was running; it owns the buffer now.
+            return;
+        }
         match result {
-            Ok((source, lines)) => {
+            Ok((source, entries)) => {
                 state.source = source;
-                state.lines = lines;
+                state.entries = entries.into();
                 state.error = None;
                 state.last_updated_at = Some(std::time::SystemTime::now());
             }
@@ -131,19 +178,47 @@ pub fn refresh_logs(app: &mut AppState, force: bool) {
 
 // ── Service collection ────────────────────────────────────────────────────────
 
-fn collect_services() -> Result<Vec<ServiceRow>, String> {
+/// Lists units of the chosen manager(s). System units come over D-Bus
+/// when the system bus is reachable, otherwise through `systemctl show`;
+/// user units always through `systemctl --user show`.
+pub fn collect_services(scope: ServiceScope) -> Result<Vec<ServiceRow>, String> {
     if !cfg!(target_os = "linux") {
         return Err("services are unsupported on this OS".to_string());
     }
+    match scope {
+        ServiceScope::System => collect_system_services(),
+        ServiceScope::User => collect_services_systemctl(true),
+        ServiceScope::Both => {
+            let mut rows = collect_system_services()?;
+            // No user manager (root over ssh, cron) shouldn't hide the system list.
+            rows.extend(collect_services_systemctl(true).unwrap_or_default());
+            sort_service_rows(&mut rows);
+            Ok(rows)
+        }
+    }
+}
+
+fn collect_system_services() -> Result<Vec<ServiceRow>, String> {
+    #[cfg(target_os = "linux")]
+    if let Ok(rows) = crate::systemd::collect_services_dbus() {
+        return Ok(rows);
+    }
+    collect_services_systemctl(false)
+}
 
-    let output = Command::new("systemctl")
+fn collect_services_systemctl(user: bool) -> Result<Vec<ServiceRow>, String> {
+    let mut cmd = Command::new("systemctl");
+    if user {
+        cmd.arg("--user");
+    }
+    let output = cmd
         .args([
             "show",
             "*.service",
             "--type=service",
             "--all",
             "--no-pager",
-            "--property=Id,Description,LoadState,ActiveState,SubState,NRestarts,ActiveEnterTimestamp,InactiveEnterTimestamp",
+            "--property=Id,Description,LoadState,ActiveState,SubState,NRestarts,ActiveEnterTimestamp,InactiveEnterTimestamp,ControlGroup",
         ])
         .output()
         .map_err(|e| format!("failed to run systemctl: {e}"))?;
@@ -157,7 +232,11 @@ fn collect_services() -> Result<Vec<ServiceRow>, String> {
         });
     }
 
-    Ok(parse_service_rows(&String::from_utf8_lossy(&output.stdout)))
+    let mut rows = parse_service_rows(&String::from_utf8_lossy(&output.stdout));
+    for row in &mut rows {
+        row.user = user;
+    }
+    Ok(rows)
 }
 
 pub fn parse_service_rows(stdout: &str) -> Vec<ServiceRow> {
@@ -171,6 +250,7 @@ pub fn parse_service_rows(stdout: &str) -> Vec<ServiceRow> {
         let mut restarts = 0u32;
         let mut active_enter = String::new();
         let mut inactive_enter = String::new();
+        let mut control_group = String::new();
 
         for line in block.lines() {
             let Some((key, value)) = line.split_once('=') else {
@@ -185,6 +265,7 @@ pub fn parse_service_rows(stdout: &str) -> Vec<ServiceRow> {
                 "NRestarts" => restarts = value.parse::<u32>().unwrap_or(0),
                 "ActiveEnterTimestamp" => active_enter = value.to_string(),
                 "InactiveEnterTimestamp" => inactive_enter = value.to_string(),
+                "ControlGroup" => control_group = value.to_string(),
                 _ => {}
             }
         }
@@ -209,9 +290,18 @@ pub fn parse_service_rows(stdout: &str) -> Vec<ServiceRow> {
             restarts,
             last_change: simplify_timestamp(&last_change),
             health,
+            usage: Default::default(),
+            user: false,
+            control_group,
         });
     }
 
+    sort_service_rows(&mut rows);
+    rows
+}
+
+/// Worst health first, then most restarts, then by name.
+pub fn sort_service_rows(rows: &mut [ServiceRow]) {
     rows.sort_by_key(|row| {
         (
             service_health_rank(row.health),
@@ -219,72 +309,114 @@ pub fn parse_service_rows(stdout: &str) -> Vec<ServiceRow> {
             row.name.clone(),
         )
     });
-    rows
 }
 
 // ── Log collection ────────────────────────────────────────────────────────────
 
-fn collect_logs(
+/// `user` reads the unit from the user journal (`--user-unit`). The regex
+/// `filter` goes to journalctl as `--grep` so the last 80 lines are 80
+/// matches, and is applied here too for syslog and for journalctl builds
+/// without pattern support.
+pub fn collect_logs(
     unit: Option<&str>,
+    user: bool,
     severity: LogSeverity,
-) -> Result<(String, Vec<String>), String> {
+    filter: Option<&LogFilter>,
+) -> Result<(String, Vec<LogEntry>), String> {
     if !cfg!(target_os = "linux") {
         return Err("logs are unsupported on this OS".to_string());
     }
 
+    let grep = filter.map(LogFilter::journal_grep);
+    let mut result = read_journal(unit, user, severity, grep.as_deref());
+    if result.is_err() && grep.is_some() {
+        // "Compiled without pattern matching support"; filter locally.
+        result = read_journal(unit, user, severity, None);
+    }
+    let (source, mut entries) = match result {
+        Ok(entries) => ("journalctl".to_string(), entries),
+        Err(err) => fallback_syslog(unit, severity, &err)?,
+    };
+    if let Some(filter) = filter {
+        entries.retain(|entry| filter.matches(entry));
+    }
+    Ok((source, entries))
+}
+
+fn read_journal(
+    unit: Option<&str>,
+    user: bool,
+    severity: LogSeverity,
+    grep: Option<&str>,
+) -> Result<Vec<LogEntry>, String> {
     let mut cmd = Command::new("journalctl");
-    cmd.args(["--no-pager", "-o", "short-iso", "-n", "80"]);
-    match severity {
-        LogSeverity::Errors => {
-            cmd.args(["-p", "err"]);
-        }
-        LogSeverity::Warnings => {
-            cmd.args(["-p", "warning"]);
-        }
-        LogSeverity::Info => {
-            cmd.args(["-p", "info"]);
-        }
-        LogSeverity::Debug => {
-            cmd.args(["-p", "debug"]);
-        }
+    cmd.args(["--no-pager", "-o", "json", "-n", "80"]);
+    journal_filter_args(&mut cmd, unit, user, severity);
+    if let Some(grep) = grep {
+        cmd.arg(format!("--grep={grep}"));
     }
-    if let Some(unit) = unit {
-        cmd.args(["-u", unit]);
+
+    let output = cmd
+        .output()
+        .map_err(|err| format!("journalctl failed: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            "journalctl failed".to_string()
+        } else {
+            stderr
+        });
     }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.lines().filter_map(parse_journal_record).collect())
+}
 
-    match cmd.output() {
-        Ok(output) if output.status.success() => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let lines = stdout
-                .lines()
-                .map(|line| line.to_string())
-                .collect::<Vec<String>>();
-            return Ok(("journalctl".to_string(), lines));
-        }
-        Ok(output) => {
-            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-            if !stderr.is_empty() {
-                return fallback_syslog(unit, severity, &stderr);
-            }
-        }
-        Err(err) => {
-            return fallback_syslog(unit, severity, &format!("journalctl failed: {err}"));
-        }
+/// `-p <priority>` and the unit match shared by the one-shot read and follow.
+pub fn journal_filter_args(
+    cmd: &mut Command,
+    unit: Option<&str>,
+    user: bool,
+    severity: LogSeverity,
+) {
+    let priority = match severity {
+        LogSeverity::Errors => "err",
+        LogSeverity::Warnings => "warning",
+        LogSeverity::Info => "info",
+        LogSeverity::Debug => "debug",
+    };
+    cmd.args(["-p", priority]);
+    if let Some(unit) = unit {
+        cmd.args([if user { "--user-unit" } else { "-u" }, unit]);
     }
+}
 
-    fallback_syslog(unit, severity, "journalctl failed")
+/// The first syslog file present, for hosts without a journal.
+pub fn syslog_path() -> Option<&'static str> {
+    ["/var/log/syslog", "/var/log/messages"]
+        .into_iter()
+        .find(|path| Path::new(path).exists())
+}
+
+/// Syslog has no fields to filter on, so the unit and severity become
+/// substring matches.
+pub fn syslog_line_matches(line: &str, unit: Option<&str>, severity: LogSeverity) -> bool {
+    if unit.is_some_and(|unit| !line.contains(unit)) {
+        return false;
+    }
+    let needle = match severity {
+        LogSeverity::Errors => "err",
+        LogSeverity::Warnings => "warn",
+        LogSeverity::Info | LogSeverity::Debug => return true,
+    };
+    line.to_lowercase().contains(needle)
 }
 
 fn fallback_syslog(
     unit: Option<&str>,
     severity: LogSeverity,
     journal_error: &str,
-) -> Result<(String, Vec<String>), String> {
-    let syslog_path = ["/var/log/syslog", "/var/log/messages"]
-        .iter()
-        .find(|path| Path::new(path).exists())
-        .copied()
-        .ok_or_else(|| journal_error.to_string())?;
+) -> Result<(String, Vec<LogEntry>), String> {
+    let syslog_path = syslog_path().ok_or_else(|| journal_error.to_string())?;
 
     let output = Command::new("tail")
         .args(["-n", "120", syslog_path])
@@ -294,27 +426,13 @@ fn fallback_syslog(
         return Err(journal_error.to_string());
     }
 
-    let mut lines = String::from_utf8_lossy(&output.stdout)
+    let entries = String::from_utf8_lossy(&output.stdout)
         .lines()
-        .map(|line| line.to_string())
-        .collect::<Vec<String>>();
-
-    if let Some(unit) = unit {
-        lines.retain(|line| line.contains(unit));
-    }
-
-    let needle = match severity {
-        LogSeverity::Errors => Some("err"),
-        LogSeverity::Warnings => Some("warn"),
-        LogSeverity::Info => None,
-        LogSeverity::Debug => None,
-    };
-    if let Some(needle) = needle {
-        let needle = needle.to_string();
-        lines.retain(|line| line.to_lowercase().contains(&needle));
-    }
+        .filter(|line| syslog_line_matches(line, unit, severity))
+        .map(raw_entry)
+        .collect::<Vec<LogEntry>>();
 
-    Ok((format!("syslog ({syslog_path})"), lines))
+    Ok((format!("syslog ({syslog_path})"), entries))
 }
 
 // ── Service health helpers ────────────────────────────────────────────────────
@@ -361,6 +479,52 @@ pub fn service_filter_label(filter: ServiceFilter) -> &'static str {
     }
 }
 
+pub fn service_scope_label(scope: ServiceScope) -> &'static str {
+    match scope {
+        ServiceScope::System => "system",
+        ServiceScope::User => "user",
+        ServiceScope::Both => "system+user",
+    }
+}
+
+pub fn next_service_scope(scope: ServiceScope) -> ServiceScope {
+    match scope {
+        ServiceScope::System => ServiceScope::User,
+        ServiceScope::User => ServiceScope::Both,
+        ServiceScope::Both => ServiceScope::System,
+    }
+}
+
+/// Points the list at another manager. Rows from the old one are dropped at
+/// once rather than left on screen until the reload lands.
+pub fn set_service_scope(app: &mut AppState, scope: ServiceScope) {
+    app.service_scope = scope;
+    app.service_scroll = 0;
+    app.service_usage_prev.clear();
+    {
+        let mut state = app.service_state.inner.lock().unwrap();
+        state.scope = scope;
+        state.rows.clear();
+        state.last_updated_at = None;
+        if state.live {
+            // Show the watcher's system units straight away.
+            let system_rows = std::mem::take(&mut state.system_rows);
+            state.replace_system_rows(system_rows);
+        }
+    }
+    app.service_last_refresh_at = None;
+    refresh_services(app, true);
+}
+
+pub fn service_sort_label(sort: ServiceSort) -> &'static str {
+    match sort {
+        ServiceSort::Health => "health",
+        ServiceSort::Mem => "memory",
+        ServiceSort::Cpu => "CPU",
+        ServiceSort::Io => "I/O",
+    }
+}
+
 pub fn log_severity_label(sev: LogSeverity) -> &'static str {
     match sev {
         LogSeverity::Errors => "err+",
@@ -399,10 +563,12 @@ pub fn filtered_proc_rows(rows: Vec<ProcRow>, search: &str) -> Vec<ProcRow> {
 pub fn filtered_service_rows(
     rows: &[ServiceRow],
     filter: ServiceFilter,
+    sort: ServiceSort,
     search: &str,
 ) -> Vec<ServiceRow> {
     let search = search.trim().to_ascii_lowercase();
-    rows.iter()
+    let mut rows: Vec<ServiceRow> = rows
+        .iter()
         .filter(|row| {
             let filter_match = match filter {
                 ServiceFilter::Failed => row.health == ServiceHealth::Critical,
@@ -416,24 +582,43 @@ pub fn filtered_service_rows(
             filter_match && search_match
         })
         .cloned()
-        .collect()
+        .collect();
+    sort_service_view(&mut rows, sort);
+    rows
 }
 
 pub fn selected_service(app: &AppState) -> Option<ServiceRow> {
     let state = app.service_state.inner.lock().unwrap();
-    let rows = filtered_service_rows(&state.rows, app.service_filter, &app.service_search);
+    let rows = filtered_service_rows(
+        &state.rows,
+        app.service_filter,
+        app.service_sort,
+        &app.service_search,
+    );
     rows.get(app.service_scroll as usize).cloned()
 }
 
+/// Logs for the selected unit; on the timers screen, the unit the selected
+/// timer activates.
 pub fn open_logs_for_selected_service(app: &mut AppState) {
-    if let Some(row) = selected_service(app) {
-        app.log_selected_unit = Some(row.name);
-        app.logs_scroll = 0;
-        app.screen = Screen::Logs;
-        refresh_logs(app, true);
+    let unit = if matches!(app.screen, Screen::Timers) {
+        selected_timer(app).map(|row| (row.unit, false))
+    } else {
+        selected_service(app).map(|row| (row.name, row.user))
+    };
+    if let Some((unit, user)) = unit.filter(|(u, _)| !u.is_empty()) {
+        open_logs_for_unit(app, unit, user);
     }
 }
 
+pub fn open_logs_for_unit(app: &mut AppState, unit: String, user: bool) {
+    app.log_selected_unit = Some(unit);
+    app.log_selected_user = user;
+    app.logs_scroll = 0;
+    app.screen = Screen::Logs;
+    refresh_logs(app, true);
+}
+
 // ── Key input handlers ────────────────────────────────────────────────────────
 
 pub fn is_text_input_key(key: &KeyEvent) -> bool {
@@ -524,13 +709,13 @@ pub fn handle_proc_search_key(app: &mut AppState, key: &KeyEvent) -> bool {
                     app.proc_search_active = false;
                 } else {
                     app.proc_search.pop();
-                    app.proc_scroll = 0;
+                    reset_proc_selection(app);
                 }
                 true
             }
             KeyCode::Char(c) if is_text_input_key(key) => {
                 app.proc_search.push(c);
-                app.proc_scroll = 0;
+                reset_proc_selection(app);
                 true
             }
             _ => false,
@@ -548,7 +733,7 @@ pub fn handle_proc_search_key(app: &mut AppState, key: &KeyEvent) -> bool {
             }
             KeyCode::Backspace if !app.proc_search.is_empty() => {
                 app.proc_search.pop();
-                app.proc_scroll = 0;
+                reset_proc_selection(app);
                 true
             }
             _ => false,
@@ -573,6 +758,7 @@ SubState=running
 NRestarts=2
 ActiveEnterTimestamp=Thu 2026-03-12 10:00:00 UTC
 InactiveEnterTimestamp=n/a
+ControlGroup=/system.slice/sshd.service
 
 Id=apt-daily.service
 Description=Daily apt download activities
@@ -598,6 +784,7 @@ InactiveEnterTimestamp=n/a
         assert_eq!(rows.len(), 2);
         assert_eq!(rows[0].name, "sshd.service");
         assert_eq!(rows[0].last_change, "Thu 2026-03-12 10:00:00…");
+        assert_eq!(rows[0].control_group, "/system.slice/sshd.service");
         assert_eq!(rows[1].name, "apt-daily.service");
         assert_eq!(rows[1].last_change, "Thu 2026-03-12 09:00:00…");
     }
@@ -614,6 +801,9 @@ InactiveEnterTimestamp=n/a
                 restarts: 0,
                 last_change: "-".to_string(),
                 health: ServiceHealth::Healthy,
+                usage: Default::default(),
+                user: false,
+                control_group: String::new(),
             },
             ServiceRow {
                 name: "nginx.service".to_string(),
@@ -624,10 +814,13 @@ InactiveEnterTimestamp=n/a
                 restarts: 0,
                 last_change: "-".to_string(),
                 health: ServiceHealth::Healthy,
+                usage: Default::default(),
+                user: false,
+                control_group: String::new(),
             },
         ];
 
-        let rows = filtered_service_rows(&rows, ServiceFilter::All, "ssh");
+        let rows = filtered_service_rows(&rows, ServiceFilter::All, ServiceSort::Health, "ssh");
 
         assert_eq!(rows.len(), 1);
         assert_eq!(rows[0].name, "sshd.service");
@@ -0,0 +1,257 @@
+use std::ffi::OsStr;
+use std::process::Command;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::services::{collect_services, selected_service};
+use crate::types::{AppState, Screen, ServiceActionState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+    Reload,
+    Enable,
+    Disable,
+}
+
+pub const SERVICE_ACTIONS: [ServiceAction; 6] = [
+    ServiceAction::Start,
+    ServiceAction::Stop,
+    ServiceAction::Restart,
+    ServiceAction::Reload,
+    ServiceAction::Enable,
+    ServiceAction::Disable,
+];
+
+impl ServiceAction {
+    /// The systemctl verb.
+    pub fn verb(self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+            ServiceAction::Reload => "reload",
+            ServiceAction::Enable => "enable",
+            ServiceAction::Disable => "disable",
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start the unit",
+            ServiceAction::Stop => "stop the unit",
+            ServiceAction::Restart => "stop, then start",
+            ServiceAction::Reload => "reload config without restarting",
+            ServiceAction::Enable => "start at boot",
+            ServiceAction::Disable => "don't start at boot",
+        }
+    }
+}
+
+/// State of the `s` action menu on the services screen.
+#[derive(Debug, Clone)]
+pub struct ServiceActionMenu {
+    pub unit: String,
+    /// A user manager unit; commands get `--user`.
+    pub user: bool,
+    pub cursor: usize,
+    /// Waiting for y/n on the action under the cursor.
+    pub confirm: bool,
+}
+
+impl ServiceActionMenu {
+    pub fn action(&self) -> ServiceAction {
+        SERVICE_ACTIONS[self.cursor.min(SERVICE_ACTIONS.len() - 1)]
+    }
+}
+
+// ── Running actions ───────────────────────────────────────────────────────────
+
+pub fn action_command(action: ServiceAction, unit: &str, user: bool) -> String {
+    let scope = if user { "--user " } else { "" };
+    format!("systemctl {scope}{} {unit}", action.verb())
+}
+
+/// Runs `<systemctl> [--user] <verb> <unit>`; `systemctl` is a parameter so
+/// tests can substitute a stand-in. `--no-ask-password` keeps polkit from
+/// prompting on the terminal underneath the TUI; it fails with a message
+/// instead.
+pub fn run_service_action_with(
+    systemctl: &OsStr,
+    action: ServiceAction,
+    unit: &str,
+    user: bool,
+) -> Result<(), String> {
+    let command = action_command(action, unit, user);
+    let mut cmd = Command::new(systemctl);
+    if user {
+        cmd.arg("--user");
+    }
+    let output = cmd
+        .args(["--no-ask-password", action.verb(), unit])
+        .output()
+        .map_err(|e| format!("{command}: {e}"))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stderr = stderr.trim();
+    Err(if stderr.is_empty() {
+        format!("{command}: exited with {}", output.status)
+    } else {
+        format!(
+            "{command}: {}",
+            stderr.lines().collect::<Vec<_>>().join(" ")
+        )
+    })
+}
+
+/// Runs the action on a background thread (a restart can take a while),
+/// then reloads the unit list so the new state shows up right away.
+pub fn start_service_action(app: &mut AppState, action: ServiceAction, unit: String, user: bool) {
+    let command = action_command(action, &unit, user);
+    {
+        let mut st = app.service_action_state.inner.lock().unwrap();
+        if st.running.is_some() {
+            return;
+        }
+        st.running = Some(command.clone());
+        st.result = None;
+    }
+    let actions = app.service_action_state.inner.clone();
+    let services = app.service_state.inner.clone();
+    let scope = app.service_scope;
+    std::thread::spawn(move || {
+        let result = run_service_action_with(OsStr::new("systemctl"), action, &unit, user)
+            .map(|()| format!("{command}: done"));
+        {
+            let mut st = actions.lock().unwrap();
+            st.running = None;
+            st.result = Some((result, Instant::now()));
+        }
+        if let Ok(rows) = collect_services(scope) {
+            let mut state = services.lock().unwrap();
+            if state.scope == scope {
+                state.replace_rows(rows);
+            }
+        }
+    });
+}
+
+/// Status line for the detail pane: the in-flight command, or the last
+/// outcome for a little while after it finished.
+pub fn service_action_status(state: &ServiceActionState) -> Option<Result<String, String>> {
+    let st = state.inner.lock().unwrap();
+    if let Some(cmd) = &st.running {
+        return Some(Ok(format!("running {cmd}…")));
+    }
+    match &st.result {
+        Some((result, at)) if at.elapsed().as_secs() < 15 => Some(result.clone()),
+        _ => None,
+    }
+}
+
+// ── Key handling ──────────────────────────────────────────────────────────────
+
+/// `s` opens the menu for the selected unit; while open it takes every key.
+pub fn handle_service_action_key(app: &mut AppState, key: &KeyEvent) -> bool {
+    if !matches!(app.screen, Screen::Services) || app.service_search_active {
+        return false;
+    }
+    let Some(menu) = app.service_action.as_mut() else {
+        if key.code == KeyCode::Char('s') {
+            // The detail view's unit, or the list selection.
+            let unit = match &app.service_detail_unit {
+                Some(unit) => Some((unit.clone(), app.service_detail_user)),
+                None => selected_service(app).map(|row| (row.name, row.user)),
+            };
+            if let Some((unit, user)) = unit {
+                app.service_action = Some(ServiceActionMenu {
+                    unit,
+                    user,
+                    cursor: 0,
+                    confirm: false,
+                });
+            }
+            return true;
+        }
+        return false;
+    };
+    if menu.confirm {
+        match key.code {
+            KeyCode::Char('y') => {
+                let (action, unit, user) = (menu.action(), menu.unit.clone(), menu.user);
+                app.service_action = None;
+                start_service_action(app, action, unit, user);
+            }
+            KeyCode::Char('n') | KeyCode::Esc => menu.confirm = false,
+            _ => {}
+        }
+        return true;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.service_action = None,
+        KeyCode::Up => menu.cursor = menu.cursor.saturating_sub(1),
+        KeyCode::Down => menu.cursor = (menu.cursor + 1).min(SERVICE_ACTIONS.len() - 1),
+        KeyCode::Enter => menu.confirm = true,
+        _ => {}
+    }
+    true
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_systemctl_and_surfaces_stderr() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("ferro-systemctl-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let fake = dir.join("systemctl");
+        let log = dir.join("args");
+        std::fs::write(
+            &fake,
+            format!(
+                "#!/bin/sh\necho \"$@\" >> '{}'\n\
+                 [ \"$3\" = locked.service ] && {{ echo 'Failed to stop locked.service: Access denied' >&2; exit 1; }}\n\
+                 exit 0\n",
+                log.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        run_service_action_with(
+            fake.as_os_str(),
+            ServiceAction::Restart,
+            "nginx.service",
+            false,
+        )
+        .unwrap();
+        let err = run_service_action_with(
+            fake.as_os_str(),
+            ServiceAction::Stop,
+            "locked.service",
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            "systemctl stop locked.service: Failed to stop locked.service: Access denied"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            "--no-ask-password restart nginx.service\n--no-ask-password stop locked.service\n"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
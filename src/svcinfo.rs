@@ -0,0 +1,283 @@
+use std::process::Command;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::journal::format_log_line;
+use crate::services::{collect_logs, open_logs_for_unit, selected_service};
+use crate::types::{AppState, LogSeverity, Screen};
+
+/// Journal lines shown at the bottom of the detail view.
+const JOURNAL_LINES: usize = 8;
+/// `list-dependencies` output can run to hundreds of units for a service
+/// that pulls in sysinit.target; the rest is one `systemctl` away.
+const DEPENDENCY_LINES: usize = 40;
+
+const PROPERTIES: &str = "Id,Description,ExecStart,MainPID,FragmentPath,UnitFileState,\
+    Restart,RestartUSec,MemoryMax,MemoryHigh,TasksMax,After,Requires,Wants,WantedBy,RequiredBy";
+
+// ── Detail model ──────────────────────────────────────────────────────────────
+
+/// What the detail view shows for one unit. Sections that come from a
+/// separate command carry `Err` with the reason rather than looking empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceDetail {
+    pub unit: String,
+    pub description: String,
+    /// One command line per `ExecStart=` entry.
+    pub exec_start: Vec<String>,
+    pub main_pid: Option<u32>,
+    pub fragment_path: String,
+    pub unit_file_state: String,
+    pub restart: String,
+    pub restart_sec: String,
+    pub memory_max: String,
+    pub memory_high: String,
+    pub tasks_max: String,
+    pub after: Vec<String>,
+    pub requires: Vec<String>,
+    pub wants: Vec<String>,
+    pub wanted_by: Vec<String>,
+    pub required_by: Vec<String>,
+    pub dependencies: Result<Vec<String>, String>,
+    pub reverse_dependencies: Result<Vec<String>, String>,
+    pub journal: Result<Vec<String>, String>,
+}
+
+// ── systemctl parsing ─────────────────────────────────────────────────────────
+
+/// The `argv[]=` part of each `ExecStart=` record, e.g.
+/// `{ path=/usr/sbin/nginx ; argv[]=/usr/sbin/nginx -g daemon on; ; ignore_errors=no ; … }`.
+pub fn parse_exec_start(value: &str) -> Vec<String> {
+    value
+        .split("argv[]=")
+        .skip(1)
+        .map(|rest| rest.split(" ; ").next().unwrap_or(rest).trim().to_string())
+        .filter(|cmd| !cmd.is_empty())
+        .collect()
+}
+
+/// Fields of `systemctl show <unit> --property=…`; the dependency and
+/// journal sections are filled in separately.
+pub fn parse_unit_properties(stdout: &str) -> ServiceDetail {
+    let mut d = ServiceDetail {
+        unit: String::new(),
+        description: String::new(),
+        exec_start: Vec::new(),
+        main_pid: None,
+        fragment_path: String::new(),
+        unit_file_state: String::new(),
+        restart: String::new(),
+        restart_sec: String::new(),
+        memory_max: String::new(),
+        memory_high: String::new(),
+        tasks_max: String::new(),
+        after: Vec::new(),
+        requires: Vec::new(),
+        wants: Vec::new(),
+        wanted_by: Vec::new(),
+        required_by: Vec::new(),
+        dependencies: Ok(Vec::new()),
+        reverse_dependencies: Ok(Vec::new()),
+        journal: Ok(Vec::new()),
+    };
+    let list = |v: &str| v.split_whitespace().map(str::to_string).collect();
+    for line in stdout.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key {
+            "Id" => d.unit = value.to_string(),
+            "Description" => d.description = value.to_string(),
+            "ExecStart" => d.exec_start = parse_exec_start(value),
+            "MainPID" => d.main_pid = value.parse().ok().filter(|&p| p != 0),
+            "FragmentPath" => d.fragment_path = value.to_string(),
+            "UnitFileState" => d.unit_file_state = value.to_string(),
+            "Restart" => d.restart = value.to_string(),
+            "RestartUSec" => d.restart_sec = value.to_string(),
+            "MemoryMax" => d.memory_max = value.to_string(),
+            "MemoryHigh" => d.memory_high = value.to_string(),
+            "TasksMax" => d.tasks_max = value.to_string(),
+            "After" => d.after = list(value),
+            "Requires" => d.requires = list(value),
+            "Wants" => d.wants = list(value),
+            "WantedBy" => d.wanted_by = list(value),
+            "RequiredBy" => d.required_by = list(value),
+            _ => {}
+        }
+    }
+    d
+}
+
+fn systemctl(args: &[&str], user: bool) -> Result<String, String> {
+    let mut cmd = Command::new("systemctl");
+    if user {
+        cmd.arg("--user");
+    }
+    let output = cmd
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run systemctl: {e}"))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(if stderr.is_empty() {
+        format!("systemctl exited with {}", output.status)
+    } else {
+        stderr
+    })
+}
+
+/// `list-dependencies --plain` output minus the unit itself on the first
+/// line; indentation is kept, it is the tree.
+fn list_dependencies(unit: &str, user: bool, reverse: bool) -> Result<Vec<String>, String> {
+    let mut args = vec!["list-dependencies", "--plain", "--no-pager"];
+    if reverse {
+        args.push("--reverse");
+    }
+    args.push(unit);
+    let stdout = systemctl(&args, user)?;
+    let mut lines: Vec<String> = stdout
+        .lines()
+        .skip(1)
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.trim_end().to_string())
+        .collect();
+    if lines.len() > DEPENDENCY_LINES {
+        let more = lines.len() - DEPENDENCY_LINES;
+        lines.truncate(DEPENDENCY_LINES);
+        lines.push(format!("  … {more} more"));
+    }
+    Ok(lines)
+}
+
+/// Loads everything for `unit` from the system or user manager; errors only
+/// if systemd doesn't know it.
+pub fn read_service_detail(unit: &str, user: bool) -> Result<ServiceDetail, String> {
+    let props = format!("--property={PROPERTIES}");
+    let mut d = parse_unit_properties(&systemctl(&["show", "--no-pager", &props, unit], user)?);
+    if d.unit.is_empty() {
+        return Err(format!("{unit}: no such unit"));
+    }
+    d.dependencies = list_dependencies(unit, user, false);
+    d.reverse_dependencies = list_dependencies(unit, user, true);
+    d.journal = collect_logs(Some(unit), user, LogSeverity::Debug, None).map(|(_, lines)| {
+        let skip = lines.len().saturating_sub(JOURNAL_LINES);
+        lines.iter().skip(skip).map(format_log_line).collect()
+    });
+    Ok(d)
+}
+
+// ── App glue ──────────────────────────────────────────────────────────────────
+
+/// Reads the detail on a background thread; the previous one (if any) stays
+/// on screen until it lands.
+fn load_service_detail(app: &AppState, unit: String, user: bool) {
+    let key = (unit, user);
+    {
+        let mut state = app.service_detail_state.inner.lock().unwrap();
+        if state.running && state.unit.as_ref() == Some(&key) {
+            return;
+        }
+        state.running = true;
+        state.unit = Some(key.clone());
+    }
+
+    let inner = app.service_detail_state.inner.clone();
+    std::thread::spawn(move || {
+        let detail = read_service_detail(&key.0, key.1);
+        let mut state = inner.lock().unwrap();
+        if state.unit.as_ref() != Some(&key) {
+            // Closed, or another unit opened, while this was loading.
+            return;
+        }
+        state.running = false;
+        state.detail = Some(detail);
+    });
+}
+
+pub fn open_service_detail(app: &mut AppState, unit: String, user: bool) {
+    app.service_detail_state.inner.lock().unwrap().detail = None;
+    load_service_detail(app, unit.clone(), user);
+    app.service_detail_unit = Some(unit);
+    app.service_detail_user = user;
+    app.service_detail_scroll = 0;
+}
+
+fn close_service_detail(app: &mut AppState) {
+    app.service_detail_unit = None;
+    let mut state = app.service_detail_state.inner.lock().unwrap();
+    state.running = false;
+    state.unit = None;
+    state.detail = None;
+}
+
+/// `i` opens the detail view for the selected unit. While open it takes the
+/// list's keys so they don't act on an invisible cursor.
+pub fn handle_service_detail_key(app: &mut AppState, key: &KeyEvent) -> bool {
+    if !matches!(app.screen, Screen::Services) || app.service_search_active {
+        return false;
+    }
+    let Some(unit) = app.service_detail_unit.clone() else {
+        if key.code == KeyCode::Char('i') {
+            if let Some(row) = selected_service(app) {
+                open_service_detail(app, row.name, row.user);
+            }
+            return true;
+        }
+        return false;
+    };
+    let user = app.service_detail_user;
+    match key.code {
+        KeyCode::Esc | KeyCode::Left | KeyCode::Backspace => close_service_detail(app),
+        KeyCode::Up => app.service_detail_scroll = app.service_detail_scroll.saturating_sub(1),
+        KeyCode::Down => app.service_detail_scroll = app.service_detail_scroll.saturating_add(1),
+        KeyCode::PageUp => app.service_detail_scroll = app.service_detail_scroll.saturating_sub(10),
+        KeyCode::PageDown => {
+            app.service_detail_scroll = app.service_detail_scroll.saturating_add(10)
+        }
+        KeyCode::Home => app.service_detail_scroll = 0,
+        KeyCode::Char('r') => load_service_detail(app, unit, user),
+        KeyCode::Char('l') | KeyCode::Enter => {
+            close_service_detail(app);
+            open_logs_for_unit(app, unit, user);
+        }
+        KeyCode::Char('/' | 'o' | 'i' | 'u') | KeyCode::Tab => {}
+        _ => return false,
+    }
+    true
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unit_properties() {
+        let stdout = "Id=nginx.service\n\
+Description=A high performance web server\n\
+ExecStart={ path=/usr/sbin/nginx ; argv[]=/usr/sbin/nginx -g daemon on; master_process on; ; ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }\n\
+MainPID=0\n\
+FragmentPath=/lib/systemd/system/nginx.service\n\
+UnitFileState=enabled\n\
+Restart=on-failure\n\
+RestartUSec=5s\n\
+MemoryMax=infinity\n\
+TasksMax=4915\n\
+After=network-online.target sysinit.target\n\
+WantedBy=multi-user.target\n";
+        let d = parse_unit_properties(stdout);
+        assert_eq!(d.unit, "nginx.service");
+        assert_eq!(
+            d.exec_start,
+            ["/usr/sbin/nginx -g daemon on; master_process on;"]
+        );
+        assert_eq!(d.main_pid, None);
+        assert_eq!(d.restart, "on-failure");
+        assert_eq!(d.after, ["network-online.target", "sysinit.target"]);
+        assert_eq!(d.wanted_by, ["multi-user.target"]);
+        assert!(d.requires.is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::types::{AppState, ServiceRow, ServiceSort, ServiceUsage};
+
+/// Cumulative cgroup counters kept between samples to turn into rates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CgroupCounters {
+    pub cpu_usec: Option<u64>,
+    pub io_bytes: Option<u64>,
+}
+
+/// The unit's own `ControlGroup` path, so template instances
+/// (`system-getty.slice/getty@tty1.service`), `Slice=` overrides and user
+/// units in `session.slice` or `background.slice` are all found.
+fn cgroup_dir(control_group: &str) -> PathBuf {
+    PathBuf::from("/sys/fs/cgroup").join(control_group.trim_start_matches('/'))
+}
+
+/// Only running units have processes to account; with `--all` most of the
+/// list is inactive and would cost four failed reads each.
+fn is_sampled(row: &ServiceRow) -> bool {
+    matches!(row.active_state.as_str(), "active" | "reloading") && !row.control_group.is_empty()
+}
+
+/// `usage_usec` from `cpu.stat`.
+pub fn parse_cpu_usage_usec(cpu_stat: &str) -> Option<u64> {
+    cpu_stat.lines().find_map(|line| {
+        line.strip_prefix("usage_usec ")
+            .and_then(|v| v.trim().parse().ok())
+    })
+}
+
+/// Read + written bytes summed over every device in `io.stat`, e.g.
+/// `8:0 rbytes=1024 wbytes=2048 rios=1 wios=2 dbytes=0 dios=0`.
+pub fn parse_io_stat_bytes(io_stat: &str) -> u64 {
+    io_stat
+        .split_whitespace()
+        .filter_map(|kv| {
+            let (key, value) = kv.split_once('=')?;
+            matches!(key, "rbytes" | "wbytes")
+                .then(|| value.parse::<u64>().ok())
+                .flatten()
+        })
+        .sum()
+}
+
+fn read_u64(path: PathBuf) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn read_cgroup(control_group: &str) -> (ServiceUsage, CgroupCounters) {
+    let dir = cgroup_dir(control_group);
+    let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+    let usage = ServiceUsage {
+        mem_bytes: read_u64(dir.join("memory.current")),
+        tasks: read_u64(dir.join("pids.current")),
+        ..ServiceUsage::default()
+    };
+    let counters = CgroupCounters {
+        cpu_usec: read("cpu.stat").and_then(|s| parse_cpu_usage_usec(&s)),
+        io_bytes: read("io.stat").map(|s| parse_io_stat_bytes(&s)),
+    };
+    (usage, counters)
+}
+
+/// CPU% (×10) and I/O bytes/s from two samples `elapsed_secs` apart.
+pub fn usage_rates(
+    prev: CgroupCounters,
+    now: CgroupCounters,
+    elapsed_secs: f64,
+) -> (Option<u32>, Option<u64>) {
+    if elapsed_secs <= 0.0 {
+        return (None, None);
+    }
+    let cpu = match (prev.cpu_usec, now.cpu_usec) {
+        (Some(a), Some(b)) => {
+            Some((b.saturating_sub(a) as f64 / (elapsed_secs * 1_000_000.0) * 1000.0) as u32)
+        }
+        _ => None,
+    };
+    let io = match (prev.io_bytes, now.io_bytes) {
+        (Some(a), Some(b)) => Some((b.saturating_sub(a) as f64 / elapsed_secs) as u64),
+        _ => None,
+    };
+    (cpu, io)
+}
+
+/// Reads each `(unit key, cgroup)`; rates need a previous sample, so they
+/// stay empty on the first call for a unit.
+pub fn sample_service_usage(
+    targets: Vec<((bool, String), String)>,
+    prev: &mut HashMap<(bool, String), CgroupCounters>,
+    elapsed_secs: f64,
+) -> HashMap<(bool, String), ServiceUsage> {
+    let mut current = HashMap::with_capacity(targets.len());
+    let mut samples = HashMap::with_capacity(targets.len());
+    for (key, control_group) in targets {
+        let (mut usage, counters) = read_cgroup(&control_group);
+        if let Some(before) = prev.get(&key) {
+            (usage.cpu_x10, usage.io_bps) = usage_rates(*before, counters, elapsed_secs);
+        }
+        current.insert(key.clone(), counters);
+        samples.insert(key, usage);
+    }
+    *prev = current;
+    samples
+}
+
+/// Samples usage for the running units currently listed; called every tick
+/// while the services screen is open. sysfs is read without holding the
+/// list's lock.
+pub fn refresh_service_usage(app: &mut AppState) {
+    let elapsed = app
+        .service_usage_at
+        .map(|t| t.elapsed().as_secs_f64())
+        .unwrap_or(0.0);
+    app.service_usage_at = Some(Instant::now());
+    let targets: Vec<_> = {
+        let state = app.service_state.inner.lock().unwrap();
+        state
+            .rows
+            .iter()
+            .filter(|row| is_sampled(row))
+            .map(|row| ((row.user, row.name.clone()), row.control_group.clone()))
+            .collect()
+    };
+    let mut samples = sample_service_usage(targets, &mut app.service_usage_prev, elapsed);
+    let mut state = app.service_state.inner.lock().unwrap();
+    for row in state.rows.iter_mut() {
+        row.usage = samples
+            .remove(&(row.user, row.name.clone()))
+            .unwrap_or_default();
+    }
+}
+
+pub fn next_service_sort(sort: ServiceSort) -> ServiceSort {
+    match sort {
+        ServiceSort::Health => ServiceSort::Mem,
+        ServiceSort::Mem => ServiceSort::Cpu,
+        ServiceSort::Cpu => ServiceSort::Io,
+        ServiceSort::Io => ServiceSort::Health,
+    }
+}
+
+/// Re-orders an already health-sorted list; `Health` keeps it as is.
+pub fn sort_service_view(rows: &mut [ServiceRow], sort: ServiceSort) {
+    match sort {
+        ServiceSort::Health => {}
+        ServiceSort::Mem => rows.sort_by_key(|r| Reverse(r.usage.mem_bytes)),
+        ServiceSort::Cpu => rows.sort_by_key(|r| Reverse(r.usage.cpu_x10)),
+        ServiceSort::Io => rows.sort_by_key(|r| Reverse(r.usage.io_bps)),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cgroup_stats_into_rates() {
+        let cpu_stat = "usage_usec 5000000\nuser_usec 3000000\nsystem_usec 2000000\n";
+        assert_eq!(parse_cpu_usage_usec(cpu_stat), Some(5_000_000));
+        let io_stat = "8:0 rbytes=1024 wbytes=2048 rios=1 wios=2 dbytes=0 dios=0\n\
+                       259:0 rbytes=100 wbytes=0 rios=3 wios=0 dbytes=0 dios=0\n";
+        assert_eq!(parse_io_stat_bytes(io_stat), 3172);
+
+        // Half a CPU and 1 MiB/s over two seconds.
+        let prev = CgroupCounters {
+            cpu_usec: Some(5_000_000),
+            io_bytes: Some(0),
+        };
+        let now = CgroupCounters {
+            cpu_usec: Some(6_000_000),
+            io_bytes: Some(2 << 20),
+        };
+        assert_eq!(usage_rates(prev, now, 2.0), (Some(500), Some(1 << 20)));
+        let stopped = CgroupCounters::default();
+        assert_eq!(usage_rates(prev, stopped, 2.0), (None, None));
+
+        // Template instances live in their own slice.
+        assert_eq!(
+            cgroup_dir("/system.slice/system-getty.slice/getty@tty1.service"),
+            PathBuf::from("/sys/fs/cgroup/system.slice/system-getty.slice/getty@tty1.service")
+        );
+    }
+}
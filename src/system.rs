@@ -2,14 +2,28 @@ use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use sysinfo::{Disks, System};
-
-use crate::types::{AppState, DashDirTarget, DiskRow, ProcRow, ProcSort, VmSnapshot};
+use sysinfo::{Disks, ProcessRefreshKind, RefreshKind, System};
+
+use crate::alerts::AlertStatus;
+use crate::config::config;
+use crate::types::{
+    AppState, CpuBreakdown, CpuTimes, DashDirTarget, DiskRow, ProcRow, ProcSort, Psi, PsiAverages,
+    PsiResource, VmSnapshot,
+};
 use crate::update::VERSION;
 use crate::utils::{format_bytes, percent, trim_to};
 
 // ── Core sysinfo refresh ──────────────────────────────────────────────────────
 
+pub fn new_system() -> System {
+    // Keep dashboard refresh light, but allow process refresh when needed.
+    let refresh_kind = RefreshKind::new()
+        .with_cpu(sysinfo::CpuRefreshKind::everything())
+        .with_memory(sysinfo::MemoryRefreshKind::everything())
+        .with_processes(ProcessRefreshKind::everything());
+    System::new_with_specifics(refresh_kind)
+}
+
 pub fn refresh(system: &mut System, disks: &mut Disks, refresh_processes: bool) {
     system.refresh_cpu();
     system.refresh_memory();
@@ -21,9 +35,10 @@ pub fn refresh(system: &mut System, disks: &mut Disks, refresh_processes: bool)
 
 // ── VM snapshot ───────────────────────────────────────────────────────────────
 
-pub fn snapshot(system: &System) -> VmSnapshot {
+pub fn snapshot(system: &System, cpu_breakdown: Option<CpuBreakdown>, psi: Psi) -> VmSnapshot {
     let cpu_usage = system.global_cpu_info().cpu_usage();
     let cpu_cores = system.cpus().len();
+    let core_usage = system.cpus().iter().map(|c| c.cpu_usage()).collect();
     let load_avg = System::load_average();
     let load_avg_one = load_avg.one;
     let load_avg_five = load_avg.five;
@@ -40,6 +55,9 @@ pub fn snapshot(system: &System) -> VmSnapshot {
     VmSnapshot {
         cpu_usage,
         cpu_cores,
+        core_usage,
+        cpu_breakdown,
+        psi,
         load_avg_one,
         load_avg_five,
         load_avg_fifteen,
@@ -53,26 +71,228 @@ pub fn snapshot(system: &System) -> VmSnapshot {
     }
 }
 
-// ── Process helpers ───────────────────────────────────────────────────────────
+// ── CPU time breakdown (/proc/stat) ───────────────────────────────────────────
 
-pub fn format_top_processes(system: &System, sort: ProcSort, count: usize) -> Vec<String> {
-    use std::cmp::Reverse;
+pub fn parse_proc_stat_cpu(content: &str) -> Option<CpuTimes> {
+    let line = content.lines().find(|l| l.starts_with("cpu "))?;
+    let f: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .map(|v| v.parse::<u64>().unwrap_or(0))
+        .collect();
+    if f.len() < 4 {
+        return None;
+    }
+    let at = |i: usize| f.get(i).copied().unwrap_or(0);
+    Some(CpuTimes {
+        user: at(0),
+        nice: at(1),
+        system: at(2),
+        idle: at(3),
+        iowait: at(4),
+        irq: at(5),
+        softirq: at(6),
+        steal: at(7),
+    })
+}
+
+pub fn cpu_breakdown_between(prev: &CpuTimes, cur: &CpuTimes) -> Option<CpuBreakdown> {
+    let d = |c: u64, p: u64| c.saturating_sub(p) as f64;
+    let deltas = [
+        d(cur.user, prev.user),
+        d(cur.nice, prev.nice),
+        d(cur.system, prev.system),
+        d(cur.idle, prev.idle),
+        d(cur.iowait, prev.iowait),
+        d(cur.irq, prev.irq),
+        d(cur.softirq, prev.softirq),
+        d(cur.steal, prev.steal),
+    ];
+    let total: f64 = deltas.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let pct = |v: f64| (v / total * 100.0) as f32;
+    Some(CpuBreakdown {
+        user: pct(deltas[0]),
+        nice: pct(deltas[1]),
+        system: pct(deltas[2]),
+        idle: pct(deltas[3]),
+        iowait: pct(deltas[4]),
+        irq: pct(deltas[5]),
+        softirq: pct(deltas[6]),
+        steal: pct(deltas[7]),
+    })
+}
+
+pub fn update_cpu_breakdown(breakdown: &mut Option<CpuBreakdown>, prev: &mut Option<CpuTimes>) {
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (breakdown, prev);
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let Some(cur) = std::fs::read_to_string("/proc/stat")
+            .ok()
+            .and_then(|c| parse_proc_stat_cpu(&c))
+        else {
+            return;
+        };
+        if let Some(p) = prev.as_ref() {
+            // Keep the last split if the tick was too short to accumulate jiffies.
+            if let Some(b) = cpu_breakdown_between(p, &cur) {
+                *breakdown = Some(b);
+            }
+        }
+        *prev = Some(cur);
+    }
+}
+
+pub fn format_cpu_breakdown(b: &CpuBreakdown) -> Vec<String> {
+    vec![
+        format!(
+            "usr {:.0}%  sys {:.0}%  nice {:.0}%",
+            b.user, b.system, b.nice
+        ),
+        format!("iowait {:.0}%  steal {:.0}%", b.iowait, b.steal),
+        format!("irq {:.0}%  softirq {:.0}%", b.irq, b.softirq),
+    ]
+}
 
-    let mut procs: Vec<ProcRow> = system
+// ── Pressure stall information (/proc/pressure) ──────────────────────────────
+
+pub fn parse_psi(content: &str) -> Option<PsiResource> {
+    let mut some = None;
+    let mut full = None;
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let kind = parts.next();
+        let mut avgs = PsiAverages::default();
+        for part in parts {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            let value = value.parse::<f32>().unwrap_or(0.0);
+            match key {
+                "avg10" => avgs.avg10 = value,
+                "avg60" => avgs.avg60 = value,
+                "avg300" => avgs.avg300 = value,
+                _ => {}
+            }
+        }
+        match kind {
+            Some("some") => some = Some(avgs),
+            Some("full") => full = Some(avgs),
+            _ => {}
+        }
+    }
+    Some(PsiResource { some: some?, full })
+}
+
+pub fn read_psi() -> Psi {
+    #[cfg(not(target_os = "linux"))]
+    {
+        Psi::default()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // Missing files (old kernel, psi=0 boot flag) simply leave the resource unset.
+        let read = |name: &str| {
+            std::fs::read_to_string(format!("/proc/pressure/{name}"))
+                .ok()
+                .and_then(|c| parse_psi(&c))
+        };
+        Psi {
+            cpu: read("cpu"),
+            memory: read("memory"),
+            io: read("io"),
+        }
+    }
+}
+
+pub fn format_psi_line(label: &str, res: &PsiResource, peak: u16) -> String {
+    format!(
+        "PSI {label} {:.1}/{:.1}/{:.1} pk {peak}%",
+        res.some.avg10, res.some.avg60, res.some.avg300
+    )
+}
+
+// ── Process helpers ───────────────────────────────────────────────────────────
+
+pub fn collect_proc_rows(system: &System) -> Vec<ProcRow> {
+    system
         .processes()
         .iter()
         .map(|(pid, p)| ProcRow::from_process(*pid, p))
-        .collect();
+        .collect()
+}
+
+pub fn sort_proc_rows(procs: &mut [ProcRow], sort: ProcSort) {
+    use std::cmp::Reverse;
 
     match sort {
         ProcSort::Cpu => procs.sort_by_key(|p| Reverse((p.cpu_x10 as i64, p.mem_bytes as i64))),
         ProcSort::Mem => procs.sort_by_key(|p| Reverse((p.mem_bytes as i64, p.cpu_x10 as i64))),
-        ProcSort::Swap => procs.sort_by_key(|p| Reverse((p.mem_bytes as i64, p.cpu_x10 as i64))),
+        ProcSort::Swap => procs.sort_by_key(|p| Reverse((p.swap_bytes as i64, p.mem_bytes as i64))),
+        ProcSort::Io => procs.sort_by_key(|p| Reverse((p.io_bps(), p.cpu_x10 as i64))),
     }
+}
 
+/// `read_bytes` / `write_bytes` from `/proc/<pid>/io`: bytes that actually hit
+/// the storage layer, unlike `rchar`/`wchar` which include page-cache hits.
+pub fn parse_proc_io(content: &str) -> Option<(u64, u64)> {
+    let field = |key: &str| {
+        content
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.trim().parse::<u64>().ok())
+    };
+    Some((field("read_bytes:")?, field("write_bytes:")?))
+}
+
+/// Fills `read_bps`/`write_bps` from `/proc/<pid>/io` deltas. Other users'
+/// processes are unreadable without root and stay at 0.
+pub fn update_proc_io_rates(
+    rows: &mut [ProcRow],
+    prev_stats: &mut HashMap<i32, (u64, u64)>,
+    elapsed_secs: f64,
+) {
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (rows, prev_stats, elapsed_secs);
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let mut current = HashMap::with_capacity(rows.len());
+        // Threads share their process's counters; reading them would double-count.
+        for row in rows.iter_mut().filter(|r| !r.is_thread) {
+            let Some((read, write)) = std::fs::read_to_string(format!("/proc/{}/io", row.pid))
+                .ok()
+                .and_then(|c| parse_proc_io(&c))
+            else {
+                continue;
+            };
+            if let Some(&(pr, pw)) = prev_stats.get(&row.pid) {
+                if elapsed_secs > 0.0 {
+                    row.read_bps = (read.saturating_sub(pr) as f64 / elapsed_secs) as u64;
+                    row.write_bps = (write.saturating_sub(pw) as f64 / elapsed_secs) as u64;
+                }
+            }
+            current.insert(row.pid, (read, write));
+        }
+        *prev_stats = current;
+    }
+}
+
+pub fn top_process_rows(procs: &[ProcRow], sort: ProcSort, count: usize) -> Vec<ProcRow> {
+    let mut procs = procs.to_vec();
+    sort_proc_rows(&mut procs, sort);
+    procs.truncate(count);
     procs
+}
+
+pub fn format_top_processes(procs: &[ProcRow], sort: ProcSort, count: usize) -> Vec<String> {
+    top_process_rows(procs, sort, count)
         .into_iter()
-        .take(count)
         .map(|p| {
             let cpu = format!("{:.1}%", p.cpu_x10 as f64 / 10.0);
             let mem = format_bytes(p.mem_bytes);
@@ -82,28 +302,18 @@ pub fn format_top_processes(system: &System, sort: ProcSort, count: usize) -> Ve
         .collect()
 }
 
-pub fn format_memory_pressure(system: &System, top_n: usize) -> Vec<String> {
-    use std::cmp::Reverse;
-
-    let total_memory = system.total_memory();
-    let available_memory = system.available_memory();
-    let total_swap = system.total_swap();
-    let used_swap = system.used_swap();
-    let available_pct = percent(available_memory, total_memory);
-    let swap_pct = percent(used_swap, total_swap);
-
-    let mut procs: Vec<ProcRow> = system
-        .processes()
-        .iter()
-        .map(|(pid, p)| ProcRow::from_process(*pid, p))
-        .collect();
-    procs.sort_by_key(|p| Reverse((p.mem_bytes as i64, p.cpu_x10 as i64)));
-    let top_total: u64 = procs.into_iter().take(top_n).map(|p| p.mem_bytes).sum();
+pub fn format_memory_pressure(vm: &VmSnapshot, procs: &[ProcRow], top_n: usize) -> Vec<String> {
+    let available_pct = percent(vm.available_memory, vm.total_memory);
+    let swap_pct = percent(vm.used_swap, vm.total_swap);
+    let top_total: u64 = top_process_rows(procs, ProcSort::Mem, top_n)
+        .into_iter()
+        .map(|p| p.mem_bytes)
+        .sum();
 
     vec![
         format!("Top {top_n} using {}", format_bytes(top_total)),
         format!("Avail {:.0}% of RAM", available_pct),
-        if total_swap > 0 {
+        if vm.total_swap > 0 {
             format!("Swap {:.0}% in use", swap_pct)
         } else {
             "Swap off".to_string()
@@ -111,6 +321,48 @@ pub fn format_memory_pressure(system: &System, top_n: usize) -> Vec<String> {
     ]
 }
 
+// ── Dashboard cache ───────────────────────────────────────────────────────────
+
+/// Process-derived dashboard lists; shared by the live loop and replay.
+pub fn fill_dashboard_process_cache(app: &mut AppState, vm: &VmSnapshot, procs: &[ProcRow]) {
+    app.dash_top_cpu = format_top_processes(procs, ProcSort::Cpu, 20);
+    app.dash_top_mem = format_top_processes(procs, ProcSort::Mem, 20);
+    // The process-based heuristic is only a stand-in for kernels without PSI.
+    app.dash_mem_pressure = if vm.psi.available() {
+        Vec::new()
+    } else {
+        format_memory_pressure(vm, procs, 5)
+    };
+}
+
+pub fn refresh_dashboard_cache(
+    app: &mut AppState,
+    vm: &VmSnapshot,
+    system: &System,
+    disks: &Disks,
+) {
+    // Keep this screen cheap: do tiny scans occasionally, not every frame.
+    let need_fs = match app.dash_last_fs_at {
+        Some(t) => t.elapsed() >= std::time::Duration::from_secs(5),
+        None => true,
+    };
+    if !need_fs {
+        return;
+    }
+
+    fill_dashboard_process_cache(app, vm, &collect_proc_rows(system));
+    app.dash_mount_rows = collect_mount_rows(12, app.dash_show_all_mounts)
+        .unwrap_or_else(|| disks_table_filtered(disks, 12, app.dash_show_all_mounts));
+    let (label, path) = dash_target_path(app.dash_dir_target);
+    app.dash_dir_sizes = scan_dir_quick(&path, 6);
+    if !app.dash_dir_sizes.is_empty() {
+        app.dash_dir_sizes.insert(0, label);
+    } else {
+        app.dash_dir_sizes = vec![label, "(no entries)".to_string()];
+    }
+    app.dash_last_fs_at = Some(std::time::Instant::now());
+}
+
 // ── Uptime ────────────────────────────────────────────────────────────────────
 
 pub fn format_uptime(secs: u64) -> String {
@@ -193,6 +445,7 @@ pub fn parse_df_row(line: &str) -> Option<DiskRow> {
 }
 
 pub fn should_hide_mount_row(row: &DiskRow) -> bool {
+    let rules = &config().mounts;
     let fs_l = row.fs.to_lowercase();
     if fs_l.contains("tmpfs")
         || fs_l.contains("udev")
@@ -202,10 +455,18 @@ pub fn should_hide_mount_row(row: &DiskRow) -> bool {
     {
         return true;
     }
+    if rules
+        .hide_fs
+        .iter()
+        .any(|pat| fs_l.contains(&pat.to_lowercase()))
+    {
+        return true;
+    }
 
-    if row.mount.starts_with("/run")
-        || row.mount.starts_with("/dev")
-        || row.mount.starts_with("/sys")
+    if rules
+        .hide_mount_prefixes
+        .iter()
+        .any(|prefix| row.mount.starts_with(prefix.as_str()))
     {
         return true;
     }
@@ -220,7 +481,7 @@ pub fn should_hide_mount_row(row: &DiskRow) -> bool {
         }
     }
 
-    row.size == 0
+    rules.hide_empty && row.size == 0
 }
 
 pub fn disks_table_filtered(disks: &Disks, limit: usize, show_all: bool) -> Vec<DiskRow> {
@@ -382,6 +643,34 @@ pub fn format_snapshot(vm: &VmSnapshot, app: &AppState, system: &System, disks:
     out.push("".to_string());
 
     out.push(format!("CPU: {:.1}% cores={}", vm.cpu_usage, vm.cpu_cores));
+    if let Some(b) = &vm.cpu_breakdown {
+        out.push(format!("  {}", format_cpu_breakdown(b).join("  ")));
+    }
+    for (label, res) in [
+        ("cpu", vm.psi.cpu),
+        ("memory", vm.psi.memory),
+        ("io", vm.psi.io),
+    ] {
+        if let Some(res) = res {
+            let full = res
+                .full
+                .map(|f| format!(" full {:.2}/{:.2}/{:.2}", f.avg10, f.avg60, f.avg300))
+                .unwrap_or_default();
+            out.push(format!(
+                "PSI {label}: some {:.2}/{:.2}/{:.2}{full}",
+                res.some.avg10, res.some.avg60, res.some.avg300
+            ));
+        }
+    }
+    if !vm.core_usage.is_empty() {
+        let cores: Vec<String> = vm
+            .core_usage
+            .iter()
+            .enumerate()
+            .map(|(i, pct)| format!("c{i}={pct:.0}%"))
+            .collect();
+        out.push(format!("  {}", cores.join(" ")));
+    }
     out.push(format!(
         "MEM: {} / {} ({:.1}%)",
         format_bytes(vm.used_memory),
@@ -390,12 +679,13 @@ pub fn format_snapshot(vm: &VmSnapshot, app: &AppState, system: &System, disks:
     ));
     out.push("".to_string());
 
+    let procs = collect_proc_rows(system);
     out.push("Top CPU:".to_string());
-    for row in format_top_processes(system, ProcSort::Cpu, 5) {
+    for row in format_top_processes(&procs, ProcSort::Cpu, 5) {
         out.push(format!("  {row}"));
     }
     out.push("Top MEM:".to_string());
-    for row in format_top_processes(system, ProcSort::Mem, 5) {
+    for row in format_top_processes(&procs, ProcSort::Mem, 5) {
         out.push(format!("  {row}"));
     }
     out.push("".to_string());
@@ -424,5 +714,76 @@ pub fn format_snapshot(vm: &VmSnapshot, app: &AppState, system: &System, disks:
         ));
     }
 
+    if !app.alerts.rules.is_empty() {
+        out.push("".to_string());
+        out.push(format!("Alerts ({} firing):", app.alerts.firing().count()));
+        for r in &app.alerts.rules {
+            let status = match r.status {
+                AlertStatus::Firing => "FIRING",
+                AlertStatus::Pending => "pending",
+                AlertStatus::Ok => "ok",
+            };
+            let rule = if r.rule.name == r.rule.expr {
+                String::new()
+            } else {
+                format!("  [{}]", r.rule.expr)
+            };
+            out.push(format!(
+                "  {status:<7} {}{rule}  value={}",
+                r.rule.name,
+                r.value.as_deref().unwrap_or("n/a")
+            ));
+        }
+    }
+
     out.join("\n")
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_breakdown_splits_time_by_state() {
+        let prev = parse_proc_stat_cpu(
+            "cpu  100 0 50 800 20 0 10 20 0 0\ncpu0 100 0 50 800 20 0 10 20 0 0\n",
+        )
+        .unwrap();
+        let cur = parse_proc_stat_cpu("cpu  150 0 70 900 30 0 10 40 0 0\n").unwrap();
+
+        let b = cpu_breakdown_between(&prev, &cur).unwrap();
+
+        assert_eq!(b.user, 25.0);
+        assert_eq!(b.system, 10.0);
+        assert_eq!(b.idle, 50.0);
+        assert_eq!(b.iowait, 5.0);
+        assert_eq!(b.steal, 10.0);
+        assert!(cpu_breakdown_between(&cur, &cur).is_none());
+    }
+
+    #[test]
+    fn parse_proc_io_reads_storage_bytes() {
+        let io = "rchar: 9000\nwchar: 8000\nsyscr: 10\nsyscw: 5\n\
+                  read_bytes: 4096\nwrite_bytes: 12288\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_proc_io(io), Some((4096, 12288)));
+        assert_eq!(parse_proc_io("rchar: 1\n"), None);
+    }
+
+    #[test]
+    fn parse_psi_reads_some_and_optional_full() {
+        let mem = parse_psi(
+            "some avg10=1.50 avg60=0.75 avg300=0.10 total=123\n\
+             full avg10=0.50 avg60=0.25 avg300=0.00 total=45\n",
+        )
+        .unwrap();
+        assert_eq!(mem.some.avg10, 1.5);
+        assert_eq!(mem.some.avg300, 0.1);
+        assert_eq!(mem.full.unwrap().avg60, 0.25);
+
+        let cpu = parse_psi("some avg10=3.00 avg60=2.00 avg300=1.00 total=9\n").unwrap();
+        assert!(cpu.full.is_none());
+        assert!(parse_psi("").is_none());
+    }
+}
@@ -0,0 +1,311 @@
+//! systemd over D-Bus: the unit list without spawning systemctl, and a
+//! watcher that reloads it as soon as systemd reports a unit change.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::dbus::{Connection, Message, Value};
+use crate::services::{service_health, simplify_timestamp, sort_service_rows};
+use crate::types::{ServiceRow, ServiceState};
+use crate::utils::format_systemd_timestamp;
+
+const SYSTEMD: &str = "org.freedesktop.systemd1";
+const MANAGER_PATH: &str = "/org/freedesktop/systemd1";
+const MANAGER: &str = "org.freedesktop.systemd1.Manager";
+const UNIT: &str = "org.freedesktop.systemd1.Unit";
+const SERVICE: &str = "org.freedesktop.systemd1.Service";
+const PROPERTIES: &str = "org.freedesktop.DBus.Properties";
+
+/// Quiet period after a change before re-listing; a restart emits a burst
+/// of signals and one reload should cover all of them.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+/// Upper bound on how long a steady stream of signals can defer a reload.
+const MAX_DEFER: Duration = Duration::from_secs(1);
+const RECONNECT_AFTER: Duration = Duration::from_secs(30);
+
+fn get_property(conn: &mut Connection, path: &str, iface: &str, name: &str) -> Option<Value> {
+    conn.call(
+        SYSTEMD,
+        path,
+        PROPERTIES,
+        "Get",
+        vec![Value::Str(iface.to_string()), Value::Str(name.to_string())],
+    )
+    .ok()?
+    .into_iter()
+    .next()
+}
+
+/// systemd timestamps are µs since the epoch; 0 means "never". Rendered as
+/// `systemctl show` would, so the column looks the same on either backend.
+fn format_usec(usec: u64) -> String {
+    if usec == 0 {
+        "-".to_string()
+    } else {
+        simplify_timestamp(&format_systemd_timestamp(usec / 1000))
+    }
+}
+
+/// What `ListUnits` doesn't carry about a unit.
+#[derive(Debug, Clone)]
+pub struct UnitProperties {
+    restarts: u32,
+    last_change: String,
+    control_group: String,
+}
+
+/// Properties per unit object path. Each costs a round trip, so the watcher
+/// keeps them until a `PropertiesChanged` names the unit.
+pub type PropertyCache = HashMap<String, UnitProperties>;
+
+fn read_unit_properties(conn: &mut Connection, path: &str, active_state: &str) -> UnitProperties {
+    let restarts = get_property(conn, path, SERVICE, "NRestarts")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32;
+    let stamp = if active_state == "active" {
+        "ActiveEnterTimestamp"
+    } else {
+        "InactiveEnterTimestamp"
+    };
+    let last_change = get_property(conn, path, UNIT, stamp)
+        .and_then(|v| v.as_u64())
+        .map(format_usec)
+        .unwrap_or_else(|| "-".to_string());
+    let control_group = get_property(conn, path, SERVICE, "ControlGroup")
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    UnitProperties {
+        restarts,
+        last_change,
+        control_group,
+    }
+}
+
+/// `.service` entries of a `ListUnits` reply as
+/// (name, description, load, active, sub, object path).
+pub fn parse_list_units(body: &[Value]) -> Vec<[String; 6]> {
+    let Some(Value::Array(_, units)) = body.first() else {
+        return Vec::new();
+    };
+    units
+        .iter()
+        .filter_map(|unit| {
+            let Value::Struct(f) = unit else { return None };
+            let s = |i: usize| f.get(i).and_then(Value::as_str).unwrap_or("").to_string();
+            s(0).ends_with(".service")
+                .then(|| [s(0), s(1), s(2), s(3), s(4), s(6)])
+        })
+        .collect()
+}
+
+/// The same rows `systemctl show` would give, sorted the same way. Only
+/// units missing from `cache` cost property reads.
+pub fn list_services(
+    conn: &mut Connection,
+    cache: &mut PropertyCache,
+) -> Result<Vec<ServiceRow>, String> {
+    let reply = conn.call(SYSTEMD, MANAGER_PATH, MANAGER, "ListUnits", vec![])?;
+    let units = parse_list_units(&reply);
+    let listed: HashSet<&str> = units.iter().map(|u| u[5].as_str()).collect();
+    cache.retain(|path, _| listed.contains(path.as_str()));
+
+    let mut rows = Vec::with_capacity(units.len());
+    for [name, description, load_state, active_state, sub_state, path] in units {
+        let props = match cache.get(&path) {
+            Some(cached) => cached.clone(),
+            None => {
+                let props = read_unit_properties(conn, &path, &active_state);
+                cache.insert(path, props.clone());
+                props
+            }
+        };
+        let health = service_health(&load_state, &active_state, &sub_state, props.restarts);
+        rows.push(ServiceRow {
+            name,
+            description,
+            load_state,
+            active_state,
+            sub_state,
+            restarts: props.restarts,
+            last_change: props.last_change,
+            health,
+            usage: Default::default(),
+            user: false,
+            control_group: props.control_group,
+        });
+    }
+    sort_service_rows(&mut rows);
+    Ok(rows)
+}
+
+/// One-shot listing over a fresh system bus connection.
+pub fn collect_services_dbus() -> Result<Vec<ServiceRow>, String> {
+    list_services(&mut Connection::system()?, &mut PropertyCache::new())
+}
+
+/// The unit whose cached properties a signal makes stale.
+fn changed_unit_path(msg: &Message) -> Option<&str> {
+    match msg.member.as_deref() {
+        Some("PropertiesChanged") => msg.path.as_deref(),
+        _ => None,
+    }
+}
+
+/// Whether a signal can change the service list: a state change on a
+/// `.service` unit object, or a unit appearing or going away.
+fn affects_services(msg: &Message) -> bool {
+    match msg.member.as_deref() {
+        Some("PropertiesChanged") => {
+            msg.path
+                .as_deref()
+                .is_some_and(|p| p.ends_with("_2eservice"))
+                && msg.body.first().and_then(Value::as_str) == Some(UNIT)
+        }
+        Some("UnitNew") | Some("UnitRemoved") => msg
+            .body
+            .first()
+            .and_then(Value::as_str)
+            .is_some_and(|u| u.ends_with(".service")),
+        _ => false,
+    }
+}
+
+fn subscribe(conn: &mut Connection) -> Result<(), String> {
+    conn.call(SYSTEMD, MANAGER_PATH, MANAGER, "Subscribe", vec![])?;
+    conn.add_match(&format!(
+        "type='signal',sender='{SYSTEMD}',interface='{PROPERTIES}',\
+         member='PropertiesChanged',path_namespace='{MANAGER_PATH}/unit'"
+    ))?;
+    conn.add_match(&format!(
+        "type='signal',sender='{SYSTEMD}',interface='{MANAGER}'"
+    ))
+}
+
+fn store(state: &ServiceState, rows: Vec<ServiceRow>) {
+    state.inner.lock().unwrap().replace_system_rows(rows);
+}
+
+/// Reloads on change until the connection fails.
+fn watch(state: &ServiceState, conn: &mut Connection) -> Result<(), String> {
+    subscribe(conn)?;
+    let mut cache = PropertyCache::new();
+    store(state, list_services(conn, &mut cache)?);
+    state.inner.lock().unwrap().live = true;
+
+    let mut dirty_since: Option<Instant> = None;
+    loop {
+        let wait = match dirty_since {
+            // A zero read timeout is an error, not a poll; past the deadline
+            // the 1ms wait just lets the reload below run.
+            Some(t) => DEBOUNCE
+                .min(MAX_DEFER.saturating_sub(t.elapsed()))
+                .max(Duration::from_millis(1)),
+            None => Duration::from_secs(3600),
+        };
+        let quiet = match conn.next_signal(wait)? {
+            Some(msg) => {
+                if let Some(path) = changed_unit_path(&msg) {
+                    cache.remove(path);
+                }
+                if affects_services(&msg) {
+                    dirty_since.get_or_insert_with(Instant::now);
+                }
+                false
+            }
+            None => true,
+        };
+        let due = match dirty_since {
+            Some(t) => quiet || t.elapsed() >= MAX_DEFER,
+            None => false,
+        };
+        if due {
+            store(state, list_services(conn, &mut cache)?);
+            dirty_since = None;
+        }
+    }
+}
+
+/// Starts the background watcher once. Without a system bus (containers,
+/// non-systemd distros) it stays quiet and the periodic systemctl poll
+/// keeps working as before.
+pub fn spawn_service_watcher(state: ServiceState) {
+    std::thread::spawn(move || loop {
+        if let Ok(mut conn) = Connection::system() {
+            let _ = watch(&state, &mut conn);
+        }
+        state.inner.lock().unwrap().live = false;
+        std::thread::sleep(RECONNECT_AFTER);
+    });
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_units_reply_keeps_services_and_signals_are_filtered() {
+        let unit = |name: &str, active: &str| {
+            Value::Struct(vec![
+                Value::Str(name.into()),
+                Value::Str(format!("{name} desc")),
+                Value::Str("loaded".into()),
+                Value::Str(active.into()),
+                Value::Str("dead".into()),
+                Value::Str(String::new()),
+                Value::Path(format!("/org/freedesktop/systemd1/unit/{name}")),
+                Value::U32(0),
+                Value::Str(String::new()),
+                Value::Path("/".into()),
+            ])
+        };
+        let body = vec![Value::Array(
+            "(ssssssouso)".into(),
+            vec![
+                unit("nginx.service", "failed"),
+                unit("sshd.socket", "active"),
+            ],
+        )];
+        let units = parse_list_units(&body);
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0][0], "nginx.service");
+        assert_eq!(units[0][3], "failed");
+        assert_eq!(units[0][5], "/org/freedesktop/systemd1/unit/nginx.service");
+
+        let changed = |path: &str, iface: &str| Message {
+            member: Some("PropertiesChanged".into()),
+            path: Some(path.into()),
+            body: vec![Value::Str(iface.into())],
+            ..Message::default()
+        };
+        assert!(affects_services(&changed(
+            "/org/freedesktop/systemd1/unit/nginx_2eservice",
+            UNIT
+        )));
+        assert!(!affects_services(&changed(
+            "/org/freedesktop/systemd1/unit/nginx_2eservice",
+            SERVICE
+        )));
+        assert!(!affects_services(&changed(
+            "/org/freedesktop/systemd1/unit/dev_2dsda_2edevice",
+            UNIT
+        )));
+
+        // Any PropertiesChanged, e.g. NRestarts on the Service interface,
+        // drops the unit's cached properties.
+        assert_eq!(
+            changed_unit_path(&changed(
+                "/org/freedesktop/systemd1/unit/nginx_2eservice",
+                SERVICE
+            )),
+            Some("/org/freedesktop/systemd1/unit/nginx_2eservice")
+        );
+
+        // Same shape as systemctl's "Tue 2023-11-14 22:13:20 UTC", in local time.
+        assert_eq!(format_usec(0), "-");
+        let shown = format_usec(1_700_000_000_000_000);
+        assert!(shown[..3].chars().all(|c| c.is_ascii_alphabetic()));
+        assert!(shown[3..].starts_with(" 2023-11-1"), "{shown}");
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::config::config;
+use crate::services::{service_health_rank, simplify_timestamp};
+use crate::types::{AppState, ServiceHealth, TimerRow};
+
+// ── Timer refresh ─────────────────────────────────────────────────────────────
+
+pub fn refresh_timers(app: &mut AppState, force: bool) {
+    if !cfg!(target_os = "linux") {
+        let mut state = app.timer_state.inner.lock().unwrap();
+        state.running = false;
+        state.unsupported = Some("systemd timers are Linux-only.".to_string());
+        state.rows.clear();
+        return;
+    }
+
+    let due = force
+        || app
+            .timer_last_refresh_at
+            .map(|t| t.elapsed() >= Duration::from_secs(config().refresh.services_secs))
+            .unwrap_or(true);
+    if !due {
+        return;
+    }
+
+    {
+        let mut state = app.timer_state.inner.lock().unwrap();
+        if state.running {
+            return;
+        }
+        state.running = true;
+        state.error = None;
+    }
+
+    app.timer_last_refresh_at = Some(Instant::now());
+    let inner = app.timer_state.inner.clone();
+    std::thread::spawn(move || {
+        let result = collect_timers();
+        let mut state = inner.lock().unwrap();
+        state.running = false;
+        match result {
+            Ok(rows) => {
+                state.rows = rows;
+                state.error = None;
+                state.last_updated_at = Some(std::time::SystemTime::now());
+            }
+            Err(err) => state.error = Some(err),
+        }
+    });
+}
+
+// ── Timer collection ──────────────────────────────────────────────────────────
+
+fn systemctl_show(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("systemctl")
+        .arg("show")
+        .args(args)
+        .arg("--no-pager")
+        .output()
+        .map_err(|e| format!("failed to run systemctl: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("systemctl exited with {}", output.status)
+        } else {
+            stderr
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Splits `systemctl show` output into one key → value map per unit.
+fn show_blocks(stdout: &str) -> Vec<HashMap<&str, &str>> {
+    stdout
+        .split("\n\n")
+        .map(|block| block.lines().filter_map(|l| l.split_once('=')).collect())
+        .filter(|m: &HashMap<&str, &str>| !m.is_empty())
+        .collect()
+}
+
+/// Every loaded timer, joined with the last run of the unit it activates.
+pub fn collect_timers() -> Result<Vec<TimerRow>, String> {
+    let timers = systemctl_show(&[
+        "*.timer",
+        "--all",
+        "--property=Id,Description,ActiveState,Unit,NextElapseUSecRealtime,LastTriggerUSec",
+    ])?;
+    let units: Vec<&str> = show_blocks(&timers)
+        .iter()
+        .filter_map(|b| b.get("Unit").copied())
+        .filter(|u| !u.is_empty())
+        .collect();
+    let services = if units.is_empty() {
+        String::new()
+    } else {
+        let mut args = units;
+        args.push("--property=Id,ActiveState,SubState,Result");
+        systemctl_show(&args)?
+    };
+    Ok(parse_timer_rows(&timers, &services))
+}
+
+/// Builds rows from `systemctl show '*.timer'` and `systemctl show` of the
+/// activated units. Failed runs first, then by next elapse.
+pub fn parse_timer_rows(timers: &str, services: &str) -> Vec<TimerRow> {
+    let services: HashMap<&str, HashMap<&str, &str>> = show_blocks(services)
+        .into_iter()
+        .filter_map(|b| Some((*b.get("Id")?, b)))
+        .collect();
+
+    let mut rows: Vec<TimerRow> = show_blocks(timers)
+        .into_iter()
+        .filter_map(|t| {
+            let name = t.get("Id").filter(|id| id.ends_with(".timer"))?;
+            let get =
+                |m: &HashMap<&str, &str>, k: &str| m.get(k).copied().unwrap_or("").to_string();
+            let unit = get(&t, "Unit");
+            let svc = services.get(unit.as_str());
+            let svc_get = |k: &str| svc.map(|s| get(s, k)).unwrap_or_default();
+            let (unit_state, unit_result) = (svc_get("ActiveState"), svc_get("Result"));
+            let active_state = get(&t, "ActiveState");
+            let health = timer_health(&active_state, &unit_state, &unit_result);
+            Some(TimerRow {
+                name: name.to_string(),
+                description: get(&t, "Description"),
+                active_state,
+                next_elapse: simplify_timestamp(&get(&t, "NextElapseUSecRealtime")),
+                last_trigger: simplify_timestamp(&get(&t, "LastTriggerUSec")),
+                unit,
+                unit_state: if unit_state.is_empty() {
+                    "-".to_string()
+                } else {
+                    format!("{unit_state} ({})", svc_get("SubState"))
+                },
+                unit_result: if unit_result.is_empty() {
+                    "-".to_string()
+                } else {
+                    unit_result
+                },
+                health,
+            })
+        })
+        .collect();
+
+    rows.sort_by_key(|r| {
+        (
+            service_health_rank(r.health),
+            r.next_elapse == "-",
+            sortable_timestamp(&r.next_elapse),
+            r.name.clone(),
+        )
+    });
+    rows
+}
+
+/// A failed last run is critical; a timer that won't fire again is a warning.
+pub fn timer_health(timer_state: &str, unit_state: &str, unit_result: &str) -> ServiceHealth {
+    if unit_state == "failed" || (!unit_result.is_empty() && unit_result != "success") {
+        ServiceHealth::Critical
+    } else if timer_state != "active" {
+        ServiceHealth::Warning
+    } else {
+        ServiceHealth::Healthy
+    }
+}
+
+/// "Thu 2026-03-12 10:00:00 UTC" → "2026-03-12 10:00:00 UTC" so timestamps
+/// compare in time order.
+fn sortable_timestamp(ts: &str) -> String {
+    match ts.split_once(' ') {
+        Some((day, rest)) if day.len() == 3 && day.chars().all(|c| c.is_ascii_alphabetic()) => {
+            rest.to_string()
+        }
+        _ => ts.to_string(),
+    }
+}
+
+pub fn selected_timer(app: &AppState) -> Option<TimerRow> {
+    let state = app.timer_state.inner.lock().unwrap();
+    state.rows.get(app.timer_scroll as usize).cloned()
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_timers_with_their_service_results() {
+        let timers = "Id=backup.timer\nDescription=Nightly backup\nActiveState=active\n\
+Unit=backup.service\nNextElapseUSecRealtime=Fri 2026-03-13 02:00:00 UTC\n\
+LastTriggerUSec=Thu 2026-03-12 02:00:03 UTC\n\n\
+Id=logrotate.timer\nDescription=Rotate logs\nActiveState=active\nUnit=logrotate.service\n\
+NextElapseUSecRealtime=Thu 2026-03-12 23:00:00 UTC\nLastTriggerUSec=Wed 2026-03-11 23:00:00 UTC\n\n\
+Id=fstrim.timer\nDescription=Discard unused blocks\nActiveState=inactive\nUnit=fstrim.service\n\
+NextElapseUSecRealtime=\nLastTriggerUSec=n/a\n";
+        let services =
+            "Id=backup.service\nActiveState=failed\nSubState=failed\nResult=exit-code\n\n\
+Id=logrotate.service\nActiveState=inactive\nSubState=dead\nResult=success\n\n\
+Id=fstrim.service\nActiveState=inactive\nSubState=dead\nResult=success\n";
+
+        let rows = parse_timer_rows(timers, services);
+        let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["backup.timer", "fstrim.timer", "logrotate.timer"]);
+
+        assert_eq!(rows[0].health, ServiceHealth::Critical);
+        assert_eq!(rows[0].unit, "backup.service");
+        assert_eq!(rows[0].unit_result, "exit-code");
+        assert_eq!(rows[0].unit_state, "failed (failed)");
+        assert_eq!(rows[1].health, ServiceHealth::Warning);
+        assert_eq!(rows[1].next_elapse, "-");
+        assert_eq!(rows[1].last_trigger, "-");
+        assert_eq!(rows[2].health, ServiceHealth::Healthy);
+    }
+}
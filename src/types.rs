@@ -1,6 +1,6 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::Instant;
 
 // proc_pid_rusage gives per-process phys_footprint (compressed included)
@@ -11,18 +11,35 @@ extern "C" {
     fn proc_pid_rusage(pid: i32, flavor: i32, buffer: *mut u8) -> i32;
 }
 
-use sysinfo::{Process, System};
+use serde::{Deserialize, Deserializer, Serialize};
+use sysinfo::{Groups, Process, System, Users};
+
+use crate::alerts::{AlertEngine, AlertEvent};
+use crate::config::config;
+use crate::logfollow::LogFollow;
+use crate::logsearch::LogFilter;
+use crate::procaction::{KillConfirm, ProcActionMenu};
+use crate::procinfo::ProcDetail;
+use crate::procrestart::RestartConfirm;
+use crate::services::sort_service_rows;
+use crate::svcaction::ServiceActionMenu;
+use crate::svcinfo::ServiceDetail;
+use crate::svcusage::CgroupCounters;
 
 // ── Screen navigation ────────────────────────────────────────────────────────
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Screen {
     #[default]
     Dashboard,
     Processes,
     DiskDive,
     Services,
+    Timers,
     Logs,
+    Network,
+    Alerts,
 }
 
 // ── Process sorting ──────────────────────────────────────────────────────────
@@ -33,6 +50,7 @@ pub enum ProcSort {
     Cpu,
     Mem,
     Swap,
+    Io,
 }
 
 // ── Disk dive targets ────────────────────────────────────────────────────────
@@ -87,6 +105,25 @@ pub enum ServiceFilter {
     All,
 }
 
+/// Which systemd manager the services view lists.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceScope {
+    #[default]
+    System,
+    /// `systemctl --user`: the calling user's own manager.
+    User,
+    Both,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceSort {
+    #[default]
+    Health,
+    Mem,
+    Cpu,
+    Io,
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum LogSeverity {
     Errors,
@@ -103,7 +140,8 @@ pub enum LogUnitFilter {
     All,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ServiceHealth {
     Healthy,
     Warning,
@@ -137,11 +175,43 @@ pub struct ServiceState {
     pub inner: Arc<Mutex<ServiceStateInner>>,
 }
 
+#[derive(Clone, Default)]
+pub struct TimerState {
+    pub inner: Arc<Mutex<TimerStateInner>>,
+}
+
 #[derive(Clone, Default)]
 pub struct LogState {
     pub inner: Arc<Mutex<LogStateInner>>,
 }
 
+#[derive(Clone, Default)]
+pub struct ServiceActionState {
+    pub inner: Arc<Mutex<ServiceActionStateInner>>,
+}
+
+#[derive(Clone, Default)]
+pub struct ServiceDetailState {
+    pub inner: Arc<Mutex<ServiceDetailStateInner>>,
+}
+
+#[derive(Clone, Default)]
+pub struct ProcDetailState {
+    pub inner: Arc<Mutex<ProcDetailStateInner>>,
+}
+
+#[derive(Clone, Default)]
+pub struct RestartState {
+    pub inner: Arc<Mutex<RestartStateInner>>,
+}
+
+#[derive(Clone, Default)]
+pub struct NotifyState {
+    pub inner: Arc<Mutex<NotifyStateInner>>,
+    /// Feeds the delivery worker; started with the first event.
+    pub queue: Option<mpsc::Sender<AlertEvent>>,
+}
+
 #[derive(Default)]
 pub struct DiskScanState {
     pub running: bool,
@@ -161,6 +231,77 @@ pub struct ServiceStateInner {
     pub error: Option<String>,
     pub rows: Vec<ServiceRow>,
     pub last_updated_at: Option<std::time::SystemTime>,
+    /// Subscribed to systemd over D-Bus; rows update as units change.
+    pub live: bool,
+    /// The watcher's latest system unit list, whatever scope is shown, so
+    /// switching scope doesn't need another listing.
+    pub system_rows: Vec<ServiceRow>,
+    /// Manager(s) the rows come from; results for another scope are dropped.
+    pub scope: ServiceScope,
+}
+
+impl ServiceStateInner {
+    /// Swaps in a fresh unit list, keeping each unit's last usage sample so
+    /// the resource columns don't blank out until the next tick.
+    pub fn replace_rows(&mut self, mut rows: Vec<ServiceRow>) {
+        for row in &mut rows {
+            if let Some(old) = self
+                .rows
+                .iter()
+                .find(|o| o.name == row.name && o.user == row.user)
+            {
+                row.usage = old.usage.clone();
+            }
+        }
+        self.rows = rows;
+        self.error = None;
+        self.last_updated_at = Some(std::time::SystemTime::now());
+    }
+
+    /// Takes a fresh system manager list (the D-Bus watcher's), keeping any
+    /// user units already listed when both scopes are shown.
+    pub fn replace_system_rows(&mut self, mut rows: Vec<ServiceRow>) {
+        self.system_rows = rows.clone();
+        match self.scope {
+            ServiceScope::System => self.replace_rows(rows),
+            ServiceScope::User => {}
+            ServiceScope::Both => {
+                rows.extend(self.rows.iter().filter(|r| r.user).cloned());
+                sort_service_rows(&mut rows);
+                self.replace_rows(rows);
+            }
+        }
+    }
+
+    /// Takes a fresh user manager list while the watcher supplies the
+    /// system units.
+    pub fn replace_user_rows(&mut self, mut rows: Vec<ServiceRow>) {
+        match self.scope {
+            ServiceScope::System => {}
+            ServiceScope::User => self.replace_rows(rows),
+            ServiceScope::Both => {
+                rows.extend(self.system_rows.iter().cloned());
+                sort_service_rows(&mut rows);
+                self.replace_rows(rows);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TimerStateInner {
+    pub running: bool,
+    pub unsupported: Option<String>,
+    pub error: Option<String>,
+    pub rows: Vec<TimerRow>,
+    pub last_updated_at: Option<std::time::SystemTime>,
+}
+
+#[derive(Default)]
+pub struct ServiceActionStateInner {
+    /// The systemctl command in flight.
+    pub running: Option<String>,
+    pub result: Option<(Result<String, String>, Instant)>,
 }
 
 #[derive(Default)]
@@ -168,14 +309,53 @@ pub struct LogStateInner {
     pub running: bool,
     pub unsupported: Option<String>,
     pub error: Option<String>,
-    pub lines: Vec<String>,
+    /// Oldest first; capped at `FOLLOW_BUFFER` while following.
+    pub entries: VecDeque<LogEntry>,
     pub last_updated_at: Option<std::time::SystemTime>,
     pub source: String,
+    /// A follow stream owns `entries`; one-shot reads leave them alone.
+    pub following: bool,
+    /// Entries pushed off the front of a full follow buffer since the last
+    /// draw; a paused selection moves back by as many to stay put.
+    pub dropped: usize,
+}
+
+#[derive(Default)]
+pub struct ServiceDetailStateInner {
+    pub running: bool,
+    /// The unit (and whether it is a user unit) being shown; a load that
+    /// finishes after the view moved on is dropped.
+    pub unit: Option<(String, bool)>,
+    pub detail: Option<Result<ServiceDetail, String>>,
+}
+
+#[derive(Default)]
+pub struct ProcDetailStateInner {
+    pub running: bool,
+    /// The PID being shown; a load that finishes after the view moved on is
+    /// dropped.
+    pub pid: Option<i32>,
+    pub detail: Option<Result<ProcDetail, String>>,
+}
+
+#[derive(Default)]
+pub struct RestartStateInner {
+    pub running: bool,
+    /// Outcome of the last relaunch not yet shown: the new PID and message,
+    /// or why it didn't happen.
+    pub result: Option<Result<(u32, String), String>>,
+}
+
+#[derive(Default)]
+pub struct NotifyStateInner {
+    pub sent: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
 }
 
 // ── Service row ──────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServiceRow {
     pub name: String,
     pub description: String,
@@ -185,11 +365,70 @@ pub struct ServiceRow {
     pub restarts: u32,
     pub last_change: String,
     pub health: ServiceHealth,
+    #[serde(default)]
+    pub usage: ServiceUsage,
+    /// From the user manager (`systemctl --user`) rather than the system one.
+    #[serde(default)]
+    pub user: bool,
+    /// `ControlGroup=`, e.g. `/system.slice/system-getty.slice/getty@tty1.service`;
+    /// empty while the unit isn't running.
+    #[serde(default)]
+    pub control_group: String,
+}
+
+/// Resource usage from the unit's cgroup. `None` where the file is missing:
+/// the unit isn't running, or that kind of accounting is off.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServiceUsage {
+    pub mem_bytes: Option<u64>,
+    /// Percent of one CPU ×10, like `ProcRow::cpu_x10`.
+    pub cpu_x10: Option<u32>,
+    /// Bytes read + written per second.
+    pub io_bps: Option<u64>,
+    pub tasks: Option<u64>,
+}
+
+// ── Log entry ────────────────────────────────────────────────────────────────
+
+/// One journal record, or a syslog line with only `message` set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Local time, e.g. "2026-03-12 10:00:00"; empty for syslog lines.
+    pub timestamp: String,
+    /// syslog priority, 0 (emerg) to 7 (debug).
+    pub priority: Option<u8>,
+    pub unit: String,
+    pub identifier: String,
+    pub pid: Option<u32>,
+    pub hostname: String,
+    pub message: String,
+    /// Every field of the record, sorted by name, for the expanded view.
+    /// Not recorded: it would repeat `_CMDLINE` and friends in every frame.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<(String, String)>,
+}
+
+// ── Timer row ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerRow {
+    pub name: String,
+    pub description: String,
+    pub active_state: String,
+    pub next_elapse: String,
+    pub last_trigger: String,
+    /// The unit the timer activates, usually the same-named `.service`.
+    pub unit: String,
+    /// "active (running)", "inactive (dead)", ... of that unit.
+    pub unit_state: String,
+    /// `Result=` of the unit's last run: success, exit-code, timeout, ...
+    pub unit_result: String,
+    pub health: ServiceHealth,
 }
 
 // ── Disk row (mount info) ────────────────────────────────────────────────────
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiskRow {
     pub fs: String,
     pub size: u64,
@@ -201,12 +440,110 @@ pub struct DiskRow {
     pub write_bps: u64,
 }
 
+// ── Network interface row ────────────────────────────────────────────────────
+
+/// Raw cumulative counters for one interface, as read from `/proc/net/dev`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetCounters {
+    pub rx_bytes: u64,
+    pub rx_packets: u64,
+    pub rx_errs: u64,
+    pub rx_drop: u64,
+    pub tx_bytes: u64,
+    pub tx_packets: u64,
+    pub tx_errs: u64,
+    pub tx_drop: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetRow {
+    pub name: String,
+    pub rx_bps: u64,
+    pub tx_bps: u64,
+    pub rx_pps: u64,
+    pub tx_pps: u64,
+    pub errs: u64,
+    pub drops: u64,
+    /// rx+tx errors since the previous sample.
+    pub errs_delta: u64,
+    pub drops_delta: u64,
+    pub rx_total: u64,
+    pub tx_total: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NetHistory {
+    pub rx: VecDeque<u64>,
+    pub tx: VecDeque<u64>,
+}
+
+// ── CPU time breakdown ───────────────────────────────────────────────────────
+
+/// Cumulative jiffies from the aggregate `cpu` line of `/proc/stat`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+/// Share of CPU time per state between two `/proc/stat` samples, in percent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuBreakdown {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    pub steal: f32,
+}
+
+// ── Pressure stall information ───────────────────────────────────────────────
+
+/// One `some`/`full` line from `/proc/pressure/*`: share of wall time stalled, in percent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PsiAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PsiResource {
+    pub some: PsiAverages,
+    pub full: Option<PsiAverages>,
+}
+
+/// `None` per resource when the kernel has no PSI support (or it is disabled).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Psi {
+    pub cpu: Option<PsiResource>,
+    pub memory: Option<PsiResource>,
+    pub io: Option<PsiResource>,
+}
+
+impl Psi {
+    pub fn available(&self) -> bool {
+        self.cpu.is_some() || self.memory.is_some() || self.io.is_some()
+    }
+}
+
 // ── VM snapshot ──────────────────────────────────────────────────────────────
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VmSnapshot {
     pub cpu_usage: f32,
     pub cpu_cores: usize,
+    pub core_usage: Vec<f32>,
+    pub cpu_breakdown: Option<CpuBreakdown>,
+    pub psi: Psi,
     pub load_avg_one: f64,
     pub load_avg_five: f64,
     pub load_avg_fifteen: f64,
@@ -221,6 +558,18 @@ pub struct VmSnapshot {
 
 // ── Process row ──────────────────────────────────────────────────────────────
 
+/// Aliased so serde does not infer a borrow from the deserializer for it.
+pub type StatusLabel = &'static str;
+
+/// Maps a recorded status label back onto the static labels used by `ProcRow`.
+fn deserialize_status<'de, D: Deserializer<'de>>(d: D) -> Result<StatusLabel, D::Error> {
+    let label = String::deserialize(d)?;
+    Ok(["Run", "Sleep", "Idle", "Stop", "Zombie", "Dead", "Trace"]
+        .into_iter()
+        .find(|known| *known == label)
+        .unwrap_or("?"))
+}
+
 fn proc_status_label(s: sysinfo::ProcessStatus) -> &'static str {
     match s {
         sysinfo::ProcessStatus::Run => "Run",
@@ -234,14 +583,25 @@ fn proc_status_label(s: sysinfo::ProcessStatus) -> &'static str {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProcRow {
     pub pid: i32,
+    #[serde(default)]
+    pub ppid: Option<i32>,
     pub name: String,
     pub cpu_x10: i32,
     pub mem_bytes: u64,
     pub swap_bytes: u64,
-    pub status: &'static str,
+    #[serde(deserialize_with = "deserialize_status")]
+    pub status: StatusLabel,
+    /// Linux userland thread listed alongside its process; skipped by the tree view.
+    #[serde(default)]
+    pub is_thread: bool,
+    /// Storage I/O rates from `/proc/<pid>/io`; filled by `update_proc_io_rates`.
+    #[serde(default)]
+    pub read_bps: u64,
+    #[serde(default)]
+    pub write_bps: u64,
 }
 
 fn read_proc_swap_bytes(pid: u32) -> u64 {
@@ -312,13 +672,21 @@ impl ProcRow {
         let mem_bytes = p.memory();
         ProcRow {
             pid: pid.as_u32() as i32,
+            ppid: p.parent().map(|pp| pp.as_u32() as i32),
             name: p.name().to_string(),
             cpu_x10,
             mem_bytes,
             swap_bytes: read_proc_swap_bytes(pid.as_u32()),
             status: proc_status_label(p.status()),
+            is_thread: p.thread_kind() == Some(sysinfo::ThreadKind::Userland),
+            read_bps: 0,
+            write_bps: 0,
         }
     }
+
+    pub fn io_bps(&self) -> u64 {
+        self.read_bps.saturating_add(self.write_bps)
+    }
 }
 
 // ── Update state ─────────────────────────────────────────────────────────────
@@ -331,11 +699,47 @@ pub struct UpdateState {
     pub error: Option<String>,
 }
 
+// ── Replay ───────────────────────────────────────────────────────────────────
+
+/// Playback position shown in the header while `ferro replay` drives the UI.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayStatus {
+    pub position: usize,
+    pub total: usize,
+    pub playing: bool,
+    pub speed: f64,
+    /// Milliseconds since the first recorded frame.
+    pub offset_ms: u64,
+}
+
 // ── CLI args ─────────────────────────────────────────────────────────────────
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Interactive TUI (no subcommand).
+    #[default]
+    Tui,
+    /// `ferro snapshot [--json] [--samples N] [--pretty]`
+    Snapshot {
+        json: bool,
+        samples: u32,
+        pretty: bool,
+    },
+    /// `ferro serve [--listen ADDR]` — OpenMetrics exporter.
+    Serve { listen: String },
+    /// `ferro record <FILE>` — append every tick to a recording.
+    Record { path: PathBuf },
+    /// `ferro replay <FILE>` — drive the TUI from a recording.
+    Replay { path: PathBuf },
+    /// `ferro config [--print-defaults]`
+    Config { print_defaults: bool },
+}
+
 #[derive(Default)]
 pub struct Args {
-    pub tick_ms: u64,
+    pub command: Command,
+    /// `None` when not given on the command line; the config file decides.
+    pub tick_ms: Option<u64>,
     pub no_mouse: bool,
     pub show_help: bool,
     pub show_version: bool,
@@ -348,27 +752,97 @@ pub struct AppState {
     pub show_help: bool,
 
     pub proc_sort: ProcSort,
+    pub proc_rows: Vec<ProcRow>,
+    pub proc_rows_stale: bool,
+    /// Viewport offset; the selection itself is `proc_selected_pid`.
     pub proc_scroll: u16,
+    /// Selection follows this PID across refreshes and re-sorts.
+    pub proc_selected_pid: Option<i32>,
+    /// Row index of the selection, used when the selected PID disappears.
+    pub proc_cursor: u16,
+    /// Result of the last process action, shown on the hint line briefly.
+    pub proc_message: Option<(String, Instant)>,
+    pub proc_restart_state: RestartState,
     pub proc_search: String,
     pub proc_search_active: bool,
-    pub proc_kill_confirm: Option<(i32, String)>,
-    pub proc_restart_confirm: Option<(i32, String, std::path::PathBuf, Vec<String>)>,
+    pub proc_kill_confirm: Option<KillConfirm>,
+    pub proc_restart_confirm: Option<RestartConfirm>,
+    /// Signal / renice / ionice menu (`s`).
+    pub proc_action: Option<ProcActionMenu>,
+    pub proc_tree: bool,
+    pub proc_collapsed: HashSet<i32>,
+    /// Detail view open for this PID (Enter on the processes screen).
+    pub proc_detail_pid: Option<i32>,
+    pub proc_detail_state: ProcDetailState,
+    /// Account lists used to name a process's owner, loaded when the detail
+    /// view opens rather than on every refresh.
+    pub proc_detail_accounts: Option<(Users, Groups)>,
+    pub proc_detail_scroll: u16,
+    pub proc_detail_at: Option<Instant>,
+    pub proc_io_prev: HashMap<i32, (u64, u64)>,
+    pub proc_io_at: Option<Instant>,
 
     pub disk_target: DiskTarget,
     pub disk_scroll: u16,
     pub disk_scan: DiskScan,
     pub service_scroll: u16,
     pub service_filter: ServiceFilter,
+    pub service_sort: ServiceSort,
+    pub service_scope: ServiceScope,
+    pub service_detail_unit: Option<String>,
+    pub service_detail_user: bool,
+    pub service_detail_state: ServiceDetailState,
+    pub service_detail_scroll: u16,
     pub service_search: String,
     pub service_search_active: bool,
     pub service_state: ServiceState,
     pub service_last_refresh_at: Option<Instant>,
+    pub service_watcher_started: bool,
+    pub service_usage_prev: HashMap<(bool, String), CgroupCounters>,
+    pub service_usage_at: Option<Instant>,
+    /// start/stop/restart/... menu (`s`).
+    pub service_action: Option<ServiceActionMenu>,
+    pub service_action_state: ServiceActionState,
+    pub timer_scroll: u16,
+    pub timer_state: TimerState,
+    pub timer_last_refresh_at: Option<Instant>,
     pub logs_scroll: u16,
     pub log_severity: LogSeverity,
     pub log_unit_filter: LogUnitFilter,
     pub log_state: LogState,
     pub log_last_refresh_at: Option<Instant>,
     pub log_selected_unit: Option<String>,
+    /// The selected unit belongs to the user manager (`journalctl --user-unit`).
+    pub log_selected_user: bool,
+    /// Show every field of the selected entry (Enter).
+    pub log_expanded: bool,
+    /// Streaming new lines (`f`) instead of re-reading the last 80.
+    pub log_follow: Option<LogFollow>,
+    /// Keep the view on the newest line; cleared by scrolling up.
+    pub log_follow_pinned: bool,
+    /// `/` query, highlighted in messages; `n`/`N` jump between matches.
+    pub log_search: String,
+    pub log_search_active: bool,
+    /// `&` regex filter on messages, applied to every read.
+    pub log_filter: Option<LogFilter>,
+    pub log_filter_input: String,
+    pub log_filter_active: bool,
+    pub log_filter_error: Option<String>,
+
+    pub net_rows: Vec<NetRow>,
+    pub net_prev: HashMap<String, NetCounters>,
+    pub net_history: HashMap<String, NetHistory>,
+    pub net_last_history_at: Option<Instant>,
+    pub net_scroll: u16,
+
+    pub alerts: AlertEngine,
+    pub alert_mount_rows: Vec<DiskRow>,
+    pub alert_last_mounts_at: Option<Instant>,
+    /// System units polled for service rules while the D-Bus watcher is down.
+    pub alert_service_rows: Arc<Mutex<Vec<ServiceRow>>>,
+    pub alert_last_services_at: Option<Instant>,
+    pub alerts_scroll: u16,
+    pub notify_state: NotifyState,
 
     // Dashboard caches (quick overview)
     pub dash_dir_target: DashDirTarget,
@@ -379,16 +853,23 @@ pub struct AppState {
     pub dash_mem_pressure: Vec<String>,
     pub dash_cpu_history: VecDeque<u16>,
     pub dash_mem_history: VecDeque<u16>,
+    pub dash_psi_cpu_history: VecDeque<u16>,
+    pub dash_psi_mem_history: VecDeque<u16>,
+    pub dash_psi_io_history: VecDeque<u16>,
     pub dash_last_proc_at: Option<Instant>,
     pub dash_last_fs_at: Option<Instant>,
     pub dash_last_history_at: Option<Instant>,
     pub dash_last_io_at: Option<Instant>,
     pub dash_diskstats_prev: HashMap<String, (u64, u64)>,
+    pub cpu_stat_prev: Option<CpuTimes>,
+    pub cpu_breakdown: Option<CpuBreakdown>,
+    pub psi: Psi,
     pub dash_show_all_mounts: bool,
     pub hostname: String,
     pub footer_tip_idx: u8,
     pub tick_ms: u64,
     pub dump_snapshot: bool,
+    pub replay: Option<ReplayStatus>,
 
     pub update: UpdateState,
     pub do_update: bool,
@@ -397,29 +878,81 @@ pub struct AppState {
 impl Default for AppState {
     fn default() -> Self {
         Self {
-            screen: Screen::default(),
+            screen: config().general.default_screen,
             show_help: false,
             proc_sort: ProcSort::default(),
+            proc_rows: Vec::new(),
+            proc_rows_stale: true,
             proc_scroll: 0,
+            proc_selected_pid: None,
+            proc_cursor: 0,
+            proc_message: None,
+            proc_restart_state: RestartState::default(),
             proc_search: String::new(),
             proc_search_active: false,
             proc_kill_confirm: None,
             proc_restart_confirm: None,
+            proc_action: None,
+            proc_tree: false,
+            proc_collapsed: HashSet::new(),
+            proc_detail_pid: None,
+            proc_detail_state: ProcDetailState::default(),
+            proc_detail_accounts: None,
+            proc_detail_scroll: 0,
+            proc_detail_at: None,
+            proc_io_prev: HashMap::new(),
+            proc_io_at: None,
             disk_target: DiskTarget::default(),
             disk_scroll: 0,
             disk_scan: DiskScan::default(),
             service_scroll: 0,
             service_filter: ServiceFilter::default(),
+            service_sort: ServiceSort::default(),
+            service_scope: ServiceScope::default(),
+            service_detail_unit: None,
+            service_detail_user: false,
+            service_detail_state: ServiceDetailState::default(),
+            service_detail_scroll: 0,
             service_search: String::new(),
             service_search_active: false,
             service_state: ServiceState::default(),
             service_last_refresh_at: None,
+            service_watcher_started: false,
+            service_usage_prev: HashMap::new(),
+            service_usage_at: None,
+            service_action: None,
+            service_action_state: ServiceActionState::default(),
+            timer_scroll: 0,
+            timer_state: TimerState::default(),
+            timer_last_refresh_at: None,
             logs_scroll: 0,
             log_severity: LogSeverity::default(),
             log_unit_filter: LogUnitFilter::default(),
             log_state: LogState::default(),
             log_last_refresh_at: None,
             log_selected_unit: None,
+            log_selected_user: false,
+            log_expanded: false,
+            log_follow: None,
+            log_follow_pinned: true,
+            log_search: String::new(),
+            log_search_active: false,
+            log_filter: None,
+            log_filter_input: String::new(),
+            log_filter_active: false,
+            log_filter_error: None,
+            net_rows: Vec::new(),
+            net_prev: HashMap::new(),
+            net_history: HashMap::new(),
+            net_last_history_at: None,
+            net_scroll: 0,
+            alerts: AlertEngine::from_config(&config().alerts),
+            alert_mount_rows: Vec::new(),
+            alert_last_mounts_at: None,
+            alert_service_rows: Arc::default(),
+            alert_last_services_at: None,
+            alerts_scroll: 0,
+            notify_state: NotifyState::default(),
             dash_dir_target: DashDirTarget::default(),
             dash_dir_sizes: Vec::new(),
             dash_mount_rows: Vec::new(),
@@ -428,16 +961,23 @@ impl Default for AppState {
             dash_mem_pressure: Vec::new(),
             dash_cpu_history: VecDeque::new(),
             dash_mem_history: VecDeque::new(),
+            dash_psi_cpu_history: VecDeque::new(),
+            dash_psi_mem_history: VecDeque::new(),
+            dash_psi_io_history: VecDeque::new(),
             dash_last_proc_at: None,
             dash_last_fs_at: None,
             dash_last_history_at: None,
             dash_last_io_at: None,
             dash_diskstats_prev: HashMap::new(),
-            dash_show_all_mounts: true,
+            cpu_stat_prev: None,
+            cpu_breakdown: None,
+            psi: Psi::default(),
+            dash_show_all_mounts: config().mounts.show_all,
             hostname: System::host_name().unwrap_or_else(|| "unknown".to_string()),
             footer_tip_idx: 0,
-            tick_ms: 500,
+            tick_ms: config().general.tick_ms,
             dump_snapshot: false,
+            replay: None,
             update: UpdateState::default(),
             do_update: false,
         }
@@ -465,4 +1005,48 @@ mod tests {
         target = target.next();
         assert_eq!(target, DashDirTarget::Cwd);
     }
+
+    #[test]
+    fn system_rows_from_the_watcher_respect_the_scope() {
+        let row = |name: &str, user: bool| ServiceRow {
+            name: name.to_string(),
+            description: String::new(),
+            load_state: "loaded".to_string(),
+            active_state: "active".to_string(),
+            sub_state: "running".to_string(),
+            restarts: 0,
+            last_change: "-".to_string(),
+            health: ServiceHealth::Healthy,
+            usage: ServiceUsage::default(),
+            user,
+            control_group: String::new(),
+        };
+        let mut state = ServiceStateInner {
+            scope: ServiceScope::Both,
+            rows: vec![row("cron.service", false), row("syncthing.service", true)],
+            ..Default::default()
+        };
+
+        state.replace_system_rows(vec![row("sshd.service", false)]);
+        let names: Vec<_> = state
+            .rows
+            .iter()
+            .map(|r| (r.name.as_str(), r.user))
+            .collect();
+        assert_eq!(
+            names,
+            [("sshd.service", false), ("syncthing.service", true)]
+        );
+
+        // User units polled while the watcher is live merge with its list.
+        state.replace_user_rows(vec![row("podman.service", true)]);
+        let names: Vec<_> = state.rows.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["podman.service", "sshd.service"]);
+
+        state.scope = ServiceScope::User;
+        state.rows = vec![row("syncthing.service", true)];
+        state.replace_system_rows(vec![row("sshd.service", false)]);
+        assert_eq!(state.rows.len(), 1);
+        assert!(state.rows[0].user);
+    }
 }
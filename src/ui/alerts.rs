@@ -0,0 +1,171 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
+
+use crate::alerts::{AlertEventKind, AlertStatus};
+use crate::config::{config, config_path};
+use crate::types::AppState;
+use crate::utils::{format_duration_short, format_utc_timestamp, trim_to, unix_ms_now};
+
+pub fn render_alerts(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
+    let firing = app.alerts.firing().count();
+    let block = Block::default()
+        .title(format!("Alerts ({firing} firing)"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if firing > 0 { Color::Red } else { Color::Green }));
+    frame.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    if app.alerts.rules.is_empty() {
+        let path = config_path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "config.toml".to_string());
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from("No alert rules configured."),
+                Line::from(""),
+                Line::from(format!("Add [[alerts]] entries to {path}")),
+                Line::from("Run `ferro config --print-defaults` for the rule syntax and examples."),
+            ])
+            .alignment(ratatui::prelude::Alignment::Center)
+            .wrap(Wrap { trim: true }),
+            inner,
+        );
+        return;
+    }
+
+    let rules_height = (app.alerts.rules.len() as u16 + 3).min(inner.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(rules_height),
+            Constraint::Min(4),
+        ])
+        .split(inner);
+    frame.render_widget(Paragraph::new(notify_status_line(app)), chunks[0]);
+    let chunks = &chunks[1..];
+
+    let now = unix_ms_now();
+    let ago = |ms: u64| format_duration_short(now.saturating_sub(ms) / 1000);
+
+    let rule_rows = app.alerts.rules.iter().map(|r| {
+        let (label, style) = match r.status {
+            AlertStatus::Firing => (
+                "FIRING",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            AlertStatus::Pending => ("pending", Style::default().fg(Color::Yellow)),
+            AlertStatus::Ok => ("ok", Style::default().fg(Color::Green)),
+        };
+        let since = match (r.status, r.fired_at_ms, r.matched_since_ms) {
+            (AlertStatus::Firing, Some(t), _) => {
+                format!("{} ({} ago)", format_utc_timestamp(t), ago(t))
+            }
+            (AlertStatus::Pending, _, Some(t)) => format!(
+                "matching {} of {}",
+                ago(t),
+                format_duration_short(r.rule.hold.as_secs())
+            ),
+            _ => String::new(),
+        };
+        Row::new(vec![
+            Cell::from(label).style(style),
+            Cell::from(trim_to(&r.rule.name, 20)),
+            Cell::from(r.rule.expr.clone()),
+            Cell::from(r.value.clone().unwrap_or_else(|| "n/a".to_string())),
+            Cell::from(since),
+        ])
+    });
+    let rules_table = Table::new(
+        rule_rows,
+        [
+            Constraint::Length(8),
+            Constraint::Length(20),
+            Constraint::Min(24),
+            Constraint::Length(10),
+            Constraint::Length(34),
+        ],
+    )
+    .header(
+        Row::new(vec!["STATE", "NAME", "RULE", "VALUE", "SINCE"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Rules"));
+    frame.render_widget(rules_table, chunks[0]);
+
+    let events: Vec<_> = app.alerts.events.iter().rev().collect();
+    let visible = chunks[1].height.saturating_sub(3) as usize;
+    let max_scroll = events.len().saturating_sub(visible.max(1));
+    app.alerts_scroll = app.alerts_scroll.min(max_scroll as u16);
+    let offset = app.alerts_scroll as usize;
+
+    let event_rows = events.iter().skip(offset).take(visible).map(|e| {
+        let (label, style) = match e.kind {
+            AlertEventKind::Fired => ("FIRED", Style::default().fg(Color::Red)),
+            AlertEventKind::Resolved => ("RESOLVED", Style::default().fg(Color::Green)),
+        };
+        Row::new(vec![
+            Cell::from(format_utc_timestamp(e.at_unix_ms)),
+            Cell::from(label).style(style),
+            Cell::from(trim_to(&e.name, 20)),
+            Cell::from(e.value.clone()),
+        ])
+    });
+    let history_title = if events.is_empty() {
+        "History (no events yet)".to_string()
+    } else {
+        format!("History ({} events, newest first)", events.len())
+    };
+    let events_table = Table::new(
+        event_rows,
+        [
+            Constraint::Length(21),
+            Constraint::Length(9),
+            Constraint::Length(20),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["TIME (UTC)", "EVENT", "NAME", "VALUE"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(history_title));
+    frame.render_widget(events_table, chunks[1]);
+}
+
+fn notify_status_line(app: &AppState) -> Line<'static> {
+    let cfg = &config().notify;
+    if !cfg.enabled() {
+        return Line::styled(
+            "Notify: off (set [notify] exec, webhook or syslog in the config)",
+            Style::default().fg(Color::DarkGray),
+        );
+    }
+    let mut sinks = Vec::new();
+    if !cfg.exec.is_empty() {
+        sinks.push(format!("exec {}", cfg.exec[0]));
+    }
+    if let Some(url) = &cfg.webhook {
+        sinks.push(format!("webhook {}", trim_to(url, 40)));
+    }
+    if cfg.syslog {
+        sinks.push("syslog".to_string());
+    }
+    let st = app.notify_state.inner.lock().unwrap();
+    let mut text = format!("Notify: {} · {} sent", sinks.join(", "), st.sent);
+    match &st.last_error {
+        Some(err) => {
+            text.push_str(&format!(", {} failed (last: {err})", st.failures));
+            Line::styled(text, Style::default().fg(Color::Yellow))
+        }
+        None => Line::styled(text, Style::default().fg(Color::Gray)),
+    }
+}
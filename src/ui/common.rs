@@ -11,20 +11,29 @@ use crate::utils::color_for_pct;
 
 pub fn render_header(app: &AppState) -> Paragraph<'static> {
     let (screen_name, screen_hint) = match app.screen {
-        Screen::Dashboard => ("Dashboard", "p: processes  d: disk  v: services  l: logs"),
+        Screen::Dashboard => (
+            "Dashboard",
+            "p: processes  d: disk  n: network  v: services  T: timers  l: logs  a: alerts",
+        ),
         Screen::Processes => (
             "Processes",
-            "Tab: CPU/Mem/Swap  k: kill  R: restart  Esc: back",
+            "Tab: CPU/Mem/Swap/IO  t: tree  Enter: details  k: kill  s: signal/nice  R: restart  Esc: back",
         ),
         Screen::DiskDive => ("Disk dive", "s: scan  Enter: open dir  ←: up  Tab: target"),
         Screen::Services => (
             "Services",
-            "Tab: filter  /: search  Enter/l: logs  r: refresh",
+            "Tab: filter  u: system/user  o: sort  /: search  i: details  Enter/l: logs  s: start/stop/restart",
+        ),
+        Screen::Timers => ("Timers", "↑/↓: select  Enter/l: service logs  r: refresh"),
+        Screen::Logs => (
+            "Logs",
+            "Tab: severity  u: unit filter  /: search  &: regex filter  Enter: fields  f: follow",
         ),
-        Screen::Logs => ("Logs", "Tab: severity  u: unit filter  r: refresh"),
+        Screen::Network => ("Network", "↑/↓: select interface  Esc: back"),
+        Screen::Alerts => ("Alerts", "↑/↓: scroll history  Esc: back"),
     };
 
-    Paragraph::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             "Ferromon",
             Style::default()
@@ -32,6 +41,25 @@ pub fn render_header(app: &AppState) -> Paragraph<'static> {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("  —  "),
+    ];
+    if let Some(replay) = app.replay {
+        spans.push(Span::styled(
+            format!(
+                "REPLAY {} {}/{} {} {}x",
+                format_replay_offset(replay.offset_ms),
+                replay.position,
+                replay.total,
+                if replay.playing { "▶" } else { "⏸" },
+                replay.speed
+            ),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("  "));
+    }
+    spans.extend([
         Span::styled(screen_name, Style::default().fg(Color::White)),
         Span::raw("  •  "),
         Span::styled(
@@ -43,26 +71,69 @@ pub fn render_header(app: &AppState) -> Paragraph<'static> {
         Span::raw("  •  "),
         Span::styled(app.hostname.clone(), Style::default().fg(Color::Yellow)),
         Span::raw("  •  "),
+    ]);
+    let firing: Vec<&str> = app.alerts.firing().map(|r| r.rule.name.as_str()).collect();
+    if !firing.is_empty() {
+        spans.push(Span::styled(
+            format!(
+                " ⚠ {} ALERT{}: {} ",
+                firing.len(),
+                if firing.len() == 1 { "" } else { "S" },
+                firing.join(", ")
+            ),
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("  •  "));
+    }
+    spans.extend([
         Span::styled("q", Style::default().fg(Color::Yellow)),
         Span::raw(": quit  "),
         Span::styled("?", Style::default().fg(Color::Yellow)),
         Span::raw(": help"),
-    ]))
+    ]);
+    Paragraph::new(Line::from(spans))
+}
+
+fn format_replay_offset(ms: u64) -> String {
+    let secs = ms / 1000;
+    if secs >= 3600 {
+        format!(
+            "+{}h{:02}m{:02}s",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        )
+    } else if secs >= 60 {
+        format!("+{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("+{secs}s")
+    }
 }
 
 pub fn render_footer(app: &AppState) -> Paragraph<'static> {
     let tips_dashboard = [
         "Tab: cycle dir target (CWD ↔ /var ↔ HOME ↔ /)",
         "f: toggle mount filter (filtered ↔ all)",
-        "p: processes · d: disk dive · v: services · l: logs",
+        "CPU signals: high iowait → disk trouble, high steal → noisy neighbour",
+        "p: processes · d: disk dive · n: network · v: services · T: timers · l: logs · a: alerts",
         "r: refresh now · ?: help",
         "Esc: back to dashboard",
     ];
 
     let tips_processes = [
-        "Tab: sort CPU → Mem → Swap → CPU",
-        "↑/↓: scroll · k: kill · R: restart",
+        "Tab: sort CPU → Mem → Swap → I/O → CPU",
+        "↑/↓: select · k: kill · R: restart",
+        "k then Tab: kill a whole subtree, process group or session",
+        "R relaunches with the same cwd, env and user; logs under ~/.local/state/ferromon",
+        "The selection sticks to its PID as rows re-sort",
+        "s: send any signal (STOP pauses, CONT resumes), renice or ionice",
+        "Enter: details (cmdline, cwd, env, fds, limits, cgroup)",
         "Swap column: Linux only (0 on macOS)",
+        "READ/s WRITE/s: storage I/O per process (Linux; other users' need root)",
+        "t: tree view · Space: fold subtree · / finds a process and its parents",
         "Esc: back",
     ];
 
@@ -79,15 +150,55 @@ pub fn render_footer(app: &AppState) -> Paragraph<'static> {
         "Esc clears search, then returns to dashboard",
         "Enter or l: open logs for selected unit",
         "↑/↓: select unit · r: refresh",
+        "s: start/stop/restart/reload/enable/disable the selected unit",
+        "o: sort by health/memory/CPU/I/O (from each unit's cgroup)",
+        "i: unit details, dependencies and the latest journal lines",
+        "u: system manager → user manager (systemctl --user) → both merged",
+    ];
+
+    let tips_timers = [
+        "Red rows: the activated service's last run did not succeed",
+        "Yellow rows: the timer is inactive and won't fire again",
+        "Enter or l: open logs for the service the timer runs",
+        "↑/↓: select timer · r: refresh",
     ];
 
     let tips_logs = [
         "Tab: cycle severity err+/warning+/info+/debug+",
         "u: selected unit ↔ all units",
         "↑/↓: scroll · r: refresh",
+        "f: follow new lines live; ↑ pauses auto-scroll, End resumes",
+        "Enter: every journal field of the selected entry (_PID, _CMDLINE, CODE_FILE…)",
+        "Rows are coloured by priority: red err+, yellow warning, cyan notice, grey debug",
+        "/: search messages as you type; n/N jump to the next/previous match",
+        "&: regex filter (journalctl --grep); &!pattern hides matching lines",
+    ];
+
+    let tips_network = [
+        "↑/↓: select interface to chart its RX/TX history",
+        "Red rows: new errors or drops since the last sample",
+        "Rates come from /proc/net/dev deltas each tick",
+        "Esc: back",
+    ];
+
+    let tips_alerts = [
+        "Rules live in [[alerts]] in config.toml",
+        "Pending rules fire once they have matched for their `for` duration",
+        "[notify] in config.toml sends fire/resolve events to a command, webhook or syslog",
+        "↑/↓: scroll history · Esc: back",
+    ];
+
+    let tips_replay = [
+        "Space: play/pause · ←/→ or ,/.: step one frame",
+        "[ / ]: seek 10 frames · g/G: start/end",
+        "+/-: playback speed · p/n/v/l: switch screen",
     ];
 
     let (label, tip) = match app.screen {
+        _ if app.replay.is_some() => (
+            "Replay",
+            tips_replay[(app.footer_tip_idx as usize) % tips_replay.len()].to_string(),
+        ),
         Screen::Dashboard => {
             let idx = app.footer_tip_idx as usize % (tips_dashboard.len() + 1);
             if idx == tips_dashboard.len() {
@@ -108,10 +219,22 @@ pub fn render_footer(app: &AppState) -> Paragraph<'static> {
             "Tip",
             tips_services[(app.footer_tip_idx as usize) % tips_services.len()].to_string(),
         ),
+        Screen::Timers => (
+            "Tip",
+            tips_timers[(app.footer_tip_idx as usize) % tips_timers.len()].to_string(),
+        ),
         Screen::Logs => (
             "Tip",
             tips_logs[(app.footer_tip_idx as usize) % tips_logs.len()].to_string(),
         ),
+        Screen::Network => (
+            "Tip",
+            tips_network[(app.footer_tip_idx as usize) % tips_network.len()].to_string(),
+        ),
+        Screen::Alerts => (
+            "Tip",
+            tips_alerts[(app.footer_tip_idx as usize) % tips_alerts.len()].to_string(),
+        ),
     };
 
     Paragraph::new(Line::from(vec![
@@ -132,28 +255,68 @@ pub fn render_help(app: &AppState) -> Paragraph<'static> {
         Line::from("  ? — toggle help"),
         Line::from("  Esc — back to dashboard"),
         Line::from("  r — refresh now"),
+        Line::from("  n — network"),
         Line::from("  v — services"),
+        Line::from("  T — timers"),
         Line::from("  l — logs"),
+        Line::from("  a — alerts"),
         Line::from(""),
     ];
 
+    if app.replay.is_some() {
+        lines.push(Line::from("Replay:"));
+        lines.push(Line::from("  Space — play/pause · ←/→ or ,/. — step"));
+        lines.push(Line::from("  [ / ] — seek 10 frames · g/G — start/end"));
+        lines.push(Line::from("  +/- — playback speed (0.25x to 16x)"));
+        lines.push(Line::from(
+            "  Disk dive, timers and dir sizes are not recorded",
+        ));
+        lines.push(Line::from(""));
+    }
+
     match app.screen {
         Screen::Dashboard => {
             lines.push(Line::from("Dashboard:"));
             lines.push(Line::from("  p — processes"));
             lines.push(Line::from("  d — disk dive"));
             lines.push(Line::from("  f — toggle mount filter (filtered ↔ all)"));
+            lines.push(Line::from(
+                "  PSI — % of time stalled on cpu/mem/io (avg10/avg60/avg300)",
+            ));
             lines.push(Line::from(
                 "  Tab — cycle dir target (CWD ↔ /var ↔ HOME ↔ /)",
             ));
         }
         Screen::Processes => {
             lines.push(Line::from("Processes:"));
-            lines.push(Line::from("  Tab — cycle CPU / Mem / Swap sort"));
-            lines.push(Line::from("  ↑/↓ — scroll · / — search"));
+            lines.push(Line::from("  Tab — cycle CPU / Mem / Swap / I/O sort"));
+            lines.push(Line::from(
+                "  ↑/↓ — select (follows the PID across refreshes) · / — search",
+            ));
             lines.push(Line::from("  k — kill selected (y=SIGTERM, K=SIGKILL)"));
-            lines.push(Line::from("  R — restart selected (SIGTERM then respawn)"));
+            lines.push(Line::from(
+                "  k then Tab — widen to subtree / process group / session (lists PIDs)",
+            ));
+            lines.push(Line::from(
+                "  s — actions: HUP/INT/QUIT/USR1/USR2/STOP/CONT/TERM/KILL, nice, ionice",
+            ));
+            lines.push(Line::from(
+                "  R — restart: same args, cwd, env and user; detached, output to a log",
+            ));
+            lines.push(Line::from(
+                "      (refused for systemd services — restart the unit instead)",
+            ));
+            lines.push(Line::from(
+                "  Enter — details: cmdline, cwd, env, fds, smaps, limits, cgroup",
+            ));
+            lines.push(Line::from("  t — tree view (ΣCPU/ΣMEM = whole subtree)"));
+            lines.push(Line::from(
+                "  tree: Space fold · ← collapse/parent · → expand · / reveals ancestors",
+            ));
             lines.push(Line::from("  Swap column: Linux only (macOS shows 0 B)"));
+            lines.push(Line::from(
+                "  READ/s WRITE/s: /proc/<pid>/io deltas (Linux; root for other users)",
+            ));
         }
         Screen::DiskDive => {
             lines.push(Line::from("Disk dive:"));
@@ -172,9 +335,33 @@ pub fn render_help(app: &AppState) -> Paragraph<'static> {
             lines.push(Line::from(
                 "  Backspace — edit search · Esc — clear search/back",
             ));
+            lines.push(Line::from("  o — sort by health / memory / CPU / I/O"));
+            lines.push(Line::from(
+                "  u — system ↔ user (systemctl --user) ↔ both, with a SCOPE column",
+            ));
             lines.push(Line::from("  ↑/↓ — select service"));
             lines.push(Line::from("  Enter / l — open logs for selected unit"));
+            lines.push(Line::from(
+                "  i — unit details: ExecStart, limits, dependencies, journal",
+            ));
+            lines.push(Line::from(
+                "  s — start / stop / restart / reload / enable / disable (confirms first)",
+            ));
             lines.push(Line::from("  r — refresh service list"));
+            lines.push(Line::from(
+                "  Updates live over D-Bus when available, else polls systemctl",
+            ));
+        }
+        Screen::Timers => {
+            lines.push(Line::from("Timers (Linux-only):"));
+            lines.push(Line::from("  ↑/↓ — select timer"));
+            lines.push(Line::from(
+                "  Enter / l — open logs for the service the timer activates",
+            ));
+            lines.push(Line::from("  r — refresh timer list"));
+            lines.push(Line::from(
+                "  red — last run failed · yellow — timer inactive",
+            ));
         }
         Screen::Logs => {
             lines.push(Line::from("Logs (Linux-only):"));
@@ -182,9 +369,46 @@ pub fn render_help(app: &AppState) -> Paragraph<'static> {
                 "  Tab — cycle severity (err+ ↔ warning+ ↔ info+ ↔ debug+)",
             ));
             lines.push(Line::from("  u — selected unit ↔ all units"));
-            lines.push(Line::from("  ↑/↓ — scroll"));
+            lines.push(Line::from("  ↑/↓ — select entry · End — newest"));
+            lines.push(Line::from(
+                "  / — incremental search (regex) · n/N — next/previous match · Esc clears",
+            ));
+            lines.push(Line::from(
+                "  & — regex filter on messages, !pattern excludes; empty pattern clears",
+            ));
+            lines.push(Line::from(
+                "  Enter — expand: all journal fields of the selected entry (Esc closes)",
+            ));
+            lines.push(Line::from(
+                "  f — follow: stream journalctl -f (or tail syslog), last 2000 lines",
+            ));
+            lines.push(Line::from(
+                "      ↑ pauses auto-scroll · End or scrolling to the bottom resumes",
+            ));
+            lines.push(Line::from("      stops when you leave the logs screen"));
             lines.push(Line::from("  r — refresh logs"));
         }
+        Screen::Network => {
+            lines.push(Line::from("Network (Linux-only):"));
+            lines.push(Line::from("  ↑/↓ — select interface"));
+            lines.push(Line::from(
+                "  RX/TX bytes/s and packets/s from /proc/net/dev deltas",
+            ));
+            lines.push(Line::from(
+                "  red rows — new errors or drops since last tick",
+            ));
+        }
+        Screen::Alerts => {
+            lines.push(Line::from("Alerts:"));
+            lines.push(Line::from("  ↑/↓ — scroll fire/resolve history"));
+            lines.push(Line::from(
+                "  rules: [[alerts]] rule = \"cpu > 90 for 60s\" in config.toml",
+            ));
+            lines.push(Line::from("  firing alerts also show in the header banner"));
+            lines.push(Line::from(
+                "  [notify] exec / webhook / syslog receive each fire and resolve as JSON",
+            ));
+        }
     }
 
     Paragraph::new(lines)
@@ -1,29 +1,90 @@
-use std::time::{Duration, Instant};
-
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::Alignment;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table, Wrap};
-use sysinfo::{Disks, System};
 
-use crate::system::{
-    collect_mount_rows, dash_target_path, disks_table_filtered, format_memory_pressure,
-    format_top_processes, format_uptime, scan_dir_quick,
-};
-use crate::types::{AppState, ProcSort, VmSnapshot};
+use crate::network::{net_rows_by_throughput, net_supported_message};
+use crate::system::{format_cpu_breakdown, format_psi_line, format_uptime};
+use crate::types::{AppState, VmSnapshot};
 use crate::ui::common::render_detail_panel;
 use crate::utils::{
     color_for_pct, format_bytes, format_rate, history_average, history_peak, trim_to,
 };
 
+/// Per-core bars when every core fits on its own row, otherwise a heatmap grid
+/// (one coloured cell per core) so a single pegged core on a big box stays visible.
+fn per_core_lines(cores: &[f32], width: u16, max_rows: usize) -> Vec<Line<'static>> {
+    // blank(1) + header(1) = 2 overhead rows
+    if cores.is_empty() || max_rows < 3 {
+        return Vec::new();
+    }
+    let body_rows = max_rows - 2;
+    let mut lines: Vec<Line<'static>> = vec![Line::from("")];
+
+    if cores.len() <= body_rows.min(24) {
+        lines.push(Line::from(Span::styled(
+            "Per-core",
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let bar_width = 16usize;
+        for (idx, pct) in cores.iter().enumerate() {
+            let pct = *pct as f64;
+            let filled = ((pct / 100.0) * bar_width as f64).round() as usize;
+            let empty = bar_width.saturating_sub(filled);
+            let bar = format!("{}{}", "█".repeat(filled), "░".repeat(empty));
+            let color = color_for_pct(pct);
+            lines.push(Line::from(vec![
+                Span::styled(format!("c{:<2} ", idx), Style::default().fg(Color::Gray)),
+                Span::styled(bar, Style::default().fg(color)),
+                Span::styled(format!(" {:>3.0}%", pct), Style::default().fg(color)),
+            ]));
+        }
+        return lines;
+    }
+
+    let (hot_idx, hot_pct) =
+        cores.iter().enumerate().fold(
+            (0, 0.0f32),
+            |acc, (i, p)| if *p > acc.1 { (i, *p) } else { acc },
+        );
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("Per-core ({})", cores.len()),
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  hot c{hot_idx} {hot_pct:.0}%"),
+            Style::default().fg(color_for_pct(hot_pct as f64)),
+        ),
+    ]));
+
+    // Two-column cells keep the grid readable; drop the gap when space is short.
+    let wide = (width as usize / 2).max(1);
+    let (per_row, cell) = if cores.len().div_ceil(wide) <= body_rows {
+        (wide, "▇ ")
+    } else {
+        ((width as usize).max(1), "▇")
+    };
+    for chunk in cores.chunks(per_row).take(body_rows) {
+        let spans: Vec<Span<'static>> = chunk
+            .iter()
+            .map(|pct| Span::styled(cell, Style::default().fg(color_for_pct(*pct as f64))))
+            .collect();
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
 pub fn render_dashboard(
     frame: &mut ratatui::Frame,
     area: Rect,
     vm: &VmSnapshot,
     app: &mut AppState,
-    system: &System,
-    disks: &Disks,
 ) {
     let panels = Layout::default()
         .direction(Direction::Horizontal)
@@ -34,30 +95,6 @@ pub fn render_dashboard(
         ])
         .split(area);
 
-    // --- dashboard quick-overview cache ---
-    // Keep this screen cheap: do tiny scans occasionally, not every frame.
-    let now = Instant::now();
-    let need_fs = match app.dash_last_fs_at {
-        Some(t) => t.elapsed() >= Duration::from_secs(5),
-        None => true,
-    };
-
-    if need_fs {
-        app.dash_top_cpu = format_top_processes(system, ProcSort::Cpu, 20);
-        app.dash_top_mem = format_top_processes(system, ProcSort::Mem, 20);
-        app.dash_mem_pressure = format_memory_pressure(system, 5);
-        app.dash_mount_rows = collect_mount_rows(12, app.dash_show_all_mounts)
-            .unwrap_or_else(|| disks_table_filtered(disks, 12, app.dash_show_all_mounts));
-        let (label, path) = dash_target_path(app.dash_dir_target);
-        app.dash_dir_sizes = scan_dir_quick(&path, 6);
-        if !app.dash_dir_sizes.is_empty() {
-            app.dash_dir_sizes.insert(0, label);
-        } else {
-            app.dash_dir_sizes = vec![label, "(no entries)".to_string()];
-        }
-        app.dash_last_fs_at = Some(now);
-    }
-
     // CPU
     let cpu_block = Block::default()
         .title("CPU")
@@ -150,36 +187,31 @@ pub fn render_dashboard(
         frame.render_widget(block.clone(), cpu_sections[1]);
         let inner = block.inner(cpu_sections[1]);
         if inner.width > 0 && inner.height > 0 {
-            let stat_strings = vec![
+            let mut stat_strings = vec![
                 format!("Now {:.1}%", vm.cpu_usage),
                 format!("Peak {}%", history_peak(&app.dash_cpu_history)),
                 format!("Recent avg {:.1}%", history_average(&app.dash_cpu_history)),
                 format!("Headroom {:.1}%", (100.0 - vm.cpu_usage as f64).max(0.0)),
             ];
-            let cpus = system.cpus();
-            let mut core_lines: Vec<Line> = Vec::new();
-            if !cpus.is_empty() {
-                let bar_width = 16usize;
-                core_lines.push(Line::from(""));
-                core_lines.push(Line::from(Span::styled(
-                    "Per-core",
-                    Style::default()
-                        .fg(Color::Gray)
-                        .add_modifier(Modifier::BOLD),
-                )));
-                for (idx, cpu) in cpus.iter().enumerate().take(24) {
-                    let pct = cpu.cpu_usage() as f64;
-                    let filled = ((pct / 100.0) * bar_width as f64).round() as usize;
-                    let empty = bar_width.saturating_sub(filled);
-                    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(empty));
-                    let color = color_for_pct(pct);
-                    core_lines.push(Line::from(vec![
-                        Span::styled(format!("c{:<2} ", idx), Style::default().fg(Color::Gray)),
-                        Span::styled(bar, Style::default().fg(color)),
-                        Span::styled(format!(" {:>3.0}%", pct), Style::default().fg(color)),
-                    ]));
+            match &vm.cpu_breakdown {
+                Some(b) => stat_strings.extend(format_cpu_breakdown(b)),
+                None if cfg!(target_os = "linux") => {
+                    stat_strings.push("CPU split: sampling…".to_string())
                 }
+                None => {}
+            }
+            if let Some(res) = &vm.psi.cpu {
+                stat_strings.push(format_psi_line(
+                    "cpu",
+                    res,
+                    history_peak(&app.dash_psi_cpu_history),
+                ));
             }
+            // Leave at least one row for the sparkline.
+            let core_rows = (inner.height as usize)
+                .saturating_sub(stat_strings.len())
+                .saturating_sub(1);
+            let core_lines = per_core_lines(&vm.core_usage, inner.width, core_rows);
             let stats_h = stat_strings.len() as u16;
             let cores_h = core_lines.len() as u16;
             let chunks = Layout::default()
@@ -338,23 +370,59 @@ pub fn render_dashboard(
     } else {
         "Swap off".to_string()
     });
+    if vm.psi.available() {
+        if let Some(res) = &vm.psi.memory {
+            memory_signals.push(format_psi_line(
+                "mem",
+                res,
+                history_peak(&app.dash_psi_mem_history),
+            ));
+            if let Some(full) = &res.full {
+                memory_signals.push(format!(
+                    "    full {:.1}/{:.1}/{:.1}",
+                    full.avg10, full.avg60, full.avg300
+                ));
+            }
+        }
+        if let Some(res) = &vm.psi.io {
+            memory_signals.push(format_psi_line(
+                "io",
+                res,
+                history_peak(&app.dash_psi_io_history),
+            ));
+        }
+    } else {
+        memory_signals.extend(app.dash_mem_pressure.iter().take(2).cloned());
+    }
+    // Without PSI the last rows are a process-based heuristic; say so in the title.
+    let signals_title = if vm.psi.available() {
+        "Signals"
+    } else {
+        "Signals (no PSI)"
+    };
     render_detail_panel(
         frame,
         memory_sections[1],
-        "Signals",
+        signals_title,
         memory_signals,
         &app.dash_mem_history,
         mem_pct_color,
     );
 
+    // Disk + network share the right-hand column.
+    let right_column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(66), Constraint::Percentage(34)])
+        .split(panels[2]);
+
     // Disk
     let disk_block = Block::default()
         .title("Disk")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Green));
-    frame.render_widget(disk_block.clone(), panels[2]);
+    frame.render_widget(disk_block.clone(), right_column[0]);
 
-    let disk_inner = disk_block.inner(panels[2]);
+    let disk_inner = disk_block.inner(right_column[0]);
     let disk_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(48), Constraint::Percentage(52)])
@@ -435,4 +503,58 @@ pub fn render_dashboard(
             .wrap(Wrap { trim: false }),
         disk_chunks[1],
     );
+
+    // Network
+    let net_block = Block::default()
+        .title("Network")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    if let Some(msg) = net_supported_message() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(msg, Style::default().fg(Color::Gray)))
+                .block(net_block)
+                .wrap(Wrap { trim: true }),
+            right_column[1],
+        );
+        return;
+    }
+
+    let net_rows = net_rows_by_throughput(&app.net_rows);
+    let net_table_rows = net_rows.iter().map(|r| {
+        let name_style = if r.errs_delta > 0 || r.drops_delta > 0 {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Row::new(vec![
+            Cell::from(Span::styled(trim_to(&r.name, 12), name_style)),
+            Cell::from(Span::styled(
+                format_rate(r.rx_bps),
+                Style::default().fg(Color::Cyan),
+            )),
+            Cell::from(Span::styled(
+                format_rate(r.tx_bps),
+                Style::default().fg(Color::Magenta),
+            )),
+            Cell::from(format!("{}/{}", r.errs, r.drops)),
+        ])
+    });
+    let net_table = Table::new(
+        net_table_rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Min(6),
+        ],
+    )
+    .header(
+        Row::new(vec!["IFACE", "RX/s", "TX/s", "ERR/DROP"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(net_block);
+    frame.render_widget(net_table, right_column[1]);
 }
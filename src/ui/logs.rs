@@ -1,13 +1,30 @@
+use std::collections::VecDeque;
+
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
+
+use regex::Regex;
 
+use crate::journal::priority_label;
+use crate::logsearch::search_regex;
 use crate::services::{log_severity_label, log_unit_filter_label};
-use crate::types::AppState;
+use crate::types::{AppState, LogEntry, LogUnitFilter};
+use crate::utils::trim_to;
 
 pub fn render_logs(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
-    let state = app.log_state.inner.lock().unwrap();
+    // Held for the whole draw: the buffer can be thousands of entries and
+    // only the visible rows are copied out.
+    let log_state = app.log_state.inner.clone();
+    let mut state = log_state.lock().unwrap();
+    // A paused selection stays on its entry as the full buffer drops old ones.
+    let dropped = std::mem::take(&mut state.dropped);
+    if !app.log_follow_pinned {
+        app.logs_scroll = app
+            .logs_scroll
+            .saturating_sub(dropped.min(u16::MAX as usize) as u16);
+    }
 
     if let Some(msg) = &state.unsupported {
         frame.render_widget(
@@ -30,10 +47,10 @@ pub fn render_logs(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
     } else {
         state.source.clone()
     };
-    let lines = state.lines.clone();
+    let entries = &state.entries;
     let err = state.error.clone();
     let running = state.running;
-    drop(state);
+    let following = state.following;
 
     let block = Block::default()
         .title("Logs")
@@ -46,52 +63,267 @@ pub fn render_logs(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
         .constraints([Constraint::Length(2), Constraint::Min(4)])
         .split(inner);
 
-    let header = Paragraph::new(vec![Line::from(vec![
-        Span::styled("Severity ", Style::default().fg(Color::Gray)),
-        Span::raw(log_severity_label(app.log_severity)),
-        Span::raw("  •  "),
-        Span::styled("Unit ", Style::default().fg(Color::Gray)),
-        Span::raw(log_unit_filter_label(
-            app.log_unit_filter,
-            app.log_selected_unit.as_deref(),
-        )),
-        Span::raw("  •  "),
-        Span::styled("Source ", Style::default().fg(Color::Gray)),
-        Span::raw(source),
-        if running {
-            Span::styled("  •  refreshing", Style::default().fg(Color::Yellow))
-        } else {
-            Span::raw("")
-        },
-    ])]);
+    let search = search_regex(&app.log_search);
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("Severity ", Style::default().fg(Color::Gray)),
+            Span::raw(log_severity_label(app.log_severity)),
+            Span::raw("  •  "),
+            Span::styled("Unit ", Style::default().fg(Color::Gray)),
+            Span::raw(log_unit_filter_label(
+                app.log_unit_filter,
+                app.log_selected_unit.as_deref(),
+            )),
+            if app.log_selected_user && app.log_unit_filter == LogUnitFilter::Selected {
+                Span::styled(" (user)", Style::default().fg(Color::Gray))
+            } else {
+                Span::raw("")
+            },
+            Span::raw("  •  "),
+            Span::styled("Source ", Style::default().fg(Color::Gray)),
+            Span::raw(source),
+            if following && app.log_follow_pinned {
+                Span::styled("  •  following", Style::default().fg(Color::Green))
+            } else if following {
+                Span::styled(
+                    "  •  following, scroll paused (End resumes)",
+                    Style::default().fg(Color::Yellow),
+                )
+            } else if running {
+                Span::styled("  •  refreshing", Style::default().fg(Color::Yellow))
+            } else {
+                Span::raw("")
+            },
+        ]),
+        search_line(app, entries, search.as_ref()),
+    ]);
     frame.render_widget(header, chunks[0]);
 
-    let body_lines = if let Some(err) = err {
-        vec![
-            Line::from(vec![
-                Span::styled("Error: ", Style::default().fg(Color::Red)),
-                Span::raw(err),
-            ]),
-            Line::from("Try switching to all units with `u` or refreshing with `r`."),
-        ]
-    } else if lines.is_empty() {
-        vec![
-            Line::from("No log lines matched the current filters."),
-            Line::from("Try `Tab` for severity or `u` for all units."),
-        ]
+    let (table_area, fields_area) = if app.log_expanded {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(6), Constraint::Length(12)])
+            .split(chunks[1]);
+        (split[0], Some(split[1]))
+    } else {
+        (chunks[1], None)
+    };
+
+    if err.is_some() || entries.is_empty() {
+        let body_lines = if let Some(err) = err {
+            vec![
+                Line::from(vec![
+                    Span::styled("Error: ", Style::default().fg(Color::Red)),
+                    Span::raw(err),
+                ]),
+                Line::from("Try switching to all units with `u` or refreshing with `r`."),
+            ]
+        } else {
+            vec![
+                Line::from("No log lines matched the current filters."),
+                Line::from(if app.log_filter.is_some() {
+                    "Try `Tab` for severity, `u` for all units or `Esc` to drop the regex filter."
+                } else {
+                    "Try `Tab` for severity or `u` for all units."
+                }),
+            ]
+        };
+        frame.render_widget(
+            Paragraph::new(body_lines)
+                .block(Block::default().borders(Borders::ALL).title("Recent lines"))
+                .wrap(Wrap { trim: false }),
+            table_area,
+        );
+        return;
+    }
+
+    let last = entries.len() - 1;
+    let selected_idx = if following && app.log_follow_pinned {
+        last
     } else {
-        lines.into_iter().map(Line::from).collect::<Vec<Line>>()
+        (app.logs_scroll as usize).min(last)
+    };
+    // Moving back down to the newest entry resumes auto-scroll.
+    if following && selected_idx == last {
+        app.log_follow_pinned = true;
+    }
+    app.logs_scroll = selected_idx as u16;
+    let visible = table_area.height.saturating_sub(3) as usize;
+    let offset = selected_idx.saturating_sub(visible.saturating_sub(1));
+    let slice = entries.iter().skip(offset).take(visible.max(1));
+
+    let table_rows = slice.enumerate().map(|(i, entry)| {
+        let base_style = priority_style(entry.priority);
+        let style = if offset + i == selected_idx {
+            base_style
+                .bg(Color::White)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            base_style
+        };
+        let source = if entry.unit.is_empty() {
+            &entry.identifier
+        } else {
+            &entry.unit
+        };
+        Row::new(vec![
+            Cell::from(entry.timestamp.clone()),
+            Cell::from(priority_label(entry.priority)),
+            Cell::from(trim_to(source, 24)),
+            Cell::from(entry.pid.map(|p| p.to_string()).unwrap_or_default()),
+            Cell::from(Line::from(highlight_matches(
+                &entry.message,
+                search.as_ref(),
+            ))),
+        ])
+        .style(style)
+    });
+
+    let title = match (following, app.log_follow_pinned) {
+        (false, _) => "Recent lines",
+        (true, true) => "Following",
+        (true, false) => "Following (paused)",
     };
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Length(20),
+            Constraint::Length(7),
+            Constraint::Length(24),
+            Constraint::Length(7),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec!["TIME", "PRI", "UNIT", "PID", "MESSAGE"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(table, table_area);
 
-    let scroll = app
-        .logs_scroll
-        .min((body_lines.len().saturating_sub(chunks[1].height as usize)) as u16);
-    app.logs_scroll = scroll;
-    frame.render_widget(
-        Paragraph::new(body_lines)
-            .block(Block::default().borders(Borders::ALL).title("Recent lines"))
-            .scroll((scroll, 0))
-            .wrap(Wrap { trim: false }),
-        chunks[1],
-    );
+    if let Some(area) = fields_area {
+        let entry = &entries[selected_idx];
+        let key = Style::default().fg(Color::Gray);
+        let mut lines = vec![Line::from(vec![
+            Span::styled("MESSAGE=", key),
+            Span::raw(entry.message.clone()),
+        ])];
+        lines.extend(
+            entry
+                .fields
+                .iter()
+                .filter(|(k, _)| k != "MESSAGE")
+                .map(|(k, v)| {
+                    Line::from(vec![
+                        Span::styled(format!("{k}="), key),
+                        Span::raw(v.clone()),
+                    ])
+                }),
+        );
+        if entry.fields.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "(syslog line; no journal fields)",
+                key,
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Entry fields (Enter/Esc to close)"),
+                )
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+}
+
+/// Second header line: the search or filter prompt while typing, else
+/// what's in effect.
+fn search_line(
+    app: &AppState,
+    entries: &VecDeque<LogEntry>,
+    search: Option<&Regex>,
+) -> Line<'static> {
+    let key = Style::default().fg(Color::Gray);
+    if app.log_search_active {
+        return Line::from(vec![
+            Span::styled("Search ", key),
+            Span::raw(format!("/{}_", app.log_search)),
+            Span::styled("  (Enter keeps, Esc clears)", key),
+        ]);
+    }
+    if app.log_filter_active {
+        let mut spans = vec![
+            Span::styled("Filter ", key),
+            Span::raw(format!("&{}_", app.log_filter_input)),
+        ];
+        spans.push(match &app.log_filter_error {
+            Some(err) => Span::styled(format!("  {err}"), Style::default().fg(Color::Red)),
+            None => Span::styled("  (regex, !pattern excludes, Enter applies)", key),
+        });
+        return Line::from(spans);
+    }
+
+    let mut spans = Vec::new();
+    if let Some(re) = search {
+        let matches = entries.iter().filter(|e| re.is_match(&e.message)).count();
+        spans.push(Span::styled("Search ", key));
+        spans.push(Span::raw(format!("/{}", trim_to(&app.log_search, 30))));
+        spans.push(Span::styled(
+            format!(" ({matches} matches, n/N)"),
+            if matches == 0 {
+                Style::default().fg(Color::Yellow)
+            } else {
+                key
+            },
+        ));
+    }
+    if let Some(filter) = &app.log_filter {
+        if !spans.is_empty() {
+            spans.push(Span::raw("  •  "));
+        }
+        spans.push(Span::styled(
+            if filter.exclude { "Hiding " } else { "Filter " },
+            key,
+        ));
+        spans.push(Span::raw(trim_to(&filter.input, 30)));
+    }
+    if spans.is_empty() {
+        spans.push(Span::styled("/ search · & regex filter", key));
+    }
+    Line::from(spans)
+}
+
+/// Splits a message into spans with search matches picked out.
+fn highlight_matches(text: &str, search: Option<&Regex>) -> Vec<Span<'static>> {
+    let Some(re) = search else {
+        return vec![Span::raw(text.to_string())];
+    };
+    let hit = Style::default().bg(Color::Yellow).fg(Color::Black);
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for m in re.find_iter(text).filter(|m| !m.is_empty()) {
+        spans.push(Span::raw(text[last..m.start()].to_string()));
+        spans.push(Span::styled(m.as_str().to_string(), hit));
+        last = m.end();
+    }
+    spans.push(Span::raw(text[last..].to_string()));
+    spans
+}
+
+fn priority_style(priority: Option<u8>) -> Style {
+    match priority {
+        Some(0..=2) => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        Some(3) => Style::default().fg(Color::Red),
+        Some(4) => Style::default().fg(Color::Yellow),
+        Some(5) => Style::default().fg(Color::Cyan),
+        Some(7) => Style::default().fg(Color::DarkGray),
+        _ => Style::default(),
+    }
 }
@@ -1,13 +1,23 @@
+pub mod alerts;
 pub mod common;
 pub mod dashboard;
 pub mod disk_dive;
 pub mod logs;
+pub mod network;
+pub mod process_detail;
 pub mod processes;
+pub mod service_detail;
 pub mod services;
+pub mod timers;
 
+pub use alerts::render_alerts;
 pub use common::{render_footer, render_header, render_help, render_too_small};
 pub use dashboard::render_dashboard;
 pub use disk_dive::render_disk_dive;
 pub use logs::render_logs;
+pub use network::render_network;
+pub use process_detail::render_process_detail;
 pub use processes::render_processes;
+pub use service_detail::render_service_detail;
 pub use services::render_services;
+pub use timers::render_timers;
@@ -0,0 +1,168 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table};
+
+use crate::network::net_supported_message;
+use crate::types::AppState;
+use crate::utils::{format_bytes, format_rate, trim_to};
+
+pub fn render_network(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
+    if let Some(msg) = net_supported_message() {
+        frame.render_widget(
+            Paragraph::new(vec![Line::from("Network"), Line::from(""), Line::from(msg)])
+                .block(Block::default().title("Network").borders(Borders::ALL))
+                .alignment(ratatui::prelude::Alignment::Center),
+            area,
+        );
+        return;
+    }
+
+    let mut rows = app.net_rows.clone();
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let block = Block::default()
+        .title("Network")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    frame.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(6),
+            Constraint::Length(7),
+        ])
+        .split(inner);
+
+    let total_rx: u64 = rows.iter().map(|r| r.rx_bps).sum();
+    let total_tx: u64 = rows.iter().map(|r| r.tx_bps).sum();
+    let summary = Paragraph::new(vec![Line::from(vec![
+        Span::styled("Interfaces ", Style::default().fg(Color::Gray)),
+        Span::raw(rows.len().to_string()),
+        Span::raw("  •  "),
+        Span::styled("RX ", Style::default().fg(Color::Gray)),
+        Span::styled(format_rate(total_rx), Style::default().fg(Color::Cyan)),
+        Span::raw("  "),
+        Span::styled("TX ", Style::default().fg(Color::Gray)),
+        Span::styled(format_rate(total_tx), Style::default().fg(Color::Magenta)),
+        Span::raw("  •  "),
+        Span::styled("source /proc/net/dev", Style::default().fg(Color::Gray)),
+    ])]);
+    frame.render_widget(summary, chunks[0]);
+
+    let visible = chunks[1].height.saturating_sub(3) as usize;
+    let selected_idx = (app.net_scroll as usize).min(rows.len().saturating_sub(1));
+    app.net_scroll = selected_idx as u16;
+    let offset = selected_idx.saturating_sub(visible.saturating_sub(1));
+    let slice = &rows[offset..rows.len().min(offset + visible.max(1))];
+
+    let table_rows = slice.iter().enumerate().map(|(i, r)| {
+        let absolute_idx = offset + i;
+        let alert = r.errs_delta > 0 || r.drops_delta > 0;
+        let base_style = if alert {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let style = if absolute_idx == selected_idx {
+            base_style
+                .bg(Color::LightCyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            base_style
+        };
+        Row::new(vec![
+            Cell::from(trim_to(&r.name, 16)),
+            Cell::from(format_rate(r.rx_bps)),
+            Cell::from(format_rate(r.tx_bps)),
+            Cell::from(r.rx_pps.to_string()),
+            Cell::from(r.tx_pps.to_string()),
+            Cell::from(r.errs.to_string()),
+            Cell::from(r.drops.to_string()),
+            Cell::from(format!(
+                "{}/{}",
+                format_bytes(r.rx_total),
+                format_bytes(r.tx_total)
+            )),
+        ])
+        .style(style)
+    });
+
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Min(16),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "IFACE",
+            "RX/s",
+            "TX/s",
+            "RXpk/s",
+            "TXpk/s",
+            "ERRS",
+            "DROPS",
+            "RX/TX TOTAL",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Interfaces"));
+    frame.render_widget(table, chunks[1]);
+
+    let Some(selected) = rows.get(selected_idx) else {
+        frame.render_widget(
+            Paragraph::new("No interfaces found.")
+                .block(Block::default().borders(Borders::ALL).title("History")),
+            chunks[2],
+        );
+        return;
+    };
+
+    let history = app
+        .net_history
+        .get(&selected.name)
+        .cloned()
+        .unwrap_or_default();
+    let spark_cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[2]);
+    for (col, title, data, color) in [
+        (
+            spark_cols[0],
+            format!("{} RX  {}", selected.name, format_rate(selected.rx_bps)),
+            history.rx,
+            Color::Cyan,
+        ),
+        (
+            spark_cols[1],
+            format!("{} TX  {}", selected.name, format_rate(selected.tx_bps)),
+            history.tx,
+            Color::Magenta,
+        ),
+    ] {
+        let data: Vec<u64> = data.into_iter().collect();
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .data(&data)
+                .style(Style::default().fg(color)),
+            col,
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::procinfo::ProcDetail;
+use crate::types::AppState;
+use crate::ui::processes::proc_message_hint;
+use crate::utils::{format_bytes, format_duration_short, format_utc_timestamp};
+
+pub fn section(title: String) -> Line<'static> {
+    Line::from(Span::styled(
+        title,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+pub fn field(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("  {label:<11}"), Style::default().fg(Color::Gray)),
+        Span::raw(value),
+    ])
+}
+
+pub fn unavailable(reason: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("  {reason}"),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
+
+fn detail_lines(d: &ProcDetail) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let path = |p: &Option<std::path::PathBuf>| {
+        p.as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "?".to_string())
+    };
+
+    lines.push(field(
+        "Command",
+        if d.cmdline.is_empty() {
+            format!("[{}]", d.name)
+        } else {
+            d.cmdline.join(" ")
+        },
+    ));
+    lines.push(field("Executable", path(&d.exe)));
+    lines.push(field("CWD", path(&d.cwd)));
+    lines.push(field("User", format!("{}  group {}", d.user, d.group)));
+    lines.push(field(
+        "Started",
+        format!(
+            "{} ({} ago)",
+            format_utc_timestamp(d.start_time_unix * 1000),
+            format_duration_short(d.run_time_secs)
+        ),
+    ));
+    lines.push(field(
+        "Parent",
+        d.ppid
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+    ));
+    lines.push(field(
+        "State",
+        format!(
+            "{}  threads {}",
+            d.status,
+            d.threads
+                .map(|t| t.to_string())
+                .unwrap_or_else(|| "?".to_string())
+        ),
+    ));
+    lines.push(field(
+        "OOM score",
+        match (d.oom_score, d.oom_score_adj) {
+            (Some(s), Some(adj)) => format!("{s} (adj {adj})"),
+            (Some(s), None) => s.to_string(),
+            _ => "n/a".to_string(),
+        },
+    ));
+    match &d.cgroup {
+        Ok(cg) if !cg.is_empty() => {
+            for (i, c) in cg.iter().enumerate() {
+                lines.push(field(if i == 0 { "Cgroup" } else { "" }, c.clone()));
+            }
+        }
+        Ok(_) => lines.push(field("Cgroup", "-".to_string())),
+        Err(e) => lines.push(field("Cgroup", e.clone())),
+    }
+
+    lines.push(Line::from(""));
+    lines.push(section("Memory (smaps_rollup)".to_string()));
+    match &d.memory {
+        Ok(m) => lines.push(Line::from(format!(
+            "  RSS {}  PSS {}  USS {}  Swap {}",
+            format_bytes(m.rss),
+            format_bytes(m.pss),
+            format_bytes(m.uss),
+            format_bytes(m.swap)
+        ))),
+        Err(e) => lines.push(unavailable(e)),
+    }
+
+    lines.push(Line::from(""));
+    lines.push(section("Namespaces".to_string()));
+    match &d.namespaces {
+        Ok(ns) => {
+            for chunk in ns.chunks(3) {
+                let text: Vec<String> = chunk.iter().map(|(n, t)| format!("{n:<7} {t}")).collect();
+                lines.push(Line::from(format!("  {}", text.join("   "))));
+            }
+        }
+        Err(e) => lines.push(unavailable(e)),
+    }
+
+    lines.push(Line::from(""));
+    lines.push(section("Limits (soft / hard)".to_string()));
+    match &d.limits {
+        Ok(limits) => {
+            for l in limits {
+                lines.push(Line::from(format!(
+                    "  {:<26}{:>14} / {:<14}{}",
+                    l.name, l.soft, l.hard, l.units
+                )));
+            }
+        }
+        Err(e) => lines.push(unavailable(e)),
+    }
+
+    lines.push(Line::from(""));
+    match &d.fds {
+        Ok(fds) => {
+            lines.push(section(format!("Open files ({})", fds.len())));
+            for (fd, target) in fds {
+                lines.push(Line::from(format!("  {fd:>5}  {target}")));
+            }
+        }
+        Err(e) => {
+            lines.push(section("Open files".to_string()));
+            lines.push(unavailable(e));
+        }
+    }
+
+    lines.push(Line::from(""));
+    match &d.environ {
+        Ok(env) => {
+            lines.push(section(format!("Environment ({})", env.len())));
+            for var in env {
+                lines.push(Line::from(format!("  {var}")));
+            }
+        }
+        Err(e) => {
+            lines.push(section("Environment".to_string()));
+            lines.push(unavailable(e));
+        }
+    }
+    lines
+}
+
+pub fn render_process_detail(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
+    let pid = app.proc_detail_pid.unwrap_or_default();
+    let state = app.proc_detail_state.inner.lock().unwrap();
+    let (title, lines) = match &state.detail {
+        Some(Ok(d)) => (format!("Process {} — {}", d.pid, d.name), detail_lines(d)),
+        Some(Err(e)) => (format!("Process {pid}"), vec![unavailable(e)]),
+        None => (format!("Process {pid}"), vec![unavailable("loading…")]),
+    };
+    drop(state);
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let body = Rect {
+        height: inner.height.saturating_sub(1),
+        ..inner
+    };
+    // Wrapped rows, so the end of a long environment stays reachable.
+    let rows: u16 = lines
+        .iter()
+        .map(|l| (l.width().max(1) as u16).div_ceil(body.width.max(1)))
+        .sum();
+    let max_scroll = rows.saturating_sub(body.height);
+    app.proc_detail_scroll = app.proc_detail_scroll.min(max_scroll);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((app.proc_detail_scroll, 0)),
+        body,
+    );
+
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓ PgUp/PgDn", Style::default().fg(Color::Yellow)),
+        Span::raw(" scroll · "),
+        Span::styled("k", Style::default().fg(Color::Red)),
+        Span::raw(" kill · "),
+        Span::styled("s", Style::default().fg(Color::Yellow)),
+        Span::raw(" signal/nice · "),
+        Span::styled("Esc/←", Style::default().fg(Color::Yellow)),
+        Span::raw(" back to list · refreshes every 2s"),
+    ]));
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    frame.render_widget(proc_message_hint(app).unwrap_or(hint), hint_area);
+}
@@ -1,53 +1,77 @@
-use std::cmp::Reverse;
-
 use ratatui::layout::{Constraint, Rect};
 use ratatui::prelude::Alignment;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
-use sysinfo::System;
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap};
 
-use crate::services::filtered_proc_rows;
+use crate::procaction::{
+    IoClass, KillConfirm, ProcActionMenu, MENU_IONICE, MENU_NICE, MENU_SIGNALS,
+};
+use crate::procrestart::RestartConfirm;
+use crate::proctree::TreeRow;
+use crate::procview::{sync_proc_selection, ProcView, ProcViewRows, PROC_LIST_LIMIT};
 use crate::types::{AppState, ProcRow, ProcSort};
-use crate::utils::{centered_rect, format_bytes, trim_to};
+use crate::ui::render_process_detail;
+use crate::utils::{centered_rect, format_bytes, format_rate, trim_to};
 
-pub fn render_processes(
-    frame: &mut ratatui::Frame,
-    area: Rect,
-    app: &mut AppState,
-    system: &System,
-) {
-    let mut procs: Vec<ProcRow> = system
-        .processes()
-        .iter()
-        .map(|(pid, p)| ProcRow::from_process(*pid, p))
-        .collect();
-
-    // Sort by current mode
-    match app.proc_sort {
-        ProcSort::Cpu => procs.sort_by_key(|p| Reverse((p.cpu_x10 as i64, p.mem_bytes as i64))),
-        ProcSort::Mem => procs.sort_by_key(|p| Reverse((p.mem_bytes as i64, p.cpu_x10 as i64))),
-        ProcSort::Swap => procs.sort_by_key(|p| Reverse((p.swap_bytes as i64, p.mem_bytes as i64))),
+pub fn render_processes(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
+    if app.proc_detail_pid.is_some() {
+        render_process_detail(frame, area, app);
+    } else {
+        let view = ProcView::build(app);
+        let selected = sync_proc_selection(app, &view);
+        match &view.rows {
+            ProcViewRows::Tree(rows) => render_process_tree(frame, area, app, rows, selected),
+            ProcViewRows::List(rows) => render_process_list(frame, area, app, rows, selected),
+        }
     }
+    render_process_overlays(frame, area, app);
+}
 
-    // In Swap mode show only processes that are actually using swap.
-    if matches!(app.proc_sort, ProcSort::Swap) {
-        procs.retain(|p| p.swap_bytes > 0);
+/// Scrolls just enough to keep the selected row on screen.
+fn scroll_to_selection(
+    app: &mut AppState,
+    selected: Option<usize>,
+    visible: usize,
+    len: usize,
+) -> usize {
+    let visible = visible.max(1);
+    let mut offset = (app.proc_scroll as usize).min(len.saturating_sub(visible));
+    if let Some(sel) = selected {
+        if sel < offset {
+            offset = sel;
+        } else if sel >= offset + visible {
+            offset = sel + 1 - visible;
+        }
     }
+    app.proc_scroll = offset as u16;
+    offset
+}
 
-    // Only show top N, but allow scrolling within that list
-    let max_rows = 200usize;
-    if procs.len() > max_rows {
-        procs.truncate(max_rows);
+/// Outcome of the last process action, shown in place of the hint for a few seconds.
+pub fn proc_message_hint(app: &AppState) -> Option<Paragraph<'static>> {
+    let (msg, at) = app.proc_message.as_ref()?;
+    if at.elapsed().as_secs() >= 5 {
+        return None;
     }
+    Some(Paragraph::new(Line::from(Span::styled(
+        msg.clone(),
+        Style::default().fg(Color::Yellow),
+    ))))
+}
 
-    // Apply search filter; when active, results are sorted by name for stability
-    let procs = filtered_proc_rows(procs, &app.proc_search);
-
+fn render_process_list(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    app: &mut AppState,
+    procs: &[ProcRow],
+    selected: Option<usize>,
+) {
     let sort_label = match app.proc_sort {
         ProcSort::Cpu => "CPU",
         ProcSort::Mem => "Mem",
         ProcSort::Swap => "Swap",
+        ProcSort::Io => "I/O",
     };
     let header_title = if app.proc_search_active {
         format!(
@@ -60,6 +84,7 @@ pub fn render_processes(
             ProcSort::Cpu => "Top processes (CPU)".to_string(),
             ProcSort::Mem => "Top processes (Memory)".to_string(),
             ProcSort::Swap => "Top processes (Swap)".to_string(),
+            ProcSort::Io => "Top processes (Disk I/O)".to_string(),
         }
     } else {
         format!(
@@ -79,15 +104,15 @@ pub fn render_processes(
     let inner = block.inner(area);
 
     let visible = (inner.height.saturating_sub(2)) as usize; // table header + footer-ish
-    let offset = app.proc_scroll as usize;
-    let offset = offset.min(procs.len().saturating_sub(1));
+    let offset = scroll_to_selection(app, selected, visible, procs.len());
 
     let slice = &procs[offset..procs.len().min(offset + visible.max(1))];
 
     // Calculate available width for process name column
     let show_swap = matches!(app.proc_sort, ProcSort::Swap);
-    // swap mode: PID(8)+SWAP(14)+STATE(7)=29; normal: PID(8)+CPU(10)+MEM(14)+STATE(7)=39
-    let fixed: u16 = if show_swap { 29 } else { 39 };
+    // swap mode: PID(8)+SWAP(14)+STATE(7)=29;
+    // normal: PID(8)+CPU(10)+MEM(10)+READ(9)+WRITE(9)+STATE(7)=53
+    let fixed: u16 = if show_swap { 29 } else { 53 };
     let name_width = ((inner.width.saturating_sub(fixed)) as usize).max(16);
 
     let rows = slice.iter().enumerate().map(|(i, p)| {
@@ -110,11 +135,13 @@ pub fn render_processes(
                 Cell::from(trim_to(&p.name, name_width)),
                 Cell::from(format!("{:.1}%", p.cpu_x10 as f64 / 10.0)),
                 Cell::from(format_bytes(p.mem_bytes)),
+                Cell::from(format_rate(p.read_bps)),
+                Cell::from(format_rate(p.write_bps)),
                 Cell::from(p.status).style(Style::default().fg(state_color)),
             ]
         };
         let row = Row::new(cells);
-        if i == 0 {
+        if Some(offset + i) == selected {
             row.style(Style::default().fg(Color::Black).bg(Color::Cyan))
         } else {
             row
@@ -137,10 +164,12 @@ pub fn render_processes(
                 Constraint::Length(8),
                 Constraint::Min(20),
                 Constraint::Length(10),
-                Constraint::Length(14),
+                Constraint::Length(10),
+                Constraint::Length(9),
+                Constraint::Length(9),
                 Constraint::Length(7),
             ],
-            vec!["PID", "NAME", "CPU", "MEM", "STATE"],
+            vec!["PID", "NAME", "CPU", "MEM", "READ/s", "WRITE/s", "STATE"],
         )
     };
 
@@ -181,19 +210,28 @@ pub fn render_processes(
     } else {
         Paragraph::new(Line::from(vec![
             Span::styled("Tab", Style::default().fg(Color::Yellow)),
-            Span::raw(" CPU/Mem/Swap · "),
+            Span::raw(" CPU/Mem/Swap/IO · "),
             Span::styled("↑/↓", Style::default().fg(Color::Yellow)),
-            Span::raw(" scroll · "),
+            Span::raw(" select · "),
             Span::styled("/", Style::default().fg(Color::Yellow)),
             Span::raw(" search · "),
+            Span::styled("t", Style::default().fg(Color::Yellow)),
+            Span::raw(" tree · "),
             Span::styled("k", Style::default().fg(Color::Red)),
             Span::raw(" kill · "),
+            Span::styled("s", Style::default().fg(Color::Yellow)),
+            Span::raw(" signal · "),
             Span::styled("R", Style::default().fg(Color::Yellow)),
             Span::raw(" restart · top "),
-            Span::styled(max_rows.to_string(), Style::default().fg(Color::White)),
+            Span::styled(
+                PROC_LIST_LIMIT.to_string(),
+                Style::default().fg(Color::White),
+            ),
         ]))
     };
-    let hint = hint.alignment(Alignment::Left);
+    let hint = proc_message_hint(app)
+        .unwrap_or(hint)
+        .alignment(Alignment::Left);
 
     let hint_area = Rect {
         x: inner.x,
@@ -202,90 +240,421 @@ pub fn render_processes(
         height: 1,
     };
     frame.render_widget(hint, hint_area);
+}
+
+fn render_process_tree(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    app: &mut AppState,
+    rows: &[TreeRow],
+    selected: Option<usize>,
+) {
+    let sort_label = match app.proc_sort {
+        ProcSort::Cpu => "CPU",
+        ProcSort::Mem => "Mem",
+        ProcSort::Swap => "Swap",
+        ProcSort::Io => "I/O",
+    };
+    let title = if app.proc_search_active {
+        format!(
+            "Process tree ({})  /{}_",
+            sort_label,
+            trim_to(&app.proc_search, 24)
+        )
+    } else if app.proc_search.is_empty() {
+        format!("Process tree ({}, {} processes)", sort_label, rows.len())
+    } else {
+        let matches = rows.iter().filter(|r| r.matched).count();
+        format!(
+            "Process tree ({})  /{}  ({} match{})",
+            sort_label,
+            trim_to(&app.proc_search, 24),
+            matches,
+            if matches == 1 { "" } else { "es" }
+        )
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+
+    let visible = (inner.height.saturating_sub(2)) as usize;
+    let offset = scroll_to_selection(app, selected, visible, rows.len());
+    let slice = &rows[offset..rows.len().min(offset + visible.max(1))];
+
+    // PID(8)+CPU(7)+ΣCPU(8)+MEM(10)+ΣMEM(10)+STATE(7)=50
+    let name_width = (inner.width.saturating_sub(50) as usize).max(16);
+
+    let table_rows = slice.iter().enumerate().map(|(i, r)| {
+        let p = &r.row;
+        let marker = if r.collapsed {
+            "▸ "
+        } else if r.children > 0 {
+            "▾ "
+        } else {
+            "  "
+        };
+        let mut name = format!("{}{}{}", r.prefix, marker, p.name);
+        if r.collapsed {
+            name.push_str(&format!(" (+{})", r.descendants));
+        }
+        let state_color = match p.status {
+            "Run" => Color::Green,
+            "Zombie" | "Dead" => Color::Red,
+            "Stop" => Color::Yellow,
+            _ => Color::DarkGray,
+        };
+        let (own, total) = match app.proc_sort {
+            ProcSort::Swap => (
+                format_bytes(p.swap_bytes),
+                format_bytes(r.subtree_swap_bytes),
+            ),
+            ProcSort::Io => (format_rate(p.io_bps()), format_rate(r.subtree_io_bps)),
+            _ => (format_bytes(p.mem_bytes), format_bytes(r.subtree_mem_bytes)),
+        };
+        let row = Row::new(vec![
+            Cell::from(p.pid.to_string()),
+            Cell::from(trim_to(&name, name_width)),
+            Cell::from(format!("{:.1}%", p.cpu_x10 as f64 / 10.0)),
+            Cell::from(format!("{:.1}%", r.subtree_cpu_x10 as f64 / 10.0)),
+            Cell::from(own),
+            Cell::from(total),
+            Cell::from(p.status).style(Style::default().fg(state_color)),
+        ]);
+        if Some(offset + i) == selected {
+            row.style(Style::default().fg(Color::Black).bg(Color::Cyan))
+        } else if !r.matched {
+            row.style(Style::default().fg(Color::DarkGray))
+        } else {
+            row
+        }
+    });
+
+    let (mem_header, mem_total_header) = match app.proc_sort {
+        ProcSort::Swap => ("SWAP", "ΣSWAP"),
+        ProcSort::Io => ("IO/s", "ΣIO/s"),
+        _ => ("MEM", "ΣMEM"),
+    };
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Length(8),
+            Constraint::Min(16),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(7),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "PID",
+            "NAME",
+            "CPU",
+            "ΣCPU",
+            mem_header,
+            mem_total_header,
+            "STATE",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(block);
+    frame.render_widget(table, area);
+
+    let hint = if app.proc_search_active {
+        Paragraph::new(Line::from(vec![
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" confirm · "),
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(" clear · matches shown with their ancestors"),
+        ]))
+    } else {
+        Paragraph::new(Line::from(vec![
+            Span::styled("Space", Style::default().fg(Color::Yellow)),
+            Span::raw(" fold · "),
+            Span::styled("←/→", Style::default().fg(Color::Yellow)),
+            Span::raw(" collapse/expand · "),
+            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::raw(" find · "),
+            Span::styled("Tab", Style::default().fg(Color::Yellow)),
+            Span::raw(" sort · "),
+            Span::styled("t", Style::default().fg(Color::Yellow)),
+            Span::raw(" flat list"),
+        ]))
+    };
+    let hint_area = Rect {
+        x: inner.x,
+        y: inner.y + inner.height.saturating_sub(1),
+        width: inner.width,
+        height: 1,
+    };
+    let hint = proc_message_hint(app).unwrap_or(hint);
+    frame.render_widget(hint.alignment(Alignment::Left), hint_area);
+}
 
+fn render_process_overlays(frame: &mut ratatui::Frame, area: Rect, app: &AppState) {
     // Kill confirmation overlay
-    if let Some((pid, name)) = &app.proc_kill_confirm {
-        let popup = centered_rect(54, 5, area);
-        frame.render_widget(Clear, popup);
-        let block = Block::default()
-            .title(" Kill Process ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Red));
-        let inner_popup = block.inner(popup);
-        frame.render_widget(block, popup);
-        let text = vec![
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled(
-                    trim_to(name, 28),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(format!("  PID {}", pid)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled(
-                    "y",
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(" SIGTERM  "),
-                Span::styled(
-                    "K",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(" SIGKILL  "),
-                Span::styled("n/Esc", Style::default().fg(Color::Yellow)),
-                Span::raw(" cancel"),
-            ]),
-        ];
-        frame.render_widget(Paragraph::new(text), inner_popup);
+    if let Some(confirm) = &app.proc_kill_confirm {
+        render_kill_confirm(frame, area, confirm);
     }
 
     // Restart confirmation overlay
-    if let Some((pid, name, exe, _)) = &app.proc_restart_confirm {
-        let popup = centered_rect(60, 6, area);
-        frame.render_widget(Clear, popup);
-        let block = Block::default()
-            .title(" Restart Process ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow));
-        let inner_popup = block.inner(popup);
-        frame.render_widget(block, popup);
-        let exe_str = exe.to_string_lossy();
-        let text = vec![
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled(
-                    trim_to(name, 28),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(format!("  PID {}", pid)),
-            ]),
-            Line::from(vec![
-                Span::styled("  exe: ", Style::default().fg(Color::Gray)),
-                Span::styled(trim_to(&exe_str, 46), Style::default().fg(Color::DarkGray)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("  SIGTERM then respawn  "),
-                Span::styled(
-                    "y",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
+    if let Some(confirm) = &app.proc_restart_confirm {
+        render_restart_confirm(frame, area, confirm);
+    }
+
+    if let Some(menu) = &app.proc_action {
+        render_proc_action_menu(frame, area, menu);
+    }
+}
+
+fn render_restart_confirm(frame: &mut ratatui::Frame, area: Rect, confirm: &RestartConfirm) {
+    let popup = centered_rect(70, 10, area);
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(" Restart Process ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner_popup = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("  {label:<6}"), Style::default().fg(Color::Gray)),
+            Span::styled(trim_to(&value, 58), Style::default().fg(Color::DarkGray)),
+        ])
+    };
+    let mut text = vec![Line::from(vec![
+        Span::raw("  "),
+        Span::styled(
+            trim_to(&confirm.name, 28),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("  PID {}", confirm.pid)),
+    ])];
+    match &confirm.plan {
+        Ok(plan) => {
+            text.push(field("exe", plan.exe.display().to_string()));
+            text.push(field("args", plan.argv[1..].join(" ")));
+            text.push(field("cwd", plan.cwd.display().to_string()));
+            text.push(field(
+                "as",
+                format!(
+                    "{} · {} env vars · new session",
+                    plan.user,
+                    plan.environ.len()
                 ),
+            ));
+            text.push(field("log", plan.log_path.display().to_string()));
+            text.push(Line::from(""));
+            text.push(Line::from(vec![
+                Span::raw("  SIGTERM, wait for exit, relaunch  "),
+                key("y"),
                 Span::raw(" confirm  "),
-                Span::styled("n/Esc", Style::default().fg(Color::Yellow)),
+                key("n/Esc"),
                 Span::raw(" cancel"),
-            ]),
-        ];
-        frame.render_widget(Paragraph::new(text), inner_popup);
+            ]));
+        }
+        Err(reason) => {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(
+                "  Can't restart faithfully:",
+                Style::default().fg(Color::Red),
+            )));
+            text.push(Line::from(format!("  {reason}")));
+            text.push(Line::from(""));
+            text.push(Line::from(vec![
+                Span::raw("  "),
+                key("n/Esc"),
+                Span::raw(" close"),
+            ]));
+        }
     }
+    frame.render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), inner_popup);
+}
+
+/// Targets listed in the kill popup before "… and N more".
+const KILL_LIST_ROWS: usize = 8;
+
+fn render_kill_confirm(frame: &mut ratatui::Frame, area: Rect, confirm: &KillConfirm) {
+    let listed = if confirm.targets.len() > 1 {
+        confirm.targets.len().min(KILL_LIST_ROWS) + 1
+    } else {
+        0
+    };
+    let popup = centered_rect(60, 7 + listed as u16, area);
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(format!(" Kill {} ", confirm.scope.label()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+    let inner_popup = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let key = |k: &'static str, color: Color| {
+        Span::styled(k, Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    let mut text = vec![
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                trim_to(&confirm.name, 28),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("  PID {}", confirm.pid)),
+        ]),
+        Line::from(vec![
+            Span::styled("  scope: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                match confirm.targets.len() {
+                    1 => format!("{} (1 process)", confirm.scope.label()),
+                    n => format!("{} ({n} processes)", confirm.scope.label()),
+                },
+                Style::default().fg(Color::White),
+            ),
+            Span::raw("  "),
+            key("Tab", Color::Yellow),
+            Span::raw(" change"),
+        ]),
+    ];
+    if listed > 0 {
+        text.push(Line::from(""));
+        for (pid, name) in confirm.targets.iter().take(KILL_LIST_ROWS) {
+            text.push(Line::from(Span::styled(
+                format!("  {pid:>8}  {}", trim_to(name, 40)),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        if confirm.targets.len() > KILL_LIST_ROWS {
+            text.push(Line::from(Span::styled(
+                format!("  … and {} more", confirm.targets.len() - KILL_LIST_ROWS),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+    text.push(Line::from(""));
+    text.push(match &confirm.refused {
+        Some(reason) => Line::from(vec![
+            Span::styled(
+                format!("  refused: {reason}  "),
+                Style::default().fg(Color::Red),
+            ),
+            key("n/Esc", Color::Yellow),
+            Span::raw(" cancel"),
+        ]),
+        None => Line::from(vec![
+            Span::raw("  "),
+            key("y", Color::Green),
+            Span::raw(" SIGTERM  "),
+            key("K", Color::Red),
+            Span::raw(" SIGKILL  "),
+            key("n/Esc", Color::Yellow),
+            Span::raw(" cancel"),
+        ]),
+    });
+    frame.render_widget(Paragraph::new(text), inner_popup);
+}
+
+fn render_proc_action_menu(frame: &mut ratatui::Frame, area: Rect, menu: &ProcActionMenu) {
+    let popup = centered_rect(60, MENU_SIGNALS.len() as u16 + 8, area);
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(" Process Actions ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner_popup = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let mut text = vec![
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                trim_to(&menu.name, 28),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("  PID {}", menu.pid)),
+        ]),
+        Line::from(""),
+    ];
+    let item = |idx: usize, label: String, note: String| {
+        let style = if idx == menu.cursor {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        Line::from(vec![
+            Span::styled(format!("  {label:<16}"), style),
+            Span::styled(format!(" {note}"), Style::default().fg(Color::DarkGray)),
+        ])
+    };
+    for (i, (sig, what)) in MENU_SIGNALS.iter().enumerate() {
+        text.push(item(i, format!("SIG{sig}"), what.to_string()));
+    }
+    text.push(item(
+        MENU_NICE,
+        format!("nice {:+}", menu.nice),
+        "←/→ adjust (-20 highest … 19 lowest)".to_string(),
+    ));
+    let io = match menu.io_class {
+        IoClass::Idle => "io idle".to_string(),
+        class => format!("io {} {}", class.label(), menu.io_level),
+    };
+    text.push(item(
+        MENU_IONICE,
+        io,
+        "←/→ level (0 highest) · c class".to_string(),
+    ));
+    text.push(Line::from(""));
+
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    text.push(if menu.confirm {
+        Line::from(vec![
+            Span::styled(
+                format!("  {}? ", menu.action().describe()),
+                Style::default().fg(Color::White),
+            ),
+            key("y"),
+            Span::raw(" confirm  "),
+            key("n/Esc"),
+            Span::raw(" back"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::raw("  "),
+            key("↑/↓"),
+            Span::raw(" choose  "),
+            key("Enter"),
+            Span::raw(" apply  "),
+            key("Esc"),
+            Span::raw(" cancel"),
+        ])
+    });
+    frame.render_widget(Paragraph::new(text), inner_popup);
 }
@@ -0,0 +1,165 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use crate::svcaction::service_action_status;
+use crate::svcinfo::ServiceDetail;
+use crate::types::AppState;
+use crate::ui::process_detail::{field, section, unavailable};
+use crate::utils::format_bytes;
+
+/// systemd prints byte limits as plain numbers and "infinity" when unset.
+fn limit(value: &str) -> String {
+    match value.parse::<u64>() {
+        Ok(bytes) => format_bytes(bytes),
+        Err(_) if value.is_empty() => "-".to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+fn units(list: &[String]) -> String {
+    if list.is_empty() {
+        "-".to_string()
+    } else {
+        list.join(" ")
+    }
+}
+
+fn tree(lines: &mut Vec<Line<'static>>, title: &str, entries: &Result<Vec<String>, String>) {
+    lines.push(Line::from(""));
+    lines.push(section(title.to_string()));
+    match entries {
+        Ok(entries) if entries.is_empty() => lines.push(unavailable("none")),
+        Ok(entries) => lines.extend(entries.iter().map(|e| Line::from(format!("  {e}")))),
+        Err(e) => lines.push(unavailable(e)),
+    }
+}
+
+fn detail_lines(d: &ServiceDetail) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if d.exec_start.is_empty() {
+        lines.push(field("ExecStart", "-".to_string()));
+    }
+    for (i, cmd) in d.exec_start.iter().enumerate() {
+        lines.push(field(if i == 0 { "ExecStart" } else { "" }, cmd.clone()));
+    }
+    lines.push(field(
+        "Main PID",
+        d.main_pid
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+    ));
+    lines.push(field(
+        "Unit file",
+        format!("{} ({})", d.fragment_path, d.unit_file_state),
+    ));
+    lines.push(field(
+        "Restart",
+        if d.restart_sec.is_empty() {
+            d.restart.clone()
+        } else {
+            format!("{} (after {})", d.restart, d.restart_sec)
+        },
+    ));
+    lines.push(field(
+        "Limits",
+        format!(
+            "memory max {} · high {} · tasks max {}",
+            limit(&d.memory_max),
+            limit(&d.memory_high),
+            if d.tasks_max.is_empty() {
+                "-"
+            } else {
+                &d.tasks_max
+            }
+        ),
+    ));
+
+    lines.push(Line::from(""));
+    lines.push(section("Relations".to_string()));
+    lines.push(field("After", units(&d.after)));
+    lines.push(field("Requires", units(&d.requires)));
+    lines.push(field("Wants", units(&d.wants)));
+    lines.push(field("WantedBy", units(&d.wanted_by)));
+    lines.push(field("RequiredBy", units(&d.required_by)));
+
+    tree(&mut lines, "Dependencies", &d.dependencies);
+    tree(
+        &mut lines,
+        "Reverse dependencies (pulled in by)",
+        &d.reverse_dependencies,
+    );
+    tree(&mut lines, "Journal (latest)", &d.journal);
+    lines
+}
+
+pub fn render_service_detail(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
+    let unit = app.service_detail_unit.clone().unwrap_or_default();
+    let state = app.service_detail_state.inner.lock().unwrap();
+    let (title, lines) = match &state.detail {
+        Some(Ok(d)) => (format!("{} — {}", d.unit, d.description), detail_lines(d)),
+        Some(Err(e)) => (unit, vec![unavailable(e)]),
+        None => (unit, vec![unavailable("loading…")]),
+    };
+    let reloading = state.running && state.detail.is_some();
+    drop(state);
+
+    let title = if app.service_detail_user {
+        format!("{title} (user)")
+    } else {
+        title
+    };
+    let title = if reloading {
+        format!("{title}  reloading…")
+    } else {
+        title
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let body = Rect {
+        height: inner.height.saturating_sub(1),
+        ..inner
+    };
+    let rows: u16 = lines
+        .iter()
+        .map(|l| (l.width().max(1) as u16).div_ceil(body.width.max(1)))
+        .sum();
+    let max_scroll = rows.saturating_sub(body.height);
+    app.service_detail_scroll = app.service_detail_scroll.min(max_scroll);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((app.service_detail_scroll, 0)),
+        body,
+    );
+
+    let hint = match service_action_status(&app.service_action_state) {
+        Some(Ok(msg)) => Line::from(Span::styled(msg, Style::default().fg(Color::Green))),
+        Some(Err(msg)) => Line::from(Span::styled(msg, Style::default().fg(Color::Red))),
+        None => Line::from(vec![
+            Span::styled("↑/↓ PgUp/PgDn", Style::default().fg(Color::Yellow)),
+            Span::raw(" scroll · "),
+            Span::styled("l", Style::default().fg(Color::Yellow)),
+            Span::raw(" logs · "),
+            Span::styled("s", Style::default().fg(Color::Yellow)),
+            Span::raw(" actions · "),
+            Span::styled("r", Style::default().fg(Color::Yellow)),
+            Span::raw(" reload · "),
+            Span::styled("Esc/←", Style::default().fg(Color::Yellow)),
+            Span::raw(" back to list"),
+        ]),
+    };
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(hint), hint_area);
+}
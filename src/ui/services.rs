@@ -1,13 +1,24 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap};
 
-use crate::services::{filtered_service_rows, service_filter_label};
-use crate::types::{AppState, ServiceHealth};
-use crate::utils::trim_to;
+use crate::services::{
+    filtered_service_rows, service_filter_label, service_scope_label, service_sort_label,
+};
+use crate::svcaction::{action_command, service_action_status, ServiceActionMenu, SERVICE_ACTIONS};
+use crate::types::{AppState, ServiceHealth, ServiceScope};
+use crate::ui::render_service_detail;
+use crate::utils::{centered_rect, format_bytes, format_rate, trim_to};
 
 pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
+    if app.service_detail_unit.is_some() {
+        render_service_detail(frame, area, app);
+        if let Some(menu) = &app.service_action {
+            render_service_action_menu(frame, area, menu);
+        }
+        return;
+    }
     let state = app.service_state.inner.lock().unwrap();
 
     if let Some(msg) = &state.unsupported {
@@ -26,7 +37,12 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
         return;
     }
 
-    let rows = filtered_service_rows(&state.rows, app.service_filter, &app.service_search);
+    let rows = filtered_service_rows(
+        &state.rows,
+        app.service_filter,
+        app.service_sort,
+        &app.service_search,
+    );
     let error = state.error.clone();
     let failed = state
         .rows
@@ -57,23 +73,27 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
             )
         })
         .unwrap_or_else(|| "not loaded yet".to_string());
+    let source = match app.service_scope {
+        ServiceScope::User => "polling systemctl --user",
+        ServiceScope::Both if state.live => "system live via D-Bus, user polled",
+        _ if state.live => "live via D-Bus",
+        _ => "polling systemctl",
+    };
     let selected = rows.get(app.service_scroll as usize).cloned();
     drop(state);
 
+    let view = format!(
+        "{}, {}, by {}",
+        service_scope_label(app.service_scope),
+        service_filter_label(app.service_filter),
+        service_sort_label(app.service_sort)
+    );
     let title = if app.service_search_active {
-        format!(
-            "Services ({})  /{}_",
-            service_filter_label(app.service_filter),
-            trim_to(&app.service_search, 18)
-        )
+        format!("Services ({view})  /{}_", trim_to(&app.service_search, 18))
     } else if app.service_search.is_empty() {
-        format!("Services ({})", service_filter_label(app.service_filter))
+        format!("Services ({view})")
     } else {
-        format!(
-            "Services ({})  /{}",
-            service_filter_label(app.service_filter),
-            trim_to(&app.service_search, 18)
-        )
+        format!("Services ({view})  /{}", trim_to(&app.service_search, 18))
     };
     let block = Block::default()
         .title(title)
@@ -86,7 +106,7 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
         .constraints([
             Constraint::Length(2),
             Constraint::Min(6),
-            Constraint::Length(5),
+            Constraint::Length(6),
         ])
         .split(inner);
 
@@ -113,7 +133,10 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
         ),
         Span::raw(active.to_string()),
         Span::raw("  •  "),
-        Span::styled(updated, Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("{updated} ({source})"),
+            Style::default().fg(Color::Gray),
+        ),
         if app.service_search_active {
             Span::styled(
                 format!("  •  typing /{}", trim_to(&app.service_search, 20)),
@@ -144,6 +167,8 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
     let offset = selected_idx.saturating_sub(visible.saturating_sub(1));
     let slice = &rows[offset..rows.len().min(offset + visible.max(1))];
 
+    // Merged view: say which manager each unit belongs to.
+    let show_scope = app.service_scope == ServiceScope::Both;
     let table_rows = slice.iter().enumerate().map(|(i, row)| {
         let absolute_idx = offset + i;
         let base_style = match row.health {
@@ -159,34 +184,65 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
         } else {
             base_style
         };
-        Row::new(vec![
-            Cell::from(trim_to(&row.name, 26)),
-            Cell::from(format!("{} ({})", row.active_state, row.sub_state)),
+        let usage = &row.usage;
+        let mut cells = Vec::with_capacity(9);
+        if show_scope {
+            cells.push(Cell::from(if row.user { "user" } else { "system" }));
+        }
+        cells.extend([
+            Cell::from(trim_to(&row.name, 24)),
+            Cell::from(trim_to(
+                &format!("{} ({})", row.active_state, row.sub_state),
+                18,
+            )),
+            Cell::from(usage.mem_bytes.map(format_bytes).unwrap_or_else(dash)),
+            Cell::from(
+                usage
+                    .cpu_x10
+                    .map(|c| format!("{:.1}", c as f64 / 10.0))
+                    .unwrap_or_else(dash),
+            ),
+            Cell::from(usage.io_bps.map(format_rate).unwrap_or_else(dash)),
             Cell::from(row.restarts.to_string()),
             Cell::from(trim_to(&row.last_change, 24)),
             Cell::from(trim_to(&row.description, 28)),
-        ])
-        .style(style)
+        ]);
+        Row::new(cells).style(style)
     });
 
-    let table = Table::new(
-        table_rows,
-        [
-            Constraint::Length(26),
-            Constraint::Length(20),
-            Constraint::Length(8),
-            Constraint::Length(24),
-            Constraint::Min(18),
-        ],
-    )
-    .header(
-        Row::new(vec!["UNIT", "STATE", "RESTARTS", "LAST CHANGE", "DESC"]).style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        ),
-    )
-    .block(Block::default().borders(Borders::ALL).title("Units"));
+    let mut widths = vec![
+        Constraint::Length(24),
+        Constraint::Length(18),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(24),
+        Constraint::Min(10),
+    ];
+    let mut header = vec![
+        "UNIT",
+        "STATE",
+        "MEM",
+        "CPU%",
+        "I/O",
+        "RESTARTS",
+        "LAST CHANGE",
+        "DESC",
+    ];
+    if show_scope {
+        widths.insert(0, Constraint::Length(6));
+        header.insert(0, "SCOPE");
+    }
+    let table = Table::new(table_rows, widths)
+        .header(
+            Row::new(header).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(Block::default().borders(Borders::ALL).title("Units"));
     frame.render_widget(table, chunks[1]);
 
     let detail_lines = if let Some(error) = error {
@@ -208,6 +264,9 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
                 Span::raw("  •  "),
                 Span::styled("Load ", Style::default().fg(Color::Gray)),
                 Span::raw(row.load_state),
+                Span::raw("  •  "),
+                Span::styled("Manager ", Style::default().fg(Color::Gray)),
+                Span::raw(if row.user { "user" } else { "system" }),
             ]),
             Line::from(vec![
                 Span::styled("State: ", Style::default().fg(Color::Gray)),
@@ -215,15 +274,22 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
                 Span::raw("  •  "),
                 Span::styled("Restarts ", Style::default().fg(Color::Gray)),
                 Span::raw(row.restarts.to_string()),
+                Span::raw("  •  "),
+                Span::styled("Tasks ", Style::default().fg(Color::Gray)),
+                Span::raw(row.usage.tasks.map(|t| t.to_string()).unwrap_or_else(dash)),
             ]),
             Line::from(vec![
                 Span::styled("When: ", Style::default().fg(Color::Gray)),
                 Span::raw(row.last_change),
             ]),
-            Line::from(vec![
-                Span::styled("Hint: ", Style::default().fg(Color::Gray)),
-                Span::raw("press Enter or l to tail logs for this service"),
-            ]),
+            match service_action_status(&app.service_action_state) {
+                Some(Ok(msg)) => Line::from(Span::styled(msg, Style::default().fg(Color::Green))),
+                Some(Err(msg)) => Line::from(Span::styled(msg, Style::default().fg(Color::Red))),
+                None => Line::from(vec![
+                    Span::styled("Hint: ", Style::default().fg(Color::Gray)),
+                    Span::raw("Enter or l tails logs · i details · s start/stop/restart/reload/enable/disable"),
+                ]),
+            },
         ]
     } else {
         let mut lines = vec![Line::from("No services match the current filter.")];
@@ -235,7 +301,9 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
                 "Press / to search by service name or description.",
             ));
         }
-        lines.push(Line::from("Press Tab to change the filter."));
+        lines.push(Line::from(
+            "Press Tab to change the filter, u to switch system/user manager.",
+        ));
         lines
     };
     frame.render_widget(
@@ -244,4 +312,85 @@ pub fn render_services(frame: &mut ratatui::Frame, area: Rect, app: &mut AppStat
             .wrap(Wrap { trim: true }),
         chunks[2],
     );
+
+    if let Some(menu) = &app.service_action {
+        render_service_action_menu(frame, area, menu);
+    }
+}
+
+fn render_service_action_menu(frame: &mut ratatui::Frame, area: Rect, menu: &ServiceActionMenu) {
+    let popup = centered_rect(60, SERVICE_ACTIONS.len() as u16 + 6, area);
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(" Service Actions ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let mut text = vec![
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                trim_to(&menu.unit, 50),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    for (i, action) in SERVICE_ACTIONS.iter().enumerate() {
+        let style = if i == menu.cursor {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        text.push(Line::from(vec![
+            Span::styled(format!("  {:<10}", action.verb()), style),
+            Span::styled(
+                format!(" {}", action.describe()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+    text.push(Line::from(""));
+    text.push(if menu.confirm {
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {}? ",
+                    action_command(menu.action(), &trim_to(&menu.unit, 24), menu.user)
+                ),
+                Style::default().fg(Color::White),
+            ),
+            key("y"),
+            Span::raw(" run  "),
+            key("n/Esc"),
+            Span::raw(" back"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::raw("  "),
+            key("↑/↓"),
+            Span::raw(" choose  "),
+            key("Enter"),
+            Span::raw(" apply  "),
+            key("Esc"),
+            Span::raw(" cancel"),
+        ])
+    });
+    frame.render_widget(Paragraph::new(text), inner);
+}
+
+fn dash() -> String {
+    "-".to_string()
 }
@@ -0,0 +1,178 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
+
+use crate::types::{AppState, ServiceHealth};
+use crate::utils::trim_to;
+
+pub fn render_timers(frame: &mut ratatui::Frame, area: Rect, app: &mut AppState) {
+    let state = app.timer_state.inner.lock().unwrap();
+
+    if let Some(msg) = &state.unsupported {
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from("Timers"),
+                Line::from(""),
+                Line::from(msg.clone()),
+            ])
+            .block(Block::default().title("Timers").borders(Borders::ALL))
+            .alignment(ratatui::prelude::Alignment::Center),
+            area,
+        );
+        return;
+    }
+
+    let rows = state.rows.clone();
+    let error = state.error.clone();
+    let failed = rows
+        .iter()
+        .filter(|row| row.health == ServiceHealth::Critical)
+        .count();
+    let inactive = rows
+        .iter()
+        .filter(|row| row.health == ServiceHealth::Warning)
+        .count();
+    let updated = state
+        .last_updated_at
+        .and_then(|t| t.elapsed().ok())
+        .map(|d| format!("updated {}s ago", d.as_secs()))
+        .unwrap_or_else(|| {
+            if state.running {
+                "loading…".to_string()
+            } else {
+                "not loaded yet".to_string()
+            }
+        });
+    drop(state);
+
+    let block = Block::default()
+        .title(format!("Timers ({})", rows.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue));
+    frame.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(6),
+            Constraint::Length(5),
+        ])
+        .split(inner);
+
+    let summary = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "Last run failed ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(failed.to_string()),
+        Span::raw("  "),
+        Span::styled(
+            "Inactive ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(inactive.to_string()),
+        Span::raw("  •  "),
+        Span::styled(updated, Style::default().fg(Color::Gray)),
+    ]));
+    frame.render_widget(summary, chunks[0]);
+
+    let visible = chunks[1].height.saturating_sub(3) as usize;
+    let selected_idx = (app.timer_scroll as usize).min(rows.len().saturating_sub(1));
+    app.timer_scroll = selected_idx as u16;
+    let offset = selected_idx.saturating_sub(visible.saturating_sub(1));
+    let slice = &rows[offset..rows.len().min(offset + visible.max(1))];
+
+    let table_rows = slice.iter().enumerate().map(|(i, row)| {
+        let base_style = match row.health {
+            ServiceHealth::Critical => Style::default().fg(Color::Red),
+            ServiceHealth::Warning => Style::default().fg(Color::Yellow),
+            ServiceHealth::Healthy => Style::default().fg(Color::Green),
+        };
+        let style = if offset + i == selected_idx {
+            base_style
+                .bg(Color::White)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            base_style
+        };
+        Row::new(vec![
+            Cell::from(trim_to(&row.name, 24)),
+            Cell::from(trim_to(&row.next_elapse, 24)),
+            Cell::from(trim_to(&row.last_trigger, 24)),
+            Cell::from(trim_to(&row.unit, 24)),
+            Cell::from(trim_to(&row.unit_result, 12)),
+        ])
+        .style(style)
+    });
+
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Length(24),
+            Constraint::Length(24),
+            Constraint::Length(24),
+            Constraint::Length(24),
+            Constraint::Min(8),
+        ],
+    )
+    .header(
+        Row::new(vec!["TIMER", "NEXT", "LAST", "SERVICE", "RESULT"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Timers"));
+    frame.render_widget(table, chunks[1]);
+
+    let detail_lines = if let Some(error) = error {
+        vec![
+            Line::from(vec![
+                Span::styled(
+                    "Timer refresh failed: ",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(error),
+            ]),
+            Line::from("Press r to retry."),
+        ]
+    } else if let Some(row) = rows.get(selected_idx) {
+        vec![
+            Line::from(vec![
+                Span::styled("Selected: ", Style::default().fg(Color::Gray)),
+                Span::raw(row.name.clone()),
+                Span::raw(" ("),
+                Span::raw(row.active_state.clone()),
+                Span::raw(")  •  "),
+                Span::raw(row.description.clone()),
+            ]),
+            Line::from(vec![
+                Span::styled("Service: ", Style::default().fg(Color::Gray)),
+                Span::raw(row.unit.clone()),
+                Span::raw("  •  "),
+                Span::styled("State ", Style::default().fg(Color::Gray)),
+                Span::raw(row.unit_state.clone()),
+                Span::raw("  •  "),
+                Span::styled("Result ", Style::default().fg(Color::Gray)),
+                Span::raw(row.unit_result.clone()),
+            ]),
+            Line::from(vec![
+                Span::styled("Hint: ", Style::default().fg(Color::Gray)),
+                Span::raw("Enter or l tails the service's logs · r refresh"),
+            ]),
+        ]
+    } else {
+        vec![Line::from("No timers loaded.")]
+    };
+    frame.render_widget(
+        Paragraph::new(detail_lines)
+            .block(Block::default().borders(Borders::ALL).title("Detail"))
+            .wrap(Wrap { trim: true }),
+        chunks[2],
+    );
+}
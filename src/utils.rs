@@ -1,8 +1,11 @@
 use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use ratatui::layout::Rect;
 use ratatui::style::Color;
 
+use crate::config::config;
+
 // ── Numeric helpers ───────────────────────────────────────────────────────────
 
 pub fn percent(used: u64, total: u64) -> f64 {
@@ -14,9 +17,10 @@ pub fn percent(used: u64, total: u64) -> f64 {
 }
 
 pub fn color_for_pct(pct: f64) -> Color {
-    if pct >= 90.0 {
+    let thresholds = &config().thresholds;
+    if pct >= thresholds.crit_pct {
         Color::Red
-    } else if pct >= 75.0 {
+    } else if pct >= thresholds.warn_pct {
         Color::Yellow
     } else {
         Color::Green
@@ -96,6 +100,109 @@ pub fn format_rate(bps: u64) -> String {
     }
 }
 
+// ── Time formatters ───────────────────────────────────────────────────────────
+
+pub fn unix_ms_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Compact elapsed time: `45s`, `3m12s`, `2h05m`, `4d03h`.
+pub fn format_duration_short(secs: u64) -> String {
+    if secs >= 86400 {
+        format!("{}d{:02}h", secs / 86400, (secs % 86400) / 3600)
+    } else if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SS` for `secs` since the epoch, already shifted into
+/// the wanted zone, and the weekday (0 = Sunday).
+fn civil_time(secs: i64) -> (String, usize) {
+    let days = secs.div_euclid(86400);
+    let tod = secs.rem_euclid(86400);
+
+    // Civil-from-days (Howard Hinnant), valid for the whole u64-ms range we see.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let text = format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        tod / 3600,
+        (tod % 3600) / 60,
+        tod % 60
+    );
+    // 1970-01-01 was a Thursday.
+    (text, (days + 4).rem_euclid(7) as usize)
+}
+
+/// `YYYY-MM-DD HH:MM:SSZ`.
+pub fn format_utc_timestamp(unix_ms: u64) -> String {
+    format!("{}Z", civil_time((unix_ms / 1000) as i64).0)
+}
+
+/// UTC offset in seconds and zone abbreviation in effect at `secs`, from
+/// the C library's zone rules (`TZ`, `/etc/localtime`).
+#[cfg(unix)]
+fn local_zone(secs: i64) -> (i64, String) {
+    let t = secs as libc::time_t;
+    // SAFETY: localtime_r only writes the tm we hand it; tm_zone points at
+    // static zone data that outlives this call.
+    unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&t, &mut tm).is_null() {
+            return (0, "UTC".to_string());
+        }
+        let zone = if tm.tm_zone.is_null() {
+            "UTC".to_string()
+        } else {
+            std::ffi::CStr::from_ptr(tm.tm_zone)
+                .to_string_lossy()
+                .into_owned()
+        };
+        (tm.tm_gmtoff as i64, zone)
+    }
+}
+
+#[cfg(not(unix))]
+fn local_zone(_secs: i64) -> (i64, String) {
+    (0, "UTC".to_string())
+}
+
+/// `YYYY-MM-DD HH:MM:SS` in local time, as journalctl's `short-iso` output
+/// showed it before entries were read as JSON.
+pub fn format_local_timestamp(unix_ms: u64) -> String {
+    let secs = (unix_ms / 1000) as i64;
+    civil_time(secs + local_zone(secs).0).0
+}
+
+/// `Tue 2023-11-14 22:13:20 UTC` in local time, the way `systemctl show`
+/// prints timestamps.
+pub fn format_systemd_timestamp(unix_ms: u64) -> String {
+    let secs = (unix_ms / 1000) as i64;
+    let (offset, zone) = local_zone(secs);
+    systemd_timestamp_in(secs, offset, &zone)
+}
+
+fn systemd_timestamp_in(secs: i64, offset: i64, zone: &str) -> String {
+    const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    let (text, weekday) = civil_time(secs + offset);
+    format!("{} {text} {zone}", DAYS[weekday])
+}
+
 // ── Layout helpers ────────────────────────────────────────────────────────────
 
 pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
@@ -108,3 +215,30 @@ pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
         height: height.min(area.height),
     }
 }
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_match_systemctl_and_utc_formats() {
+        assert_eq!(
+            format_utc_timestamp(1_700_000_000_123),
+            "2023-11-14 22:13:20Z"
+        );
+        assert_eq!(
+            systemd_timestamp_in(1_700_000_000, 0, "UTC"),
+            "Tue 2023-11-14 22:13:20 UTC"
+        );
+        assert_eq!(
+            systemd_timestamp_in(1_700_000_000, 3600, "CET"),
+            "Tue 2023-11-14 23:13:20 CET"
+        );
+        assert_eq!(
+            systemd_timestamp_in(1_699_999_200, -8 * 3600, "PST"),
+            "Tue 2023-11-14 14:00:00 PST"
+        );
+    }
+}